@@ -0,0 +1,35 @@
+use std::io::Result;
+use std::time::{Duration, Instant};
+
+use nalgebra::Rotation3;
+use winterm::wireframe::{self, Camera};
+use winterm::{Color, Draw, Key, Rect, Window};
+
+fn main() -> Result<()> {
+    let mut window = Window::new(40, 60)?;
+    let camera = Camera::new([0., 1., 2.5].into(), [0., 0., 0.].into());
+    let start = Instant::now();
+    loop {
+        window.poll_events()?;
+        if window.get_key(Key::Esc) {
+            break;
+        }
+        let angle = start.elapsed().as_secs_f32();
+        let rotation = Rotation3::from_euler_angles(angle * 0.3, angle, 0.);
+        let vertices = wireframe::CUBE_VERTICES.map(|vertex| rotation * vertex);
+        window.fill_rect(
+            Rect::new(0, 0, window.height(), window.width()),
+            Color::Black,
+        );
+        wireframe::draw_wireframe(
+            &mut window,
+            &camera,
+            &vertices,
+            &wireframe::CUBE_EDGES,
+            Color::Green,
+        );
+        window.redraw()?;
+        std::thread::sleep(Duration::from_millis(16));
+    }
+    Ok(())
+}
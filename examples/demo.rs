@@ -1,9 +1,7 @@
-use crossterm::{
-    event::{self, KeyCode},
-    style::Color,
-    terminal, Result,
-};
-use winterm::Window;
+use std::io::Result;
+
+use crossterm::{event, terminal};
+use winterm::{Color, Key, Window};
 
 struct Player {
     x: u16,
@@ -30,23 +28,23 @@ fn main() -> Result<()> {
     let mut player = Player { x: 12, y: 12 };
     loop {
         window.poll_events()?;
-        if window.get_key(KeyCode::Esc) {
+        if window.get_key(Key::Esc) {
             break;
         }
         window.set_pixel(player.y, player.x, background_color);
-        if player.y > 0 && (window.get_key(KeyCode::Up) || window.get_key(KeyCode::Char('w'))) {
+        if player.y > 0 && (window.get_key(Key::Up) || window.get_key(Key::Char('w'))) {
             player.y -= 1;
         }
         if player.y < window.height() - 1
-            && (window.get_key(KeyCode::Down) || window.get_key(KeyCode::Char('s')))
+            && (window.get_key(Key::Down) || window.get_key(Key::Char('s')))
         {
             player.y += 1;
         }
-        if player.x > 0 && (window.get_key(KeyCode::Left) || window.get_key(KeyCode::Char('a'))) {
+        if player.x > 0 && (window.get_key(Key::Left) || window.get_key(Key::Char('a'))) {
             player.x -= 1;
         }
         if player.x < window.width() - 1
-            && (window.get_key(KeyCode::Right) || window.get_key(KeyCode::Char('d')))
+            && (window.get_key(Key::Right) || window.get_key(Key::Char('d')))
         {
             player.x += 1;
         }
@@ -0,0 +1,29 @@
+use std::env;
+use std::io::Result;
+
+use winterm::{stream::FrameReceiver, Draw, Key, Window};
+
+fn main() -> Result<()> {
+    let address = env::args()
+        .nth(1)
+        .unwrap_or_else(|| "127.0.0.1:7878".to_string());
+    let mut receiver = FrameReceiver::connect(address)?;
+    let frame = receiver.receive()?;
+    let mut window = Window::new(frame.height(), frame.width())?;
+    window.blit(frame, 0, 0);
+    window.redraw()?;
+    loop {
+        window.poll_events()?;
+        if window.get_key(Key::Esc) {
+            break;
+        }
+        let frame = receiver.receive()?;
+        if (frame.height(), frame.width()) != (window.height(), window.width()) {
+            drop(window);
+            window = Window::new(frame.height(), frame.width())?;
+        }
+        window.blit(frame, 0, 0);
+        window.redraw()?;
+    }
+    Ok(())
+}
@@ -0,0 +1,254 @@
+//! ANSI escape sequences written by the window, queued with [`queue!`].
+//!
+//! The sequences are the ones of crossterm, which executes them through WinAPI on legacy Windows
+//! consoles.
+
+use std::fmt::{self, Display};
+use std::io::{self, Result, Write};
+
+use crate::Color;
+
+/// Command writing an ANSI escape sequence.
+pub(crate) trait Command {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result;
+
+    /// Executes the command on a legacy Windows console without support for ANSI escape
+    /// sequences.
+    #[cfg(all(windows, feature = "crossterm"))]
+    fn execute_winapi(&self) -> Result<()>;
+
+    #[cfg(all(windows, feature = "crossterm"))]
+    fn is_ansi_code_supported(&self) -> bool {
+        crossterm::ansi_support::supports_ansi()
+    }
+}
+
+/// Writes the commands to an [`io::Write`], returning an [`io::Result`].
+macro_rules! queue {
+    ($output:expr $(, $command:expr)* $(,)?) => {{
+        #[allow(unused_imports)]
+        use ::std::io::Write as _;
+        ::std::io::Result::Ok($output.by_ref())
+            $(.and_then(|output| {
+                $crate::ansi::queue_command(&mut *output, $command)?;
+                Ok(output)
+            }))*
+            .map(|_| ())
+    }};
+}
+pub(crate) use queue;
+
+/// Writes `command` to `output`, see [`queue!`].
+pub(crate) fn queue_command(output: &mut impl Write, command: impl Command) -> Result<()> {
+    #[cfg(all(windows, feature = "crossterm"))]
+    if !command.is_ansi_code_supported() {
+        output.flush()?;
+        return command.execute_winapi();
+    }
+    let mut adapter = Adapter {
+        output,
+        result: Ok(()),
+    };
+    command
+        .write_ansi(&mut adapter)
+        .map_err(|fmt::Error| match adapter.result {
+            Err(error) => error,
+            Ok(()) => io::Error::other("formatting an escape sequence failed"),
+        })
+}
+
+/// [`fmt::Write`] forwarding to an [`io::Write`], keeping the error it returns.
+struct Adapter<'a, W: Write> {
+    output: &'a mut W,
+    result: Result<()>,
+}
+
+impl<W: Write> fmt::Write for Adapter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.output.write_all(s.as_bytes()).map_err(|error| {
+            self.result = Err(error);
+            fmt::Error
+        })
+    }
+}
+
+/// Moves the cursor to a column and a row, starting at 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct MoveTo(pub(crate) u16, pub(crate) u16);
+
+impl Command for MoveTo {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        write!(f, "\x1B[{};{}H", self.1 + 1, self.0 + 1)
+    }
+
+    #[cfg(all(windows, feature = "crossterm"))]
+    fn execute_winapi(&self) -> Result<()> {
+        crossterm::Command::execute_winapi(&crossterm::cursor::MoveTo(self.0, self.1))
+    }
+}
+
+/// Prints text at the cursor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Print<T: Display>(pub(crate) T);
+
+impl<T: Display> Command for Print<T> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+
+    /// Never called, text being printed the same way on legacy consoles.
+    #[cfg(all(windows, feature = "crossterm"))]
+    fn execute_winapi(&self) -> Result<()> {
+        Ok(())
+    }
+
+    #[cfg(all(windows, feature = "crossterm"))]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
+
+/// Sets the color of the text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SetForegroundColor(pub(crate) Color);
+
+impl Command for SetForegroundColor {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        write_color(f, self.0, "39", "38")
+    }
+
+    #[cfg(all(windows, feature = "crossterm"))]
+    fn execute_winapi(&self) -> Result<()> {
+        crossterm::Command::execute_winapi(&crossterm::style::SetForegroundColor(self.0.into()))
+    }
+}
+
+/// Sets the color of the background of the text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SetBackgroundColor(pub(crate) Color);
+
+impl Command for SetBackgroundColor {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        write_color(f, self.0, "49", "48")
+    }
+
+    #[cfg(all(windows, feature = "crossterm"))]
+    fn execute_winapi(&self) -> Result<()> {
+        crossterm::Command::execute_winapi(&crossterm::style::SetBackgroundColor(self.0.into()))
+    }
+}
+
+/// Sets the foreground then the background color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SetColors(pub(crate) Color, pub(crate) Color);
+
+impl Command for SetColors {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        SetForegroundColor(self.0).write_ansi(f)?;
+        SetBackgroundColor(self.1).write_ansi(f)
+    }
+
+    #[cfg(all(windows, feature = "crossterm"))]
+    fn execute_winapi(&self) -> Result<()> {
+        SetForegroundColor(self.0).execute_winapi()?;
+        SetBackgroundColor(self.1).execute_winapi()
+    }
+}
+
+/// Writes the SGR sequence of `color`, `reset` for [`Color::Reset`] and prefixed by `set`
+/// otherwise.
+fn write_color(f: &mut impl fmt::Write, color: Color, reset: &str, set: &str) -> fmt::Result {
+    let ansi_value = match color {
+        Color::Reset => return write!(f, "\x1B[{reset}m"),
+        Color::Rgb { r, g, b } => return write!(f, "\x1B[{set};2;{r};{g};{b}m"),
+        Color::AnsiValue(value) => value,
+        Color::Black => 0,
+        Color::DarkRed => 1,
+        Color::DarkGreen => 2,
+        Color::DarkYellow => 3,
+        Color::DarkBlue => 4,
+        Color::DarkMagenta => 5,
+        Color::DarkCyan => 6,
+        Color::Grey => 7,
+        Color::DarkGrey => 8,
+        Color::Red => 9,
+        Color::Green => 10,
+        Color::Yellow => 11,
+        Color::Blue => 12,
+        Color::Magenta => 13,
+        Color::Cyan => 14,
+        Color::White => 15,
+    };
+    write!(f, "\x1B[{set};5;{ansi_value}m")
+}
+
+/// Clears the whole terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ClearAll;
+
+impl Command for ClearAll {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str("\x1B[2J")
+    }
+
+    #[cfg(all(windows, feature = "crossterm"))]
+    fn execute_winapi(&self) -> Result<()> {
+        crossterm::Command::execute_winapi(&crossterm::terminal::Clear(
+            crossterm::terminal::ClearType::All,
+        ))
+    }
+}
+
+/// Starts a synchronized update (DEC private mode 2026), terminals without support ignore it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BeginSynchronizedUpdate;
+
+impl Command for BeginSynchronizedUpdate {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str("\x1B[?2026h")
+    }
+
+    #[cfg(all(windows, feature = "crossterm"))]
+    fn execute_winapi(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Ends a synchronized update started with [`BeginSynchronizedUpdate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct EndSynchronizedUpdate;
+
+impl Command for EndSynchronizedUpdate {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str("\x1B[?2026l")
+    }
+
+    #[cfg(all(windows, feature = "crossterm"))]
+    fn execute_winapi(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ClearAll, MoveTo, Print, SetColors};
+    use crate::Color;
+
+    #[test]
+    fn commands_write_the_crossterm_sequences() {
+        let mut output = Vec::new();
+        queue!(
+            output,
+            MoveTo(2, 1),
+            SetColors(Color::Red, Color::Rgb { r: 1, g: 2, b: 3 }),
+            Print("a"),
+            SetColors(Color::Reset, Color::AnsiValue(42)),
+            ClearAll,
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "\x1B[2;3H\x1B[38;5;9m\x1B[48;2;1;2;3ma\x1B[39m\x1B[48;5;42m\x1B[2J",
+        );
+    }
+}
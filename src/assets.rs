@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::io::{self, ErrorKind};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+
+use crate::color::Quantizer;
+use crate::font::BitmapFont;
+use crate::{
+    canvas::{self, Canvas},
+    Color,
+};
+
+/// Lightweight reference to an asset of an [`Assets`] registry.
+pub struct Handle<T> {
+    index: usize,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Handle<T> {
+    fn new(index: usize) -> Self {
+        Handle {
+            index,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for Handle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Handle").field(&self.index).finish()
+    }
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Handle<T> {}
+
+impl<T> PartialEq for Handle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl<T> Eq for Handle<T> {}
+
+impl<T> Hash for Handle<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+    }
+}
+
+/// Registry loading the canvases, fonts and palettes once per path, referenced by handles passed
+/// to the draw calls, e.g. [`Draw::blit_asset`](crate::Draw::blit_asset).
+///
+/// ```no_run
+/// use winterm::Color;
+/// use winterm::{Assets, Draw, Window};
+///
+/// # let mut window = Window::new(36, 64)?;
+/// let mut assets = Assets::new();
+/// let player = assets.load_canvas("player.wtrm")?;
+/// // Loading the same file again returns the same handle.
+/// assert_eq!(assets.load_canvas("player.wtrm")?, player);
+/// let font = assets.load_font("font.wtrm", 8, 6)?;
+/// window.blit_asset(&assets, player, 4, 8);
+/// window.draw_asset_text(&assets, font, 0, 0, "Score: 42", Color::White);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct Assets {
+    canvases: Vec<Canvas>,
+    palettes: Vec<Quantizer>,
+    fonts: Vec<BitmapFont>,
+    canvas_paths: HashMap<PathBuf, Handle<Canvas>>,
+    font_paths: HashMap<PathBuf, Handle<BitmapFont>>,
+    palette_paths: HashMap<PathBuf, Handle<Quantizer>>,
+}
+
+impl Assets {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Assets::default()
+    }
+
+    /// Loads a canvas, or gets its handle if it was already loaded.
+    ///
+    /// The files are read with [`Canvas::load`], or as images with the `image` feature when their
+    /// extension is the one of an image format.
+    pub fn load_canvas(&mut self, path: impl AsRef<Path>) -> io::Result<Handle<Canvas>> {
+        let path = path.as_ref().canonicalize()?;
+        if let Some(&handle) = self.canvas_paths.get(&path) {
+            return Ok(handle);
+        }
+        let handle = self.add_canvas(canvas::open(&path)?);
+        self.canvas_paths.insert(path, handle);
+        Ok(handle)
+    }
+
+    /// Adds a canvas created by the application, e.g. with [`Canvas::from_fn`].
+    pub fn add_canvas(&mut self, canvas: Canvas) -> Handle<Canvas> {
+        self.canvases.push(canvas);
+        Handle::new(self.canvases.len() - 1)
+    }
+
+    /// Gets a canvas.
+    pub fn canvas(&self, handle: Handle<Canvas>) -> &Canvas {
+        &self.canvases[handle.index]
+    }
+
+    /// Gets a canvas mutably.
+    pub fn canvas_mut(&mut self, handle: Handle<Canvas>) -> &mut Canvas {
+        &mut self.canvases[handle.index]
+    }
+
+    /// Loads a font from a sheet of `glyph_height` by `glyph_width` glyphs, see [`BitmapFont`],
+    /// or gets its handle if it was already loaded, with the glyph size it was first loaded with.
+    ///
+    /// The file is read as a canvas, see [`Assets::load_canvas`].
+    ///
+    /// Returns an error if the glyphs are empty or wider than the sheet.
+    pub fn load_font(
+        &mut self,
+        path: impl AsRef<Path>,
+        glyph_height: u16,
+        glyph_width: u16,
+    ) -> io::Result<Handle<BitmapFont>> {
+        let path = path.as_ref().canonicalize()?;
+        if let Some(&handle) = self.font_paths.get(&path) {
+            return Ok(handle);
+        }
+        let sheet = canvas::open(&path)?;
+        if glyph_height == 0 || glyph_width == 0 || glyph_width > sheet.width() {
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                "the glyphs must be non-empty and fit in the width of the sheet",
+            ));
+        }
+        let handle = self.add_font(BitmapFont::new(sheet, glyph_height, glyph_width));
+        self.font_paths.insert(path, handle);
+        Ok(handle)
+    }
+
+    /// Adds a font created by the application.
+    pub fn add_font(&mut self, font: BitmapFont) -> Handle<BitmapFont> {
+        self.fonts.push(font);
+        Handle::new(self.fonts.len() - 1)
+    }
+
+    /// Gets a font.
+    pub fn font(&self, handle: Handle<BitmapFont>) -> &BitmapFont {
+        &self.fonts[handle.index]
+    }
+
+    /// Loads a palette, or gets its handle if it was already loaded.
+    ///
+    /// The file is read as a canvas, see [`Assets::load_canvas`], whose distinct colors are the
+    /// palette, in row order, e.g. a palette image with one pixel per color.
+    ///
+    /// Returns an error if the canvas has no pixels or more than 256 colors.
+    pub fn load_palette(&mut self, path: impl AsRef<Path>) -> io::Result<Handle<Quantizer>> {
+        let path = path.as_ref().canonicalize()?;
+        if let Some(&handle) = self.palette_paths.get(&path) {
+            return Ok(handle);
+        }
+        let mut colors: Vec<Color> = Vec::new();
+        for &color in canvas::open(&path)?.pixels() {
+            if !colors.contains(&color) {
+                if colors.len() == 256 {
+                    return Err(io::Error::new(
+                        ErrorKind::InvalidData,
+                        "more than 256 colors in the palette",
+                    ));
+                }
+                colors.push(color);
+            }
+        }
+        if colors.is_empty() {
+            return Err(io::Error::new(ErrorKind::InvalidData, "empty palette"));
+        }
+        let handle = self.add_palette(Quantizer::new(&colors));
+        self.palette_paths.insert(path, handle);
+        Ok(handle)
+    }
+
+    /// Adds a palette created by the application.
+    pub fn add_palette(&mut self, palette: Quantizer) -> Handle<Quantizer> {
+        self.palettes.push(palette);
+        Handle::new(self.palettes.len() - 1)
+    }
+
+    /// Gets a palette.
+    pub fn palette(&self, handle: Handle<Quantizer>) -> &Quantizer {
+        &self.palettes[handle.index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::io::ErrorKind;
+
+    use crate::Color;
+
+    use super::Assets;
+    use crate::{Canvas, Draw};
+
+    #[test]
+    fn draw_calls_take_handles() {
+        let directory = env::temp_dir().join(format!("winterm-assets-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        // 2x2 glyphs, 48 per row, 'A' being the 34th one.
+        let mut sheet = Canvas::new(4, 96, Color::Black);
+        sheet.set_pixel(0, 66, Color::White);
+        sheet.set_pixel(1, 67, Color::White);
+        let font_path = directory.join("font.wtrm");
+        sheet.save(&font_path).unwrap();
+        let sprite_path = directory.join("sprite.wtrm");
+        let green = Color::Rgb { r: 0, g: 255, b: 0 };
+        Canvas::new(2, 2, green).save(&sprite_path).unwrap();
+
+        let mut assets = Assets::new();
+        let font = assets.load_font(&font_path, 2, 2).unwrap();
+        assert_eq!(assets.load_font(&font_path, 2, 2).unwrap(), font);
+        let sprite = assets.load_canvas(&sprite_path).unwrap();
+        let error = assets.load_font(&sprite_path, 2, 3).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+
+        let mut target = Canvas::new(4, 6, Color::Black);
+        target.blit_asset(&assets, sprite, 2, 0);
+        target.draw_asset_text(&assets, font, 0, 0, "A\u{e9}A", Color::Red);
+        assert_eq!(target.get_pixel(3, 1), green);
+        assert_eq!(target.get_pixel(0, 0), Color::Red);
+        assert_eq!(target.get_pixel(1, 1), Color::Red);
+        assert_eq!(target.get_pixel(0, 1), Color::Black);
+        // The character without a glyph is left blank, the next one being drawn after it.
+        assert_eq!(target.get_pixel(0, 2), Color::Black);
+        assert_eq!(target.get_pixel(0, 4), Color::Red);
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
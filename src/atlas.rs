@@ -0,0 +1,153 @@
+use std::cmp;
+
+use crate::{Canvas, Color, Draw, Rect};
+
+/// Sprites packed into a single canvas, drawn with [`Draw::blit_region`].
+///
+/// ```
+/// use winterm::Color;
+/// use winterm::{Atlas, Canvas, Draw};
+///
+/// let atlas = Atlas::pack(vec![
+///     Canvas::new(16, 16, Color::Green),
+///     Canvas::new(8, 24, Color::Red),
+/// ]);
+/// let mut target = Canvas::new(32, 32, Color::Black);
+/// target.blit_region(atlas.canvas(), atlas.rect(1), 4, 4);
+/// assert_eq!(target.get_pixel(4, 4), Color::Red);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Atlas {
+    canvas: Canvas,
+    rects: Vec<Rect>,
+}
+
+impl Atlas {
+    /// Packs `sprites` into rows of sprites, from the tallest to the shortest, the atlas being
+    /// about as wide as it is tall and black outside of the sprites.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sprites do not fit in 65535 by 65535 pixels.
+    pub fn pack(sprites: Vec<Canvas>) -> Self {
+        let area: u64 = sprites
+            .iter()
+            .map(|sprite| u64::from(sprite.height()) * u64::from(sprite.width()))
+            .sum();
+        let widest = sprites.iter().map(Canvas::width).max().unwrap_or(0);
+        let max_width = cmp::max(u32::from(widest), (area as f64).sqrt().ceil() as u32);
+        let mut order: Vec<usize> = (0..sprites.len()).collect();
+        order.sort_by_key(|&i| cmp::Reverse(sprites[i].height()));
+        let mut positions = vec![(0, 0); sprites.len()];
+        let (mut y, mut x, mut row_height, mut width) = (0u32, 0u32, 0u32, 0u32);
+        for i in order {
+            let sprite = &sprites[i];
+            if x + u32::from(sprite.width()) > max_width {
+                y += row_height;
+                (x, row_height) = (0, 0);
+            }
+            positions[i] = (y, x);
+            x += u32::from(sprite.width());
+            row_height = cmp::max(row_height, u32::from(sprite.height()));
+            width = cmp::max(width, x);
+        }
+        let height = y + row_height;
+        let max = u32::from(u16::MAX);
+        assert!(
+            height <= max && width <= max,
+            "the sprites do not fit in an atlas"
+        );
+        let mut canvas = Canvas::new(height as u16, width as u16, Color::Black);
+        let rects = sprites
+            .iter()
+            .zip(positions)
+            .map(|(sprite, (y, x))| {
+                let (y, x) = (y as u16, x as u16);
+                canvas.blit(sprite, y, x);
+                Rect::new(y, x, sprite.height(), sprite.width())
+            })
+            .collect();
+        Atlas { canvas, rects }
+    }
+
+    /// Gets the canvas containing the sprites.
+    pub fn canvas(&self) -> &Canvas {
+        &self.canvas
+    }
+
+    /// Gets the rectangle of the sprite at `index` in the packed sprites.
+    pub fn rect(&self, index: usize) -> Rect {
+        self.rects[index]
+    }
+
+    /// Gets the rectangles of the sprites, in the order they were packed in.
+    pub fn rects(&self) -> &[Rect] {
+        &self.rects
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Color;
+
+    use super::Atlas;
+    use crate::{Canvas, Rect};
+
+    fn sprites() -> Vec<Canvas> {
+        (0..12u8)
+            .map(|i| {
+                let (height, width) = (u16::from(i % 5) + 1, u16::from(i % 3) * 4 + 2);
+                Canvas::from_fn(height, width, |y, x| Color::Rgb {
+                    r: i + 1,
+                    g: y as u8,
+                    b: x as u8,
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn packed_sprites_do_not_overlap() {
+        let sprites = sprites();
+        let atlas = Atlas::pack(sprites.clone());
+        let bounds = Rect::new(0, 0, atlas.canvas().height(), atlas.canvas().width());
+        for (i, rect) in atlas.rects().iter().enumerate() {
+            assert!(bounds.contains_rect(*rect));
+            for other in &atlas.rects()[i + 1..] {
+                assert!(!rect.intersects(*other), "{rect:?} overlaps {other:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn rects_hold_the_sprites() {
+        let sprites = sprites();
+        let atlas = Atlas::pack(sprites.clone());
+        assert_eq!(atlas.rects().len(), sprites.len());
+        for (i, sprite) in sprites.iter().enumerate() {
+            let rect = atlas.rect(i);
+            assert_eq!((rect.height, rect.width), (sprite.height(), sprite.width()));
+            for y in 0..sprite.height() {
+                for x in 0..sprite.width() {
+                    assert_eq!(
+                        atlas.canvas().get_pixel(rect.y + y, rect.x + x),
+                        sprite.get_pixel(y, x)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn atlas_is_about_square() {
+        let atlas = Atlas::pack(vec![Canvas::new(4, 4, Color::Red); 16]);
+        assert_eq!((atlas.canvas().height(), atlas.canvas().width()), (16, 16));
+    }
+
+    #[test]
+    fn empty_atlas() {
+        let atlas = Atlas::pack(Vec::new());
+        assert!(atlas.rects().is_empty());
+        assert_eq!(atlas.canvas().height(), 0);
+    }
+}
@@ -0,0 +1,446 @@
+use std::collections::VecDeque;
+use std::fmt::{self, Debug};
+#[cfg(feature = "crossterm")]
+use std::io::stdout;
+use std::io::{self, Result, Write};
+use std::sync::{Arc, Mutex, MutexGuard};
+#[cfg(feature = "crossterm")]
+use std::time::Duration;
+
+#[cfg(feature = "crossterm")]
+use crossterm::cursor::{Hide, Show};
+#[cfg(feature = "crossterm")]
+use crossterm::event::{
+    self, DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste,
+    EnableFocusChange, EnableMouseCapture,
+};
+#[cfg(feature = "crossterm")]
+use crossterm::execute;
+#[cfg(feature = "crossterm")]
+use crossterm::terminal::{
+    self, DisableLineWrap, EnableLineWrap, EnterAlternateScreen, LeaveAlternateScreen,
+};
+
+use crate::input::Event;
+use crate::Color;
+#[cfg(all(unix, feature = "crossterm"))]
+use crate::Instant;
+
+/// Terminal a window is drawn to and receives its events from.
+///
+/// The window writes ANSI escape sequences, so a backend can forward them to any terminal
+/// emulator, e.g. a web one such as xterm.js, see [`CallbackBackend`].
+pub trait Backend: Debug + Send {
+    /// Gets the terminal size, as columns and rows.
+    fn size(&self) -> Result<(u16, u16)>;
+
+    /// Prepares the terminal when the window is created.
+    fn enter(&mut self) -> Result<()>;
+
+    /// Restores the terminal when the window is dropped.
+    fn leave(&mut self) -> Result<()>;
+
+    /// Writes and flushes a frame.
+    fn write(&mut self, bytes: &[u8]) -> Result<()>;
+
+    /// Returns the next event if one is available, without waiting.
+    fn poll_event(&mut self) -> Result<Option<Event>>;
+
+    /// Waits for the next event.
+    fn read_event(&mut self) -> Result<Event>;
+
+    /// Enables or disables the reporting of mouse events, does nothing by default.
+    fn set_mouse_capture(&mut self, _enabled: bool) -> Result<()> {
+        Ok(())
+    }
+
+    /// Queries the terminal background color, called once after [`Backend::enter`].
+    ///
+    /// Returns `None` by default, when the terminal does not answer.
+    fn query_background(&mut self) -> Result<Option<Color>> {
+        Ok(None)
+    }
+}
+
+/// Time to wait for the terminal to answer a query.
+#[cfg(all(unix, feature = "crossterm"))]
+const QUERY_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Backend using [crossterm](https://docs.rs/crossterm) with the standard output, the default
+/// one.
+#[cfg(feature = "crossterm")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CrosstermBackend;
+
+#[cfg(feature = "crossterm")]
+impl Backend for CrosstermBackend {
+    fn size(&self) -> Result<(u16, u16)> {
+        terminal::size()
+    }
+
+    fn enter(&mut self) -> Result<()> {
+        execute!(
+            stdout(),
+            EnterAlternateScreen,
+            DisableLineWrap,
+            Hide,
+            EnableFocusChange,
+            EnableBracketedPaste
+        )?;
+        terminal::enable_raw_mode()
+    }
+
+    fn leave(&mut self) -> Result<()> {
+        execute!(
+            stdout(),
+            DisableMouseCapture,
+            DisableFocusChange,
+            DisableBracketedPaste,
+            LeaveAlternateScreen,
+            EnableLineWrap,
+            Show
+        )?;
+        terminal::disable_raw_mode()
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> Result<()> {
+        let mut stdout = stdout().lock();
+        stdout.write_all(bytes)?;
+        stdout.flush()?;
+        Ok(())
+    }
+
+    fn set_mouse_capture(&mut self, enabled: bool) -> Result<()> {
+        if enabled {
+            execute!(stdout(), EnableMouseCapture)
+        } else {
+            execute!(stdout(), DisableMouseCapture)
+        }
+    }
+
+    /// Skips the events added after crossterm 0.25, see [`Event`].
+    fn poll_event(&mut self) -> Result<Option<Event>> {
+        while event::poll(Duration::from_secs(0))? {
+            if let Some(event) = crate::input::from_crossterm(event::read()?) {
+                return Ok(Some(event));
+            }
+        }
+        Ok(None)
+    }
+
+    fn read_event(&mut self) -> Result<Event> {
+        loop {
+            if let Some(event) = crate::input::from_crossterm(event::read()?) {
+                return Ok(event);
+            }
+        }
+    }
+
+    /// Sends an OSC 11 query and reads the answer from the controlling terminal, unix only.
+    #[cfg(unix)]
+    fn query_background(&mut self) -> Result<Option<Color>> {
+        use std::fs::File;
+        use std::io::Read;
+        use std::os::unix::io::AsRawFd;
+
+        let mut tty = File::open("/dev/tty")?;
+        self.write(b"\x1B]11;?\x1B\\")?;
+        let deadline = Instant::now() + QUERY_TIMEOUT;
+        let mut answer = Vec::new();
+        while !answer.ends_with(b"\x07") && !answer.ends_with(b"\x1B\\") {
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            let mut poll_fd = libc::pollfd {
+                fd: tty.as_raw_fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            // SAFETY: `poll_fd` is a single valid `pollfd` that outlives the call.
+            if unsafe { libc::poll(&mut poll_fd, 1, timeout.as_millis() as libc::c_int) } <= 0 {
+                return Ok(None);
+            }
+            let mut byte = [0];
+            if tty.read(&mut byte)? == 0 {
+                return Ok(None);
+            }
+            answer.push(byte[0]);
+        }
+        Ok(parse_background(&answer))
+    }
+}
+
+/// Parses an OSC 11 answer such as `ESC ] 11 ; rgb:ffff/ffff/ffff BEL`.
+#[cfg(all(unix, feature = "crossterm"))]
+fn parse_background(answer: &[u8]) -> Option<Color> {
+    let answer = std::str::from_utf8(answer).ok()?;
+    let (_, rgb) = answer.split_once("rgb:")?;
+    let rgb = rgb.trim_end_matches(['\x07', '\x1B', '\\']);
+    let mut channels = rgb.split('/').map(|channel| {
+        if !(1..=4).contains(&channel.len()) {
+            return None;
+        }
+        let value = u32::from_str_radix(channel, 16).ok()?;
+        let max = 16u32.pow(channel.len() as u32) - 1;
+        Some((value * 255 / max) as u8)
+    });
+    let color = Color::Rgb {
+        r: channels.next()??,
+        g: channels.next()??,
+        b: channels.next()??,
+    };
+    channels.next().is_none().then_some(color)
+}
+
+/// Backend for a standard output that is not a terminal, see [`crate::Window::new`].
+#[cfg(feature = "crossterm")]
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct DumpBackend;
+
+#[cfg(feature = "crossterm")]
+impl Backend for DumpBackend {
+    fn size(&self) -> Result<(u16, u16)> {
+        Ok((0, 0))
+    }
+
+    fn enter(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn leave(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> Result<()> {
+        let mut stdout = stdout().lock();
+        stdout.write_all(bytes)?;
+        stdout.flush()?;
+        Ok(())
+    }
+
+    fn poll_event(&mut self) -> Result<Option<Event>> {
+        Ok(None)
+    }
+
+    fn read_event(&mut self) -> Result<Event> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "the standard output is not a terminal",
+        ))
+    }
+}
+
+/// Callback of a [`CallbackBackend`], writing the bytes of a frame to the terminal.
+type WriteCallback = Box<dyn FnMut(&[u8]) -> Result<()> + Send>;
+
+/// Backend writing the frames to a callback, and returning the events pushed to its
+/// [`EventQueue`].
+///
+/// It fits terminals the window cannot read from itself, e.g. xterm.js in a web page, where the
+/// callback writes to the emulator and its listeners push the input events. Waiting for an event
+/// is not possible there, [`Backend::read_event`] thus fails with [`io::ErrorKind::WouldBlock`]
+/// when the queue is empty, which closes the blocking dialogs such as the debugger.
+///
+/// ```
+/// use winterm::input::Event;
+/// use winterm::{CallbackBackend, Window};
+///
+/// # fn main() -> std::io::Result<()> {
+/// let backend = CallbackBackend::new(80, 24, |bytes| {
+///     // Forward `bytes` to the terminal emulator.
+///     # let _ = bytes;
+///     Ok(())
+/// });
+/// let events = backend.events();
+/// let mut window = Window::with_backend(24, 80, backend)?;
+/// events.push(Event::Resize(100, 30));
+/// window.poll_events()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct CallbackBackend {
+    write: WriteCallback,
+    events: EventQueue,
+}
+
+impl CallbackBackend {
+    /// Creates a backend for a terminal of `columns` and `rows`, writing the frames to `write`.
+    pub fn new(
+        columns: u16,
+        rows: u16,
+        write: impl FnMut(&[u8]) -> Result<()> + Send + 'static,
+    ) -> Self {
+        CallbackBackend {
+            write: Box::new(write),
+            events: EventQueue::new(columns, rows),
+        }
+    }
+
+    /// Gets the queue the events of the terminal are pushed to.
+    pub fn events(&self) -> EventQueue {
+        self.events.clone()
+    }
+}
+
+impl Debug for CallbackBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CallbackBackend")
+            .field("events", &self.events)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Backend for CallbackBackend {
+    fn size(&self) -> Result<(u16, u16)> {
+        Ok(self.events.size())
+    }
+
+    /// Hides the cursor.
+    fn enter(&mut self) -> Result<()> {
+        (self.write)(b"\x1B[?25l")
+    }
+
+    /// Shows the cursor.
+    fn leave(&mut self) -> Result<()> {
+        (self.write)(b"\x1B[?25h")
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> Result<()> {
+        (self.write)(bytes)
+    }
+
+    fn poll_event(&mut self) -> Result<Option<Event>> {
+        Ok(self.events.lock().events.pop_front())
+    }
+
+    fn read_event(&mut self) -> Result<Event> {
+        self.poll_event()?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::WouldBlock, "no event pushed"))
+    }
+}
+
+/// Events of a [`CallbackBackend`], cloned to push them from the terminal listeners.
+#[derive(Debug, Clone)]
+pub struct EventQueue {
+    state: Arc<Mutex<QueueState>>,
+}
+
+#[derive(Debug)]
+struct QueueState {
+    events: VecDeque<Event>,
+    size: (u16, u16),
+}
+
+impl EventQueue {
+    fn new(columns: u16, rows: u16) -> Self {
+        EventQueue {
+            state: Arc::new(Mutex::new(QueueState {
+                events: VecDeque::new(),
+                size: (columns, rows),
+            })),
+        }
+    }
+
+    /// Pushes an event returned by the next poll of the backend.
+    ///
+    /// An [`Event::Resize`] also changes the size of the terminal returned by the backend.
+    pub fn push(&self, event: Event) {
+        let mut state = self.lock();
+        if let Event::Resize(columns, rows) = event {
+            state.size = (columns, rows);
+        }
+        state.events.push_back(event);
+    }
+
+    /// Gets the terminal size, as columns and rows.
+    pub fn size(&self) -> (u16, u16) {
+        self.lock().size
+    }
+
+    fn lock(&self) -> MutexGuard<'_, QueueState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Buffered output of a window, written to its backend when flushed.
+#[derive(Debug)]
+pub(crate) struct Terminal {
+    pub(crate) backend: Box<dyn Backend>,
+    buffer: Vec<u8>,
+}
+
+impl Terminal {
+    pub(crate) fn new(backend: Box<dyn Backend>) -> Self {
+        Terminal {
+            backend,
+            buffer: Vec::new(),
+        }
+    }
+}
+
+impl Write for Terminal {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(bytes);
+        Ok(bytes.len())
+    }
+
+    /// Writes the buffer to the backend, also called by crossterm before executing a WinAPI
+    /// command so that the output stays in order on legacy Windows consoles.
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.buffer.is_empty() {
+            self.backend.write(&self.buffer)?;
+            self.buffer.clear();
+        }
+        Ok(())
+    }
+}
+
+/// Backend of the unit tests, discarding the output and returning the events pushed to `events`.
+#[cfg(test)]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TestBackend {
+    pub(crate) events: Arc<Mutex<VecDeque<Event>>>,
+    pub(crate) output: Arc<Mutex<Vec<u8>>>,
+}
+
+#[cfg(test)]
+impl TestBackend {
+    /// Pushes an event returned by the next poll.
+    pub(crate) fn push(&self, event: Event) {
+        self.events.lock().unwrap().push_back(event);
+    }
+
+    /// Takes the bytes written since the last call.
+    pub(crate) fn take_output(&self) -> Vec<u8> {
+        std::mem::take(&mut self.output.lock().unwrap())
+    }
+}
+
+#[cfg(test)]
+impl Backend for TestBackend {
+    fn size(&self) -> Result<(u16, u16)> {
+        Ok((80, 24))
+    }
+
+    fn enter(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn leave(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> Result<()> {
+        self.output.lock().unwrap().extend_from_slice(bytes);
+        Ok(())
+    }
+
+    fn poll_event(&mut self) -> Result<Option<Event>> {
+        Ok(self.events.lock().unwrap().pop_front())
+    }
+
+    fn read_event(&mut self) -> Result<Event> {
+        self.poll_event()?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::WouldBlock, "no event pushed"))
+    }
+}
@@ -0,0 +1,165 @@
+//! [Bevy](https://bevyengine.org) plugin presenting an [`Image`] through a winterm [`Window`] and
+//! feeding the terminal keyboard input into [`ButtonInput<KeyCode>`].
+//!
+//! Terminals do not report key releases, so a key is considered released on the frame following
+//! its last press or repeat.
+//!
+//! Bevy apps only run their schedule once by default, add a looping runner such as
+//! `ScheduleRunnerPlugin` to run the game.
+
+use crate::input::Event;
+use crate::{color, Key, Window};
+use bevy_app::{App, Last, Plugin, PreUpdate};
+use bevy_asset::{Assets, Handle};
+use bevy_color::ColorToPacked;
+use bevy_ecs::system::{NonSendMut, Res, ResMut, Resource};
+use bevy_image::Image;
+use bevy_input::keyboard::KeyCode;
+use bevy_input::ButtonInput;
+
+/// Plugin creating the winterm [`Window`], available as a non-send resource.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WintermPlugin {
+    pub height: u16,
+    pub width: u16,
+}
+
+/// Image presented in the window at the end of each frame, cropped to the window size.
+#[derive(Debug, Clone, Default, Resource)]
+pub struct WintermTarget(pub Handle<Image>);
+
+impl Plugin for WintermPlugin {
+    fn build(&self, app: &mut App) {
+        let window = Window::new(self.height, self.width).expect("failed to create the window");
+        app.insert_non_send_resource(window)
+            .init_resource::<ButtonInput<KeyCode>>()
+            .add_systems(PreUpdate, poll_events)
+            .add_systems(Last, present);
+    }
+}
+
+fn poll_events(mut window: NonSendMut<Window>, mut input: ResMut<ButtonInput<KeyCode>>) {
+    if window.poll_events().is_err() {
+        return;
+    }
+    input.clear();
+    input.release_all();
+    for event in &window.last_events {
+        if let Event::Key(key_event) = event {
+            if let Some(key) = to_bevy_key_code(key_event.code) {
+                input.press(key);
+            }
+        }
+    }
+}
+
+fn present(
+    mut window: NonSendMut<Window>,
+    target: Option<Res<WintermTarget>>,
+    images: Option<Res<Assets<Image>>>,
+) {
+    let (Some(target), Some(images)) = (target, images) else {
+        return;
+    };
+    let Some(image) = images.get(&target.0) else {
+        return;
+    };
+    for y in 0..window.height().min(image.height() as u16) {
+        for x in 0..window.width().min(image.width() as u16) {
+            if let Ok(pixel) = image.get_color_at(x.into(), y.into()) {
+                let [r, g, b, _] = pixel.to_srgba().to_u8_array();
+                let pixel = color::from_rgb([r, g, b]);
+                if window.get_pixel(y, x) != pixel {
+                    window.set_pixel(y, x, pixel);
+                }
+            }
+        }
+    }
+    let _ = window.redraw();
+}
+
+fn to_bevy_key_code(key: Key) -> Option<KeyCode> {
+    Some(match key {
+        Key::Backspace => KeyCode::Backspace,
+        Key::Enter => KeyCode::Enter,
+        Key::Left => KeyCode::ArrowLeft,
+        Key::Right => KeyCode::ArrowRight,
+        Key::Up => KeyCode::ArrowUp,
+        Key::Down => KeyCode::ArrowDown,
+        Key::Home => KeyCode::Home,
+        Key::End => KeyCode::End,
+        Key::PageUp => KeyCode::PageUp,
+        Key::PageDown => KeyCode::PageDown,
+        Key::Tab | Key::BackTab => KeyCode::Tab,
+        Key::Delete => KeyCode::Delete,
+        Key::Insert => KeyCode::Insert,
+        Key::Esc => KeyCode::Escape,
+        Key::F(n) => match n {
+            1 => KeyCode::F1,
+            2 => KeyCode::F2,
+            3 => KeyCode::F3,
+            4 => KeyCode::F4,
+            5 => KeyCode::F5,
+            6 => KeyCode::F6,
+            7 => KeyCode::F7,
+            8 => KeyCode::F8,
+            9 => KeyCode::F9,
+            10 => KeyCode::F10,
+            11 => KeyCode::F11,
+            12 => KeyCode::F12,
+            _ => return None,
+        },
+        Key::Char(char) => match char.to_ascii_lowercase() {
+            'a' => KeyCode::KeyA,
+            'b' => KeyCode::KeyB,
+            'c' => KeyCode::KeyC,
+            'd' => KeyCode::KeyD,
+            'e' => KeyCode::KeyE,
+            'f' => KeyCode::KeyF,
+            'g' => KeyCode::KeyG,
+            'h' => KeyCode::KeyH,
+            'i' => KeyCode::KeyI,
+            'j' => KeyCode::KeyJ,
+            'k' => KeyCode::KeyK,
+            'l' => KeyCode::KeyL,
+            'm' => KeyCode::KeyM,
+            'n' => KeyCode::KeyN,
+            'o' => KeyCode::KeyO,
+            'p' => KeyCode::KeyP,
+            'q' => KeyCode::KeyQ,
+            'r' => KeyCode::KeyR,
+            's' => KeyCode::KeyS,
+            't' => KeyCode::KeyT,
+            'u' => KeyCode::KeyU,
+            'v' => KeyCode::KeyV,
+            'w' => KeyCode::KeyW,
+            'x' => KeyCode::KeyX,
+            'y' => KeyCode::KeyY,
+            'z' => KeyCode::KeyZ,
+            '0' => KeyCode::Digit0,
+            '1' => KeyCode::Digit1,
+            '2' => KeyCode::Digit2,
+            '3' => KeyCode::Digit3,
+            '4' => KeyCode::Digit4,
+            '5' => KeyCode::Digit5,
+            '6' => KeyCode::Digit6,
+            '7' => KeyCode::Digit7,
+            '8' => KeyCode::Digit8,
+            '9' => KeyCode::Digit9,
+            ' ' => KeyCode::Space,
+            '-' => KeyCode::Minus,
+            '=' => KeyCode::Equal,
+            '[' => KeyCode::BracketLeft,
+            ']' => KeyCode::BracketRight,
+            '\\' => KeyCode::Backslash,
+            ';' => KeyCode::Semicolon,
+            '\'' => KeyCode::Quote,
+            '`' => KeyCode::Backquote,
+            ',' => KeyCode::Comma,
+            '.' => KeyCode::Period,
+            '/' => KeyCode::Slash,
+            _ => return None,
+        },
+        _ => return None,
+    })
+}
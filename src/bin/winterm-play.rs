@@ -0,0 +1,133 @@
+//! Animated GIF player, fitting the animation to the terminal.
+//!
+//! Usage: `winterm-play <gif>`, then Space to pause, and `q` or Escape to quit.
+
+use std::fs::File;
+use std::io::{self, BufReader, Result};
+use std::process::ExitCode;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crossterm::terminal;
+use image::codecs::gif::GifDecoder;
+use image::AnimationDecoder;
+use winterm::{color, Canvas, Color, Draw, Filter, Key, Window};
+
+/// Delay of the frames without one, as browsers do.
+const DEFAULT_DELAY: Duration = Duration::from_millis(100);
+/// Longest wait before polling the events again.
+const EVENTS_INTERVAL: Duration = Duration::from_millis(16);
+
+fn main() -> ExitCode {
+    let Some(path) = std::env::args().nth(1) else {
+        eprintln!("usage: winterm-play <gif>");
+        return ExitCode::FAILURE;
+    };
+    let frames = match load(&path) {
+        Ok(frames) if !frames.is_empty() => frames,
+        Ok(_) => {
+            eprintln!("winterm-play: {path}: no frames");
+            return ExitCode::FAILURE;
+        }
+        Err(error) => {
+            eprintln!("winterm-play: {path}: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Err(error) = run(&frames) {
+        eprintln!("winterm-play: {error}");
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+/// Decodes the frames of a GIF, with their delays.
+fn load(path: &str) -> io::Result<Vec<(Canvas, Duration)>> {
+    let decoder = GifDecoder::new(BufReader::new(File::open(path)?)).map_err(io::Error::other)?;
+    decoder
+        .into_frames()
+        .map(|frame| {
+            let frame = frame.map_err(io::Error::other)?;
+            let delay = Duration::from(frame.delay());
+            let buffer = frame.into_buffer();
+            let (width, height) = buffer.dimensions();
+            let mut canvas = Canvas::new(
+                height.min(u16::MAX.into()) as u16,
+                width.min(u16::MAX.into()) as u16,
+                Color::Black,
+            );
+            for (x, y, rgba) in buffer.enumerate_pixels() {
+                if y < canvas.height().into() && x < canvas.width().into() {
+                    let [r, g, b, _] = rgba.0;
+                    canvas.set_pixel(y as u16, x as u16, color::from_rgb([r, g, b]));
+                }
+            }
+            let delay = if delay.is_zero() {
+                DEFAULT_DELAY
+            } else {
+                delay
+            };
+            Ok((canvas, delay))
+        })
+        .collect()
+}
+
+fn run(frames: &[(Canvas, Duration)]) -> Result<()> {
+    // The border takes 2 columns and 1 or 2 rows, each row holding 2 pixels.
+    let (columns, rows) = terminal::size()?;
+    let mut window = Window::new(
+        rows.saturating_sub(2).saturating_mul(2).max(1),
+        columns.saturating_sub(2).max(1),
+    )?;
+    let frames: Vec<(Canvas, Duration)> = frames
+        .iter()
+        .map(|(frame, delay)| (fit(&window, frame), *delay))
+        .collect();
+    let mut paused = false;
+    let mut index = 0;
+    let mut next_frame = Instant::now();
+    loop {
+        window.poll_events()?;
+        if window.get_key(Key::Esc) || window.get_key(Key::Char('q')) {
+            return Ok(());
+        }
+        if window.get_key(Key::Char(' ')) {
+            paused = !paused;
+            next_frame = Instant::now();
+        }
+        let now = Instant::now();
+        if !paused && now >= next_frame {
+            let (frame, delay) = &frames[index];
+            window.pixels_mut().fill(Color::Black);
+            window.blit(
+                frame,
+                (window.height() - frame.height()) / 2,
+                (window.width() - frame.width()) / 2,
+            );
+            window.redraw()?;
+            // Late frames are not skipped, the next ones being delayed instead.
+            next_frame = (next_frame + *delay).max(now);
+            index = (index + 1) % frames.len();
+        }
+        let wait = next_frame.saturating_duration_since(Instant::now());
+        thread::sleep(if paused {
+            EVENTS_INTERVAL
+        } else {
+            wait.min(EVENTS_INTERVAL)
+        });
+    }
+}
+
+/// Scales `frame` to fit the window, keeping its aspect ratio.
+fn fit(window: &Window, frame: &Canvas) -> Canvas {
+    let scale = (f32::from(window.height()) / f32::from(frame.height().max(1)))
+        .min(f32::from(window.width()) / f32::from(frame.width().max(1)));
+    let height = ((f32::from(frame.height()) * scale).round() as u16).min(window.height());
+    let width = ((f32::from(frame.width()) * scale).round() as u16).min(window.width());
+    let filter = if scale < 1. {
+        Filter::Bilinear
+    } else {
+        Filter::Nearest
+    };
+    frame.resized(height, width, filter)
+}
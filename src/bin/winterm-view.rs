@@ -0,0 +1,161 @@
+//! Image viewer, fitting an image to the terminal.
+//!
+//! Usage: `winterm-view <image>`, then `+` and `-` to zoom, the arrows or `hjkl` to pan, `0` to
+//! fit the image again, and `q` or Escape to quit.
+
+use std::io;
+use std::io::Result;
+use std::process::ExitCode;
+use std::thread;
+use std::time::Duration;
+
+use crossterm::terminal;
+use winterm::{color, Canvas, Color, Draw, Filter, Key, Rect, Window};
+
+const ZOOM_STEP: f32 = 1.25;
+/// Part of the visible region panned by each key press.
+const PAN_STEP: f32 = 0.1;
+
+/// Zoom, 1 fitting the whole image, and center of the visible region, in image pixels.
+struct View {
+    zoom: f32,
+    center_y: f32,
+    center_x: f32,
+}
+
+impl View {
+    fn fit(image: &Canvas) -> Self {
+        View {
+            zoom: 1.,
+            center_y: f32::from(image.height()) / 2.,
+            center_x: f32::from(image.width()) / 2.,
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let Some(path) = std::env::args().nth(1) else {
+        eprintln!("usage: winterm-view <image>");
+        return ExitCode::FAILURE;
+    };
+    let image = match load(&path) {
+        Ok(image) => image,
+        Err(error) => {
+            eprintln!("winterm-view: {path}: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Err(error) = run(&image) {
+        eprintln!("winterm-view: {error}");
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+fn load(path: &str) -> io::Result<Canvas> {
+    let mut image = image::open(path).map_err(io::Error::other)?;
+    let max = u32::from(u16::MAX);
+    if image.height() > max || image.width() > max {
+        image = image.thumbnail(max, max);
+    }
+    let image = image.to_rgb8();
+    let mut canvas = Canvas::new(image.height() as u16, image.width() as u16, Color::Black);
+    for (x, y, rgb) in image.enumerate_pixels() {
+        canvas.set_pixel(y as u16, x as u16, color::from_rgb(rgb.0));
+    }
+    Ok(canvas)
+}
+
+fn run(image: &Canvas) -> Result<()> {
+    // The border takes 2 columns and 1 or 2 rows, each row holding 2 pixels.
+    let (columns, rows) = terminal::size()?;
+    let mut window = Window::new(
+        rows.saturating_sub(2).saturating_mul(2).max(1),
+        columns.saturating_sub(2).max(1),
+    )?;
+    let mut view = View::fit(image);
+    draw(&mut window, image, &view);
+    window.redraw()?;
+    loop {
+        window.poll_events()?;
+        if window.get_key(Key::Esc) || window.get_key(Key::Char('q')) {
+            return Ok(());
+        }
+        let (visible_height, visible_width) = visible_size(&window, image, view.zoom);
+        let mut changed = true;
+        if window.get_key(Key::Char('+')) || window.get_key(Key::Char('=')) {
+            view.zoom *= ZOOM_STEP;
+        } else if window.get_key(Key::Char('-')) {
+            view.zoom = (view.zoom / ZOOM_STEP).max(1.);
+        } else if window.get_key(Key::Char('0')) {
+            view = View::fit(image);
+        } else if window.get_key(Key::Left) || window.get_key(Key::Char('h')) {
+            view.center_x -= visible_width * PAN_STEP;
+        } else if window.get_key(Key::Right) || window.get_key(Key::Char('l')) {
+            view.center_x += visible_width * PAN_STEP;
+        } else if window.get_key(Key::Up) || window.get_key(Key::Char('k')) {
+            view.center_y -= visible_height * PAN_STEP;
+        } else if window.get_key(Key::Down) || window.get_key(Key::Char('j')) {
+            view.center_y += visible_height * PAN_STEP;
+        } else {
+            changed = false;
+        }
+        if changed {
+            view.center_y = view.center_y.clamp(
+                visible_height / 2.,
+                f32::from(image.height()) - visible_height / 2.,
+            );
+            view.center_x = view.center_x.clamp(
+                visible_width / 2.,
+                f32::from(image.width()) - visible_width / 2.,
+            );
+            draw(&mut window, image, &view);
+            window.redraw()?;
+        }
+        thread::sleep(Duration::from_millis(16));
+    }
+}
+
+/// Gets the size of the image region visible at `zoom`, in image pixels.
+fn visible_size(window: &Window, image: &Canvas, zoom: f32) -> (f32, f32) {
+    let scale = fit_scale(window, image) * zoom;
+    (
+        (f32::from(window.height()) / scale).min(f32::from(image.height())),
+        (f32::from(window.width()) / scale).min(f32::from(image.width())),
+    )
+}
+
+/// Gets the scale fitting the whole image in the window.
+fn fit_scale(window: &Window, image: &Canvas) -> f32 {
+    (f32::from(window.height()) / f32::from(image.height().max(1)))
+        .min(f32::from(window.width()) / f32::from(image.width().max(1)))
+}
+
+fn draw(window: &mut Window, image: &Canvas, view: &View) {
+    let (visible_height, visible_width) = visible_size(window, image, view.zoom);
+    let top =
+        (view.center_y - visible_height / 2.).clamp(0., f32::from(image.height()) - visible_height);
+    let left =
+        (view.center_x - visible_width / 2.).clamp(0., f32::from(image.width()) - visible_width);
+    let region = image.crop(Rect::new(
+        top as u16,
+        left as u16,
+        visible_height.ceil() as u16,
+        visible_width.ceil() as u16,
+    ));
+    let scale = fit_scale(window, image) * view.zoom;
+    let height = ((f32::from(region.height()) * scale).round() as u16).min(window.height());
+    let width = ((f32::from(region.width()) * scale).round() as u16).min(window.width());
+    let filter = if scale < 1. {
+        Filter::Bilinear
+    } else {
+        Filter::Nearest
+    };
+    let resized = region.resized(height, width, filter);
+    window.pixels_mut().fill(Color::Black);
+    window.blit(
+        &resized,
+        (window.height() - height) / 2,
+        (window.width() - width) / 2,
+    );
+}
@@ -0,0 +1,32 @@
+use crate::{Canvas, Color, Window};
+
+impl Window {
+    /// Sets the brush painted by [`Window::stamp`], its [`Color::Reset`] pixels being transparent.
+    pub fn set_brush(&mut self, brush: &Canvas) {
+        self.brush = Some(brush.clone());
+    }
+
+    /// Paints the brush centered at `y` and `x`, its parts outside of the window being ignored.
+    ///
+    /// Does nothing if no brush was set with [`Window::set_brush`].
+    pub fn stamp(&mut self, y: i32, x: i32) {
+        let Some(brush) = self.brush.take() else {
+            return;
+        };
+        let top = y - i32::from(brush.height()) / 2;
+        let left = x - i32::from(brush.width()) / 2;
+        for brush_y in 0..brush.height() {
+            for brush_x in 0..brush.width() {
+                let color = brush.get_pixel(brush_y, brush_x);
+                let (y, x) = (top + i32::from(brush_y), left + i32::from(brush_x));
+                if color != Color::Reset
+                    && (0..i32::from(self.height())).contains(&y)
+                    && (0..i32::from(self.width())).contains(&x)
+                {
+                    self.set_pixel(y as u16, x as u16, color);
+                }
+            }
+        }
+        self.brush = Some(brush);
+    }
+}
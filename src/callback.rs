@@ -0,0 +1,150 @@
+use std::fmt;
+use std::mem;
+
+use crate::{Key, Window, WindowEvent};
+
+/// Identifier of a callback, see [`Window::on_event`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CallbackId(usize);
+
+type Callback = Box<dyn FnMut(&mut Window, &WindowEvent) + Send>;
+
+/// Callbacks registered on a window, run by [`Window::poll_events`].
+#[derive(Default)]
+pub(crate) struct Callbacks {
+    entries: Vec<(CallbackId, Callback)>,
+    next_id: usize,
+    /// Whether the callbacks are running, taken out of `entries`.
+    running: bool,
+    /// Callbacks removed while the others were running.
+    removed: Vec<CallbackId>,
+}
+
+impl fmt::Debug for Callbacks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Callbacks")
+            .field("len", &self.entries.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Window {
+    /// Registers a callback run by [`Window::poll_events`] for each of the [`Window::events`].
+    pub fn on_event(
+        &mut self,
+        callback: impl FnMut(&mut Window, &WindowEvent) + Send + 'static,
+    ) -> CallbackId {
+        let id = CallbackId(self.callbacks.next_id);
+        self.callbacks.next_id += 1;
+        self.callbacks.entries.push((id, Box::new(callback)));
+        id
+    }
+
+    /// Registers a callback run by [`Window::poll_events`] when `key` is pressed, following
+    /// the case sensitivity setting, see [`Window::set_case_insensitive_keys`].
+    pub fn on_key(
+        &mut self,
+        key: Key,
+        mut callback: impl FnMut(&mut Window) + Send + 'static,
+    ) -> CallbackId {
+        self.on_event(move |window, event| {
+            if let WindowEvent::KeyPressed { code, .. } = *event {
+                if window.keys_match(key, code) {
+                    callback(window);
+                }
+            }
+        })
+    }
+
+    /// Registers a callback run by [`Window::poll_events`] with the new terminal columns and rows
+    /// when it is resized.
+    pub fn on_resize(
+        &mut self,
+        mut callback: impl FnMut(&mut Window, u16, u16) + Send + 'static,
+    ) -> CallbackId {
+        self.on_event(move |window, event| {
+            if let WindowEvent::Resized { columns, rows } = *event {
+                callback(window, columns, rows);
+            }
+        })
+    }
+
+    /// Removes a callback, possibly from another callback.
+    pub fn remove_callback(&mut self, id: CallbackId) {
+        self.callbacks
+            .entries
+            .retain(|(entry_id, _)| *entry_id != id);
+        if self.callbacks.running && !self.callbacks.removed.contains(&id) {
+            self.callbacks.removed.push(id);
+        }
+    }
+
+    /// Runs the callbacks for the events of the last poll.
+    pub(crate) fn run_callbacks(&mut self) {
+        if self.callbacks.entries.is_empty() {
+            return;
+        }
+        let events = self.events();
+        let mut entries = mem::take(&mut self.callbacks.entries);
+        self.callbacks.running = true;
+        for event in &events {
+            for (id, callback) in &mut entries {
+                if !self.callbacks.removed.contains(id) {
+                    callback(self, event);
+                }
+            }
+        }
+        self.callbacks.running = false;
+        let removed = mem::take(&mut self.callbacks.removed);
+        entries.retain(|(id, _)| !removed.contains(id));
+        entries.append(&mut self.callbacks.entries);
+        self.callbacks.entries = entries;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use super::CallbackId;
+    use crate::backend::TestBackend;
+    use crate::input::{Event, KeyEvent};
+    use crate::{Key, Modifiers, Window};
+
+    #[test]
+    fn removing_outside_of_the_callbacks_records_nothing() {
+        let mut window = Window::with_backend(2, 2, TestBackend::default()).unwrap();
+        for _ in 0..100 {
+            let id = window.on_key(Key::Esc, |_| {});
+            window.remove_callback(id);
+            window.remove_callback(id);
+        }
+        assert!(window.callbacks.entries.is_empty());
+        assert!(window.callbacks.removed.is_empty());
+    }
+
+    #[test]
+    fn callback_removed_by_another_one_is_not_run() {
+        let backend = TestBackend::default();
+        let mut window = Window::with_backend(2, 2, backend.clone()).unwrap();
+        let runs = Arc::new(AtomicUsize::new(0));
+        let first_runs = Arc::clone(&runs);
+        // The identifiers are given in order, the second callback being the next one.
+        let second = CallbackId(window.callbacks.next_id + 1);
+        window.on_key(Key::Esc, move |window| {
+            window.remove_callback(second);
+            first_runs.fetch_add(1, Ordering::Relaxed);
+        });
+        let second_runs = Arc::clone(&runs);
+        let id = window.on_key(Key::Esc, move |_| {
+            second_runs.fetch_add(10, Ordering::Relaxed);
+        });
+        assert_eq!(id, second);
+        backend.push(Event::Key(KeyEvent::new(Key::Esc, Modifiers::NONE)));
+        window.poll_events().unwrap();
+        assert_eq!(runs.load(Ordering::Relaxed), 1);
+        assert_eq!(window.callbacks.entries.len(), 1);
+        assert!(window.callbacks.removed.is_empty());
+    }
+}
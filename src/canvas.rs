@@ -0,0 +1,395 @@
+use std::io::{self, ErrorKind};
+use std::path::Path;
+use std::{cmp, fs};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{color, Color, Margins, PixelStorage, Rect};
+
+/// Magic bytes starting the files written by [`Canvas::save`].
+const MAGIC: &[u8; 4] = b"WTRM";
+
+/// Resampling filter of [`Canvas::resized`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Filter {
+    /// Takes the nearest pixel, keeping hard edges, e.g. for pixel art.
+    #[default]
+    Nearest,
+    /// Interpolates the 4 nearest pixels in RGB, smoothing the result, e.g. for thumbnails.
+    Bilinear,
+}
+
+/// Off-screen pixel buffer, drawn onto a window with [`Draw::blit`](crate::Draw::blit).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "RawCanvas")
+)]
+pub struct Canvas {
+    height: u16,
+    width: u16,
+    pixels: Vec<Color>,
+}
+
+impl Canvas {
+    /// Creates a canvas filled with `color`.
+    pub fn new(height: u16, width: u16, color: Color) -> Self {
+        Canvas {
+            height,
+            width,
+            pixels: vec![color; height as usize * width as usize],
+        }
+    }
+
+    /// Creates a canvas whose pixels are colored by `f`, called with their `y` and `x` row by row.
+    ///
+    /// ```
+    /// use winterm::Color;
+    /// use winterm::Canvas;
+    ///
+    /// let checkerboard = Canvas::from_fn(8, 8, |y, x| {
+    ///     if (y + x) % 2 == 0 {
+    ///         Color::White
+    ///     } else {
+    ///         Color::Black
+    ///     }
+    /// });
+    /// ```
+    pub fn from_fn(height: u16, width: u16, mut f: impl FnMut(u16, u16) -> Color) -> Self {
+        let mut pixels = Vec::with_capacity(height as usize * width as usize);
+        for y in 0..height {
+            for x in 0..width {
+                pixels.push(f(y, x));
+            }
+        }
+        Canvas {
+            height,
+            width,
+            pixels,
+        }
+    }
+
+    /// Gets the canvas width.
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    /// Gets the canvas height.
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    fn index(&self, y: u16, x: u16) -> usize {
+        assert!(y < self.height && x < self.width, "pixel out of bounds");
+        y as usize * self.width as usize + x as usize
+    }
+
+    /// Gets a pixel color.
+    pub fn get_pixel(&self, y: u16, x: u16) -> Color {
+        self.pixels[self.index(y, x)]
+    }
+
+    /// Sets a pixel color.
+    pub fn set_pixel(&mut self, y: u16, x: u16, color: Color) {
+        let index = self.index(y, x);
+        self.pixels[index] = color;
+    }
+
+    /// Gets the pixels, row by row.
+    pub fn pixels(&self) -> &[Color] {
+        &self.pixels
+    }
+
+    /// Gets the pixels mutably, row by row.
+    pub fn pixels_mut(&mut self) -> &mut [Color] {
+        &mut self.pixels
+    }
+
+    /// Iterates over the pixels row by row, with their `y` and `x`.
+    pub fn enumerate_pixels(&self) -> impl ExactSizeIterator<Item = (u16, u16, &Color)> {
+        let width = cmp::max(self.width, 1) as usize;
+        self.pixels
+            .iter()
+            .enumerate()
+            .map(move |(i, color)| ((i / width) as u16, (i % width) as u16, color))
+    }
+
+    /// Iterates mutably over the pixels row by row, with their `y` and `x`.
+    ///
+    /// ```
+    /// use winterm::Color;
+    /// use winterm::{color, Canvas};
+    ///
+    /// let mut canvas = Canvas::new(16, 16, Color::Black);
+    /// for (y, x, pixel) in canvas.enumerate_pixels_mut() {
+    ///     *pixel = color::from_rgb([(y * 16) as u8, (x * 16) as u8, 0]);
+    /// }
+    /// ```
+    pub fn enumerate_pixels_mut(
+        &mut self,
+    ) -> impl ExactSizeIterator<Item = (u16, u16, &mut Color)> {
+        let width = cmp::max(self.width, 1) as usize;
+        self.pixels
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, color)| ((i / width) as u16, (i % width) as u16, color))
+    }
+
+    /// Iterates over the rows of pixels, from top to bottom.
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = &[Color]> {
+        self.pixels.chunks_exact(cmp::max(self.width, 1).into())
+    }
+
+    /// Iterates mutably over the rows of pixels, from top to bottom.
+    pub fn rows_mut(&mut self) -> impl ExactSizeIterator<Item = &mut [Color]> {
+        self.pixels.chunks_exact_mut(cmp::max(self.width, 1).into())
+    }
+
+    /// Iterates over the columns of pixels, from left to right, each one from top to bottom.
+    pub fn columns(
+        &self,
+    ) -> impl ExactSizeIterator<Item = impl ExactSizeIterator<Item = &Color> + Clone> {
+        let width = self.width as usize;
+        (0..width).map(move |x| {
+            self.pixels
+                .get(x..)
+                .unwrap_or_default()
+                .iter()
+                .step_by(width)
+        })
+    }
+
+    /// Replaces each pixel by the color returned by `f`, called with its `y`, `x` and color row
+    /// by row.
+    pub fn map_pixels(&mut self, mut f: impl FnMut(u16, u16, Color) -> Color) {
+        for (y, x, pixel) in self.enumerate_pixels_mut() {
+            *pixel = f(y, x, *pixel);
+        }
+    }
+
+    /// Creates a copy of the pixels of `rect`, clamped to the canvas.
+    pub fn crop(&self, rect: Rect) -> Self {
+        let end_y = cmp::min(rect.end_y(), self.height);
+        let end_x = cmp::min(rect.end_x(), self.width);
+        let (y, x) = (cmp::min(rect.y, end_y), cmp::min(rect.x, end_x));
+        let mut pixels = Vec::with_capacity((end_y - y) as usize * (end_x - x) as usize);
+        for row in y..end_y {
+            let start = row as usize * self.width as usize;
+            pixels.extend_from_slice(&self.pixels[start + x as usize..start + end_x as usize]);
+        }
+        Canvas {
+            height: end_y - y,
+            width: end_x - x,
+            pixels,
+        }
+    }
+
+    /// Creates a copy of the canvas surrounded by `margins` filled with `color`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the copy would be larger than 65535 pixels in height or width.
+    pub fn padded(&self, margins: Margins, color: Color) -> Self {
+        let size = |size: u16, before: u16, after: u16| {
+            size.checked_add(before)
+                .and_then(|size| size.checked_add(after))
+                .expect("the padded canvas is too large")
+        };
+        let height = size(self.height, margins.top, margins.bottom);
+        let width = size(self.width, margins.left, margins.right);
+        let mut padded = Canvas::new(height, width, color);
+        for y in 0..self.height {
+            let start = (margins.top + y) as usize * width as usize + margins.left as usize;
+            let row = y as usize * self.width as usize;
+            padded.pixels[start..start + self.width as usize]
+                .copy_from_slice(&self.pixels[row..row + self.width as usize]);
+        }
+        padded
+    }
+
+    /// Creates a copy of the canvas scaled to `height` and `width` with `filter`.
+    ///
+    /// The copy of an empty canvas is black.
+    pub fn resized(&self, height: u16, width: u16, filter: Filter) -> Self {
+        if self.height == 0 || self.width == 0 {
+            return Canvas::new(height, width, Color::Black);
+        }
+        let (scale_y, scale_x) = (
+            f32::from(self.height) / f32::from(height),
+            f32::from(self.width) / f32::from(width),
+        );
+        // Coordinates, in the canvas, of the center of a pixel of the copy.
+        let source = |position: u16, scale: f32, size: u16| {
+            ((f32::from(position) + 0.5) * scale - 0.5).clamp(0., f32::from(size - 1))
+        };
+        let mut pixels = Vec::with_capacity(height as usize * width as usize);
+        for y in 0..height {
+            let source_y = source(y, scale_y, self.height);
+            for x in 0..width {
+                let source_x = source(x, scale_x, self.width);
+                pixels.push(match filter {
+                    Filter::Nearest => {
+                        self.get_pixel(source_y.round() as u16, source_x.round() as u16)
+                    }
+                    Filter::Bilinear => self.bilinear(source_y, source_x),
+                });
+            }
+        }
+        Canvas {
+            height,
+            width,
+            pixels,
+        }
+    }
+
+    fn bilinear(&self, y: f32, x: f32) -> Color {
+        let (top, left) = (y as u16, x as u16);
+        let bottom = cmp::min(top + 1, self.height - 1);
+        let right = cmp::min(left + 1, self.width - 1);
+        let (ratio_y, ratio_x) = (y.fract(), x.fract());
+        let rgb = |y, x| color::to_rgb(self.get_pixel(y, x)).map(f32::from);
+        let lerp = |a: [f32; 3], b: [f32; 3], t: f32| [0, 1, 2].map(|i| a[i] + (b[i] - a[i]) * t);
+        let upper = lerp(rgb(top, left), rgb(top, right), ratio_x);
+        let lower = lerp(rgb(bottom, left), rgb(bottom, right), ratio_x);
+        color::from_rgb(lerp(upper, lower, ratio_y).map(|channel| channel.round() as u8))
+    }
+
+    /// Gets the RGB values of the pixels, row by row, see [`color::to_rgb`].
+    pub fn as_rgb_bytes(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .copied()
+            .flat_map(color::to_rgb)
+            .collect()
+    }
+
+    /// Saves the canvas to a file.
+    ///
+    /// The format is the `WTRM` magic bytes, the height and the width as little endian `u16`,
+    /// then the RGB values of the pixels row by row, see [`Canvas::as_rgb_bytes`].
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut bytes = Vec::with_capacity(8 + self.pixels.len() * 3);
+        bytes.extend(MAGIC);
+        bytes.extend(self.height.to_le_bytes());
+        bytes.extend(self.width.to_le_bytes());
+        bytes.extend(self.as_rgb_bytes());
+        fs::write(path, bytes)
+    }
+
+    /// Loads a canvas saved with [`Canvas::save`], its pixels being [`Color::Rgb`].
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let bytes = fs::read(path)?;
+        let invalid = |message| io::Error::new(ErrorKind::InvalidData, message);
+        let (header, rgb) = bytes
+            .split_at_checked(8)
+            .ok_or_else(|| invalid("missing header"))?;
+        if &header[..4] != MAGIC {
+            return Err(invalid("not a winterm canvas"));
+        }
+        let height = u16::from_le_bytes([header[4], header[5]]);
+        let width = u16::from_le_bytes([header[6], header[7]]);
+        if rgb.len() != height as usize * width as usize * 3 {
+            return Err(invalid("pixels count does not match the dimensions"));
+        }
+        Ok(Canvas {
+            height,
+            width,
+            pixels: rgb
+                .chunks_exact(3)
+                .map(|rgb| color::from_rgb([rgb[0], rgb[1], rgb[2]]))
+                .collect(),
+        })
+    }
+}
+
+/// Loads a canvas saved with [`Canvas::save`], or an image with the `image` feature when the
+/// extension is the one of an image format.
+pub(crate) fn open(path: &Path) -> io::Result<Canvas> {
+    #[cfg(feature = "image")]
+    if image::ImageFormat::from_path(path).is_ok() {
+        return crate::image_interop::open_image(path);
+    }
+    Canvas::load(path)
+}
+
+impl PixelStorage for Canvas {
+    fn height(&self) -> u16 {
+        self.height()
+    }
+
+    fn width(&self) -> u16 {
+        self.width()
+    }
+
+    fn get_pixel(&self, y: u16, x: u16) -> Color {
+        self.get_pixel(y, x)
+    }
+
+    fn set_pixel(&mut self, y: u16, x: u16, color: Color) {
+        self.set_pixel(y, x, color);
+    }
+}
+
+/// Deserialized canvas whose pixels count is not yet checked.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct RawCanvas {
+    height: u16,
+    width: u16,
+    pixels: Vec<Color>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RawCanvas> for Canvas {
+    type Error = String;
+
+    fn try_from(raw: RawCanvas) -> Result<Self, Self::Error> {
+        let expected = raw.height as usize * raw.width as usize;
+        if raw.pixels.len() != expected {
+            return Err(format!(
+                "expected {expected} pixels, found {}",
+                raw.pixels.len()
+            ));
+        }
+        Ok(Canvas {
+            height: raw.height,
+            width: raw.width,
+            pixels: raw.pixels,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Color;
+
+    use super::Canvas;
+    use crate::Margins;
+
+    #[test]
+    fn padded_surrounds_the_pixels() {
+        let canvas = Canvas::new(1, 2, Color::Red);
+        let padded = canvas.padded(Margins::new(1, 2, 3, 4), Color::Blue);
+        assert_eq!((padded.height(), padded.width()), (5, 8));
+        assert_eq!(padded.get_pixel(1, 4), Color::Red);
+        assert_eq!(padded.get_pixel(1, 5), Color::Red);
+        assert_eq!(padded.get_pixel(1, 6), Color::Blue);
+        assert_eq!(padded.get_pixel(0, 4), Color::Blue);
+    }
+
+    #[test]
+    fn padded_to_the_largest_size() {
+        let canvas = Canvas::new(0, 1, Color::Red);
+        let padded = canvas.padded(Margins::new(u16::MAX, 0, 0, 0), Color::Blue);
+        assert_eq!(padded.height(), u16::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "too large")]
+    fn padded_beyond_the_largest_size_panics() {
+        Canvas::new(1, 1, Color::Red).padded(Margins::uniform(u16::MAX), Color::Blue);
+    }
+}
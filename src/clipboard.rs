@@ -0,0 +1,42 @@
+use std::io::Result;
+
+use crate::ansi::{queue, Print};
+use crate::{queue_text_frame, Window};
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+impl Window {
+    /// Copies the last presented frame to the clipboard as ANSI art, using an OSC 52 sequence.
+    ///
+    /// Supporting terminals set the clipboard of the machine they run on, including through SSH,
+    /// the frame can then be pasted in another terminal.
+    pub fn copy_frame_to_clipboard(&mut self) -> Result<()> {
+        let mut text = Vec::new();
+        queue_text_frame(&mut text, &self.front, self.encoding())?;
+        queue!(
+            self.terminal,
+            Print(format!("\x1B]52;c;{}\x1B\\", base64(&text)))
+        )?;
+        self.flush_output()
+    }
+}
+
+/// Encodes bytes with the standard base64 alphabet and padding.
+fn base64(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, &byte)| {
+            group | (byte as u32) << (16 - 8 * i)
+        });
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let index = (group >> (18 - 6 * i)) & 0x3F;
+                encoded.push(BASE64_ALPHABET[index as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
@@ -0,0 +1,524 @@
+//! Conversions between [`Color`] and RGB values, and colormaps.
+
+use std::sync::OnceLock;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Color;
+
+/// RGB values of the 16 ANSI colors, as used by xterm.
+const ANSI_COLORS: [[u8; 3]; 16] = [
+    [0, 0, 0],
+    [128, 0, 0],
+    [0, 128, 0],
+    [128, 128, 0],
+    [0, 0, 128],
+    [128, 0, 128],
+    [0, 128, 128],
+    [192, 192, 192],
+    [128, 128, 128],
+    [255, 0, 0],
+    [0, 255, 0],
+    [255, 255, 0],
+    [0, 0, 255],
+    [255, 0, 255],
+    [0, 255, 255],
+    [255, 255, 255],
+];
+
+/// The 16 ANSI colors, in the order of [`ANSI_COLORS`].
+const NAMED_COLORS: [Color; 16] = [
+    Color::Black,
+    Color::DarkRed,
+    Color::DarkGreen,
+    Color::DarkYellow,
+    Color::DarkBlue,
+    Color::DarkMagenta,
+    Color::DarkCyan,
+    Color::Grey,
+    Color::DarkGrey,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::White,
+];
+
+/// Intensities of the 6x6x6 color cube of the 256 colors palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Gets the RGB values of a color.
+///
+/// Named and ANSI colors use the xterm palette, [`Color::Reset`] is considered black.
+pub fn to_rgb(color: Color) -> [u8; 3] {
+    match color {
+        Color::Reset | Color::Black => ANSI_COLORS[0],
+        Color::DarkRed => ANSI_COLORS[1],
+        Color::DarkGreen => ANSI_COLORS[2],
+        Color::DarkYellow => ANSI_COLORS[3],
+        Color::DarkBlue => ANSI_COLORS[4],
+        Color::DarkMagenta => ANSI_COLORS[5],
+        Color::DarkCyan => ANSI_COLORS[6],
+        Color::Grey => ANSI_COLORS[7],
+        Color::DarkGrey => ANSI_COLORS[8],
+        Color::Red => ANSI_COLORS[9],
+        Color::Green => ANSI_COLORS[10],
+        Color::Yellow => ANSI_COLORS[11],
+        Color::Blue => ANSI_COLORS[12],
+        Color::Magenta => ANSI_COLORS[13],
+        Color::Cyan => ANSI_COLORS[14],
+        Color::White => ANSI_COLORS[15],
+        Color::Rgb { r, g, b } => [r, g, b],
+        Color::AnsiValue(value @ 0..=15) => ANSI_COLORS[value as usize],
+        Color::AnsiValue(value @ 16..=231) => {
+            let value = value - 16;
+            [
+                CUBE_LEVELS[(value / 36) as usize],
+                CUBE_LEVELS[(value / 6 % 6) as usize],
+                CUBE_LEVELS[(value % 6) as usize],
+            ]
+        }
+        Color::AnsiValue(value) => {
+            let level = 8 + 10 * (value - 232);
+            [level, level, level]
+        }
+    }
+}
+
+/// Creates an RGB color.
+pub fn from_rgb([r, g, b]: [u8; 3]) -> Color {
+    Color::Rgb { r, g, b }
+}
+
+/// Gets the hue in degrees, between 0 and 360, and the saturation and value, between 0 and 1, of
+/// a color.
+pub fn to_hsv(color: Color) -> [f32; 3] {
+    let [r, g, b] = to_rgb(color).map(|channel| f32::from(channel) / 255.);
+    let max = r.max(g).max(b);
+    let delta = max - r.min(g).min(b);
+    let hue = if delta == 0. {
+        0.
+    } else if max == r {
+        60. * ((g - b) / delta).rem_euclid(6.)
+    } else if max == g {
+        60. * ((b - r) / delta + 2.)
+    } else {
+        60. * ((r - g) / delta + 4.)
+    };
+    let saturation = if max == 0. { 0. } else { delta / max };
+    [hue, saturation, max]
+}
+
+/// Creates an RGB color from a hue in degrees, wrapped around 360, and a saturation and a value
+/// clamped between 0 and 1.
+pub fn from_hsv(hue: f32, saturation: f32, value: f32) -> Color {
+    let hue = hue.rem_euclid(360.) / 60.;
+    let (saturation, value) = (saturation.clamp(0., 1.), value.clamp(0., 1.));
+    let chroma = value * saturation;
+    let second = chroma * (1. - (hue % 2. - 1.).abs());
+    let [r, g, b] = match hue as u8 {
+        0 => [chroma, second, 0.],
+        1 => [second, chroma, 0.],
+        2 => [0., chroma, second],
+        3 => [0., second, chroma],
+        4 => [second, 0., chroma],
+        _ => [chroma, 0., second],
+    };
+    let minimum = value - chroma;
+    from_rgb([r, g, b].map(|channel| ((channel + minimum) * 255.).round() as u8))
+}
+
+/// Converts sRGB values to CIELAB coordinates, with the D65 white point.
+fn to_lab(rgb: [u8; 3]) -> [f32; 3] {
+    let [r, g, b] = rgb.map(|channel| {
+        let channel = f32::from(channel) / 255.;
+        if channel <= 0.04045 {
+            channel / 12.92
+        } else {
+            ((channel + 0.055) / 1.055).powf(2.4)
+        }
+    });
+    let xyz = [
+        (0.4124 * r + 0.3576 * g + 0.1805 * b) / 0.95047,
+        0.2126 * r + 0.7152 * g + 0.0722 * b,
+        (0.0193 * r + 0.1192 * g + 0.9505 * b) / 1.08883,
+    ];
+    let [x, y, z] = xyz.map(|value| {
+        if value > 216. / 24389. {
+            value.cbrt()
+        } else {
+            (24389. / 27. * value + 16.) / 116.
+        }
+    });
+    [116. * y - 16., 500. * (x - y), 200. * (y - z)]
+}
+
+/// Gets the nearest color of the 6x6x6 cube of the 256 colors palette, the other colors being
+/// kept.
+pub fn to_ansi_256(color: Color) -> Color {
+    let Color::Rgb { r, g, b } = color else {
+        return color;
+    };
+    // Index of the nearest of the `CUBE_LEVELS`, the lower one on ties.
+    let level = |value: u8| match value {
+        0..=47 => 0,
+        48..=115 => 1,
+        _ => (value - 36) / 40,
+    };
+    Color::AnsiValue(16 + 36 * level(r) + 6 * level(g) + level(b))
+}
+
+/// Checks if a color is light, its relative luminance being above one half.
+pub fn is_light(color: Color) -> bool {
+    let [r, g, b] = to_rgb(color).map(f32::from);
+    0.2126 * r + 0.7152 * g + 0.0722 * b > 127.5
+}
+
+/// Gets the nearest of the 16 ANSI colors, [`Color::Reset`] being kept.
+///
+/// The colors other than the ANSI ones are looked up in a [`Quantizer`] of the nearest colors in
+/// CIELAB, built by the first call, so that e.g. skin tones and pastels keep their hue.
+pub fn to_ansi(color: Color) -> Color {
+    static QUANTIZER: OnceLock<Quantizer> = OnceLock::new();
+    if color == Color::Reset {
+        return color;
+    }
+    let rgb = to_rgb(color);
+    if let Some(index) = ANSI_COLORS.iter().position(|&ansi| ansi == rgb) {
+        return NAMED_COLORS[index];
+    }
+    let quantizer = QUANTIZER.get_or_init(|| Quantizer::perceptual(&NAMED_COLORS));
+    NAMED_COLORS[quantizer.nearest_index(color) as usize]
+}
+
+/// Levels of each channel in the lookup table of a [`Quantizer`].
+const QUANTIZER_LEVELS: usize = 32;
+
+/// Lookup table of the nearest color of a palette, for each of 32x32x32 RGB values.
+///
+/// Building it searches the palette once per entry, then each lookup is a single index, so that
+/// quantizing whole frames adds negligible time. The colors are approximated to their 5 most
+/// significant bits per channel.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Quantizer {
+    palette: Vec<Color>,
+    table: Box<[u8]>,
+}
+
+impl Quantizer {
+    /// Builds the lookup table of `palette`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `palette` is empty or has more than 256 colors.
+    pub fn new(palette: &[Color]) -> Self {
+        Quantizer::with_space(palette, |rgb| rgb.map(f32::from))
+    }
+
+    /// Builds the lookup table of `palette`, the nearest colors being the nearest in CIELAB,
+    /// whose distances are closer to the perceived differences than the RGB ones.
+    fn perceptual(palette: &[Color]) -> Self {
+        Quantizer::with_space(palette, to_lab)
+    }
+
+    /// Builds the lookup table of `palette`, the distances being euclidean in the color space
+    /// the RGB values are converted to by `space`.
+    fn with_space(palette: &[Color], space: impl Fn([u8; 3]) -> [f32; 3]) -> Self {
+        assert!(
+            (1..=256).contains(&palette.len()),
+            "the palette must have between 1 and 256 colors"
+        );
+        let palette_coordinates: Vec<[f32; 3]> =
+            palette.iter().map(|&color| space(to_rgb(color))).collect();
+        let step = 256 / QUANTIZER_LEVELS;
+        let center = |level: usize| (level * step + step / 2) as u8;
+        let mut table = Vec::with_capacity(QUANTIZER_LEVELS.pow(3));
+        for r in 0..QUANTIZER_LEVELS {
+            for g in 0..QUANTIZER_LEVELS {
+                for b in 0..QUANTIZER_LEVELS {
+                    let [x, y, z] = space([center(r), center(g), center(b)]);
+                    let distance = |i: usize| {
+                        let [px, py, pz] = palette_coordinates[i];
+                        (x - px).powi(2) + (y - py).powi(2) + (z - pz).powi(2)
+                    };
+                    let nearest = (0..palette_coordinates.len())
+                        .min_by(|&i, &j| distance(i).total_cmp(&distance(j)))
+                        .unwrap_or(0);
+                    table.push(nearest as u8);
+                }
+            }
+        }
+        Quantizer {
+            palette: palette.to_vec(),
+            table: table.into_boxed_slice(),
+        }
+    }
+
+    /// Gets the palette.
+    pub fn palette(&self) -> &[Color] {
+        &self.palette
+    }
+
+    /// Gets the index of the palette color nearest to `color`.
+    pub fn nearest_index(&self, color: Color) -> u8 {
+        let shift = 8 - QUANTIZER_LEVELS.ilog2();
+        let [r, g, b] = to_rgb(color).map(|channel| (channel >> shift) as usize);
+        self.table[(r * QUANTIZER_LEVELS + g) * QUANTIZER_LEVELS + b]
+    }
+
+    /// Gets the palette color nearest to `color`.
+    pub fn nearest(&self, color: Color) -> Color {
+        self.palette[self.nearest_index(color) as usize]
+    }
+}
+
+/// Extracts a palette of up to `colors` colors from `pixels`, e.g. the pixels of a loaded photo,
+/// with the median cut algorithm.
+///
+/// The pixels are split into `colors` boxes, the box with the widest range of a channel being
+/// split at its median along that channel, and each palette color is the average of a box. The
+/// palette is empty if there are no pixels, and has fewer colors if there are fewer distinct
+/// ones.
+///
+/// ```
+/// use winterm::Color;
+/// use winterm::color;
+///
+/// let pixels = [Color::Red, Color::Red, Color::Blue, Color::Rgb { r: 0, g: 0, b: 250 }];
+/// assert_eq!(
+///     color::median_cut(&pixels, 2),
+///     [Color::Rgb { r: 255, g: 0, b: 0 }, Color::Rgb { r: 0, g: 0, b: 253 }],
+/// );
+/// ```
+pub fn median_cut(pixels: &[Color], colors: usize) -> Vec<Color> {
+    let mut rgb: Vec<[u8; 3]> = pixels.iter().map(|&color| to_rgb(color)).collect();
+    if rgb.is_empty() || colors == 0 {
+        return Vec::new();
+    }
+    // Widest channel of a box, and its range.
+    let widest = |pixels: &[[u8; 3]]| {
+        (0..3)
+            .map(|channel| {
+                let values = pixels.iter().map(|pixel| pixel[channel]);
+                let range = values.clone().max().unwrap_or(0) - values.min().unwrap_or(0);
+                (channel, range)
+            })
+            .max_by_key(|&(_, range)| range)
+            .unwrap_or((0, 0))
+    };
+    let mut boxes = Vec::with_capacity(colors.min(rgb.len()));
+    boxes.push(0..rgb.len());
+    while boxes.len() < colors {
+        let Some((i, channel)) = boxes
+            .iter()
+            .enumerate()
+            .map(|(i, pixels)| (i, widest(&rgb[pixels.clone()])))
+            .filter(|&(_, (_, range))| range > 0)
+            .max_by_key(|&(_, (_, range))| range)
+            .map(|(i, (channel, _))| (i, channel))
+        else {
+            break;
+        };
+        let pixels = boxes[i].clone();
+        rgb[pixels.clone()].sort_unstable_by_key(|pixel| pixel[channel]);
+        // The median is moved past the pixels of the same value, for them to stay in one box.
+        let values = &rgb[pixels.clone()];
+        let median = values[values.len() / 2][channel];
+        let split = pixels.start + values.partition_point(|pixel| pixel[channel] < median);
+        let split = if split == pixels.start {
+            pixels.start + values.partition_point(|pixel| pixel[channel] <= median)
+        } else {
+            split
+        };
+        boxes[i] = pixels.start..split;
+        boxes.push(split..pixels.end);
+    }
+    boxes
+        .into_iter()
+        .map(|pixels| {
+            let count = pixels.len() as u64;
+            let sums = rgb[pixels].iter().fold([0u64; 3], |sums, pixel| {
+                [0, 1, 2].map(|channel| sums[channel] + u64::from(pixel[channel]))
+            });
+            from_rgb(sums.map(|sum| ((sum + count / 2) / count) as u8))
+        })
+        .collect()
+}
+
+/// Evenly spaced colors of the viridis colormap, interpolated by [`Colormap::Viridis`].
+const VIRIDIS: [[u8; 3]; 5] = [
+    [68, 1, 84],
+    [59, 82, 139],
+    [33, 145, 140],
+    [94, 201, 98],
+    [253, 231, 37],
+];
+
+/// Evenly spaced colors of the magma colormap, interpolated by [`Colormap::Magma`].
+const MAGMA: [[u8; 3]; 6] = [
+    [0, 0, 4],
+    [59, 15, 112],
+    [140, 41, 129],
+    [222, 73, 104],
+    [254, 159, 109],
+    [252, 253, 191],
+];
+
+/// Gradient mapping values between 0 and 1 to colors, for heatmaps.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Colormap {
+    /// Perceptually uniform, from dark blue to yellow.
+    #[default]
+    Viridis,
+    /// Perceptually uniform, from black to light yellow through purple.
+    Magma,
+    /// From black to white.
+    Grayscale,
+}
+
+impl Colormap {
+    /// Gets the color of `value`, clamped between 0 and 1.
+    pub fn color(self, value: f64) -> Color {
+        let stops: &[[u8; 3]] = match self {
+            Colormap::Viridis => &VIRIDIS,
+            Colormap::Magma => &MAGMA,
+            Colormap::Grayscale => &[[0, 0, 0], [255, 255, 255]],
+        };
+        let position = if value.is_nan() {
+            0.
+        } else {
+            value.clamp(0., 1.) * (stops.len() - 1) as f64
+        };
+        let index = (position as usize).min(stops.len() - 2);
+        let ratio = position - index as f64;
+        let [start, end] = [stops[index], stops[index + 1]];
+        from_rgb([0, 1, 2].map(|channel| {
+            (start[channel] as f64 + (end[channel] as f64 - start[channel] as f64) * ratio).round()
+                as u8
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Color;
+
+    use super::*;
+
+    fn rgb(r: u8, g: u8, b: u8) -> Color {
+        Color::Rgb { r, g, b }
+    }
+
+    #[test]
+    fn median_cut_without_pixels_or_colors() {
+        assert!(median_cut(&[], 4).is_empty());
+        assert!(median_cut(&[rgb(1, 2, 3)], 0).is_empty());
+    }
+
+    #[test]
+    fn median_cut_keeps_the_distinct_colors_when_there_are_fewer() {
+        let pixels = [
+            rgb(10, 20, 30),
+            rgb(200, 0, 0),
+            rgb(10, 20, 30),
+            rgb(0, 0, 255),
+        ];
+        let mut palette = median_cut(&pixels, 8);
+        palette.sort_by_key(|&color| to_rgb(color));
+        assert_eq!(palette, [rgb(0, 0, 255), rgb(10, 20, 30), rgb(200, 0, 0)]);
+    }
+
+    #[test]
+    fn median_cut_averages_the_clusters() {
+        let pixels = [
+            rgb(0, 0, 0),
+            rgb(250, 100, 0),
+            rgb(4, 4, 4),
+            rgb(254, 104, 0),
+        ];
+        let mut palette = median_cut(&pixels, 2);
+        palette.sort_by_key(|&color| to_rgb(color));
+        assert_eq!(palette, [rgb(2, 2, 2), rgb(252, 102, 0)]);
+    }
+
+    #[test]
+    fn median_cut_splits_along_the_widest_channel() {
+        // The green range is the widest, so the split separates the low and high greens.
+        let pixels = [rgb(0, 0, 0), rgb(40, 0, 0), rgb(0, 200, 0), rgb(40, 200, 0)];
+        let mut palette = median_cut(&pixels, 2);
+        palette.sort_by_key(|&color| to_rgb(color));
+        assert_eq!(palette, [rgb(20, 0, 0), rgb(20, 200, 0)]);
+    }
+
+    #[test]
+    fn median_cut_never_exceeds_the_requested_colors() {
+        let pixels: Vec<Color> = (0..=255)
+            .map(|value| rgb(value, 255 - value, value / 2))
+            .collect();
+        for colors in [1, 3, 16, 100] {
+            assert_eq!(median_cut(&pixels, colors).len(), colors);
+        }
+    }
+
+    #[test]
+    fn quantizer_maps_palette_colors_to_themselves() {
+        let palette = [
+            rgb(0, 0, 0),
+            rgb(255, 0, 0),
+            rgb(0, 128, 255),
+            rgb(255, 255, 255),
+        ];
+        let quantizer = Quantizer::new(&palette);
+        for (i, &color) in palette.iter().enumerate() {
+            assert_eq!(quantizer.nearest_index(color), i as u8);
+            assert_eq!(quantizer.nearest(color), color);
+        }
+    }
+
+    #[test]
+    fn quantizer_picks_the_nearest_color() {
+        let quantizer = Quantizer::new(&[rgb(0, 0, 0), rgb(255, 255, 255), rgb(255, 0, 0)]);
+        assert_eq!(quantizer.nearest(rgb(30, 20, 25)), rgb(0, 0, 0));
+        assert_eq!(quantizer.nearest(rgb(230, 240, 220)), rgb(255, 255, 255));
+        assert_eq!(quantizer.nearest(rgb(200, 40, 30)), rgb(255, 0, 0));
+        assert_eq!(quantizer.nearest(Color::DarkRed), rgb(255, 0, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "the palette must have between 1 and 256 colors")]
+    fn quantizer_rejects_an_empty_palette() {
+        Quantizer::new(&[]);
+    }
+
+    #[test]
+    fn to_lab_of_black_and_white() {
+        let close = |a: [f32; 3], b: [f32; 3]| a.iter().zip(b).all(|(a, b)| (a - b).abs() < 0.1);
+        assert!(close(to_lab([0, 0, 0]), [0., 0., 0.]));
+        assert!(close(to_lab([255, 255, 255]), [100., 0., 0.]));
+        let [l, a, b] = to_lab([255, 0, 0]);
+        assert!((l - 53.2).abs() < 0.5 && a > 75. && b > 60.);
+    }
+
+    #[test]
+    fn to_ansi_keeps_the_ansi_colors() {
+        for (color, rgb) in NAMED_COLORS.into_iter().zip(ANSI_COLORS) {
+            assert_eq!(to_ansi(color), color);
+            assert_eq!(to_ansi(from_rgb(rgb)), color);
+        }
+        assert_eq!(to_ansi(Color::Reset), Color::Reset);
+        assert_eq!(to_ansi(Color::AnsiValue(9)), Color::Red);
+    }
+
+    #[test]
+    fn to_ansi_keeps_the_hue() {
+        assert_eq!(to_ansi(rgb(180, 120, 200)), Color::DarkMagenta);
+        assert_eq!(to_ansi(rgb(150, 220, 150)), Color::DarkGreen);
+        assert_eq!(to_ansi(rgb(40, 40, 160)), Color::DarkBlue);
+        assert_eq!(to_ansi(rgb(250, 250, 140)), Color::Yellow);
+        assert_eq!(to_ansi(rgb(100, 30, 30)), Color::DarkRed);
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::input::{Event, KeyEventKind};
+use crate::{Instant, Key, Window};
+
+/// Debounce intervals of the keys, and time of their last accepted press.
+#[derive(Debug, Default)]
+pub(crate) struct Debounce {
+    default: Option<Duration>,
+    intervals: HashMap<Key, Option<Duration>>,
+    last_press: HashMap<Key, Instant>,
+}
+
+impl Debounce {
+    /// Checks if `event` should be kept, i.e. it is not a press of a key already pressed within
+    /// its debounce interval.
+    pub(crate) fn accept(&mut self, event: &Event, now: Instant) -> bool {
+        let Event::Key(key_event) = event else {
+            return true;
+        };
+        if key_event.kind != KeyEventKind::Press {
+            return true;
+        }
+        let key = key_event.code;
+        let interval = self.intervals.get(&key).copied().unwrap_or(self.default);
+        let Some(interval) = interval else {
+            return true;
+        };
+        if self
+            .last_press
+            .get(&key)
+            .is_some_and(|&last_press| now - last_press < interval)
+        {
+            return false;
+        }
+        self.last_press.insert(key, now);
+        true
+    }
+}
+
+impl Window {
+    /// Sets the debounce interval of the keys without their own, disabled with `None`, the
+    /// default.
+    ///
+    /// Presses of a key within the interval of its last reported press are dropped by
+    /// [`Window::poll_events`], so that terminals delivering duplicate events report one press.
+    /// Held keys are also reported at most once per interval.
+    pub fn set_key_debounce(&mut self, interval: Option<Duration>) {
+        self.debounce.default = interval;
+    }
+
+    /// Sets the debounce interval of `key`, overriding the one of
+    /// [`Window::set_key_debounce`], `None` disabling it for that key.
+    pub fn set_key_debounce_for(&mut self, key: impl Into<Key>, interval: Option<Duration>) {
+        self.debounce.intervals.insert(key.into(), interval);
+    }
+
+    /// Removes the debounce interval of `key`, which uses the one of
+    /// [`Window::set_key_debounce`] again.
+    pub fn clear_key_debounce_for(&mut self, key: impl Into<Key>) {
+        self.debounce.intervals.remove(&key.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::Debounce;
+    use crate::input::{Event, KeyEvent, KeyEventKind};
+    use crate::{Key, Modifiers};
+
+    fn press(code: Key) -> Event {
+        Event::Key(KeyEvent::new(code, Modifiers::NONE))
+    }
+
+    #[test]
+    fn presses_within_the_interval_are_dropped() {
+        let mut debounce = Debounce {
+            default: Some(Duration::from_millis(100)),
+            ..Debounce::default()
+        };
+        let start = Instant::now();
+        assert!(debounce.accept(&press(Key::Char('a')), start));
+        assert!(!debounce.accept(&press(Key::Char('a')), start + Duration::from_millis(50)));
+        assert!(debounce.accept(&press(Key::Char('b')), start + Duration::from_millis(50)));
+        // The interval starts from the last accepted press, not from the dropped one.
+        assert!(debounce.accept(&press(Key::Char('a')), start + Duration::from_millis(100)));
+    }
+
+    #[test]
+    fn per_key_intervals_override_the_default() {
+        let mut debounce = Debounce {
+            default: Some(Duration::from_millis(100)),
+            ..Debounce::default()
+        };
+        debounce.intervals.insert(Key::Enter, None);
+        debounce
+            .intervals
+            .insert(Key::Esc, Some(Duration::from_secs(1)));
+        let start = Instant::now();
+        let later = start + Duration::from_millis(500);
+        assert!(debounce.accept(&press(Key::Enter), start));
+        assert!(debounce.accept(&press(Key::Enter), start));
+        assert!(debounce.accept(&press(Key::Esc), start));
+        assert!(!debounce.accept(&press(Key::Esc), later));
+    }
+
+    #[test]
+    fn only_presses_are_debounced() {
+        let mut debounce = Debounce {
+            default: Some(Duration::from_secs(1)),
+            ..Debounce::default()
+        };
+        let now = Instant::now();
+        let release = Event::Key(KeyEvent::new_with_kind(
+            Key::Up,
+            Modifiers::NONE,
+            KeyEventKind::Release,
+        ));
+        assert!(debounce.accept(&press(Key::Up), now));
+        assert!(debounce.accept(&release, now));
+        assert!(debounce.accept(&release, now));
+        assert!(debounce.accept(&Event::Resize(10, 10), now));
+        assert!(!debounce.accept(&press(Key::Up), now));
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let mut debounce = Debounce::default();
+        let now = Instant::now();
+        assert!(debounce.accept(&press(Key::Up), now));
+        assert!(debounce.accept(&press(Key::Up), now));
+    }
+}
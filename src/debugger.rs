@@ -0,0 +1,86 @@
+use std::collections::VecDeque;
+use std::io::Result;
+use std::mem;
+
+use crate::ansi::{queue, MoveTo, Print, SetColors};
+use crate::input::{Event, KeyEvent, KeyEventKind};
+use crate::{Canvas, Color, Key, Window};
+
+impl Window {
+    /// Sets the key opening the frame debugger during [`Window::poll_events`], disabled by
+    /// default.
+    ///
+    /// The debugger freezes the application until the key is pressed again, or Escape, and steps
+    /// through the frames of the history, see [`Window::set_frame_history`], with the Left and
+    /// Right arrows, or `h` and `l`.
+    pub fn set_debugger_key(&mut self, key: Option<Key>) {
+        self.debugger_key = key;
+    }
+
+    /// Runs the frame debugger if its key was pressed in the last events.
+    pub(crate) fn update_debugger(&mut self) -> Result<()> {
+        let Some(key) = self.debugger_key else {
+            return Ok(());
+        };
+        if !self.get_key(key) {
+            return Ok(());
+        }
+        let history = mem::take(&mut self.history.frames);
+        let presented = self.front.clone();
+        let result = self.debug_frames(&history, &presented);
+        self.history.frames = history;
+        self.front = presented;
+        result.and_then(|()| self.redraw_all())
+    }
+
+    /// Displays the frames of the history, from the newest one, until the debugger is closed.
+    fn debug_frames(&mut self, history: &VecDeque<Canvas>, presented: &Canvas) -> Result<()> {
+        let mut age = 0;
+        loop {
+            self.front.clone_from(history.get(age).unwrap_or(presented));
+            self.redraw_all()?;
+            self.queue_debugger_status(age, history.len())?;
+            self.flush_output()?;
+            match self.terminal.backend.read_event()? {
+                Event::Key(KeyEvent {
+                    code,
+                    kind: KeyEventKind::Press,
+                    ..
+                }) => match code {
+                    Key::Left | Key::Char('h') => {
+                        age = (age + 1).min(history.len().saturating_sub(1));
+                    }
+                    Key::Right | Key::Char('l') => age = age.saturating_sub(1),
+                    Key::Esc => return Ok(()),
+                    code if Some(code) == self.debugger_key => return Ok(()),
+                    _ => (),
+                },
+                Event::Resize(columns, rows) => {
+                    self.terminal_size.x = columns;
+                    self.terminal_size.y = rows;
+                    self.calculate_origin();
+                }
+                _ => (),
+            }
+        }
+    }
+
+    fn queue_debugger_status(&mut self, age: usize, frames: usize) -> Result<()> {
+        let status = if frames == 0 {
+            " no frame history, Esc: resume ".to_owned()
+        } else {
+            format!(
+                " frame {}/{frames} (-{age}), Left/Right: step, Esc: resume ",
+                frames - age
+            )
+        };
+        let colors = self.overlay_colors();
+        queue!(
+            self.terminal,
+            MoveTo(0, self.terminal_size.y.saturating_sub(1)),
+            colors,
+            Print(status),
+            SetColors(Color::Reset, Color::Reset)
+        )
+    }
+}
@@ -0,0 +1,28 @@
+use crate::{Color, Window};
+
+impl Window {
+    /// Sets a pixel color if `depth` is nearer than the one of the pixel, smaller being nearer.
+    ///
+    /// The depth buffer is allocated by the first call, every depth being infinite, see
+    /// [`Window::clear_depth`].
+    pub fn set_pixel_depth(&mut self, y: u16, x: u16, color: Color, depth: f32) {
+        let (y, x) = self.wrap(y, x);
+        if self.is_clipped(y, x) {
+            return;
+        }
+        let length = self.height() as usize * self.width() as usize;
+        if self.depth.len() != length {
+            self.depth = vec![f32::INFINITY; length];
+        }
+        let index = y as usize * self.width() as usize + x as usize;
+        if depth < self.depth[index] {
+            self.depth[index] = depth;
+            self.set_pixel(y, x, color);
+        }
+    }
+
+    /// Resets the depth of every pixel to infinity, usually at the start of each frame.
+    pub fn clear_depth(&mut self) {
+        self.depth.fill(f32::INFINITY);
+    }
+}
@@ -0,0 +1,122 @@
+use std::cmp::Reverse;
+use std::fmt::Write;
+
+use crate::{color, Color, Rect, Window};
+
+/// Names of the 16 ANSI colors, the pixel colors being described by the nearest one.
+fn color_name(color: Color) -> &'static str {
+    match color::to_ansi(color) {
+        Color::DarkRed => "dark red",
+        Color::DarkGreen => "dark green",
+        Color::DarkYellow => "olive",
+        Color::DarkBlue => "dark blue",
+        Color::DarkMagenta => "purple",
+        Color::DarkCyan => "teal",
+        Color::Grey => "light grey",
+        Color::DarkGrey => "grey",
+        Color::Red => "red",
+        Color::Green => "green",
+        Color::Yellow => "yellow",
+        Color::Blue => "blue",
+        Color::Magenta => "magenta",
+        Color::Cyan => "cyan",
+        Color::White => "white",
+        _ => "black",
+    }
+}
+
+/// Names of the thirds of the window, from top to bottom and from left to right.
+const VERTICAL_THIRDS: [&str; 3] = ["top", "middle", "bottom"];
+const HORIZONTAL_THIRDS: [&str; 3] = ["left", "center", "right"];
+
+impl Window {
+    /// Tags a region of the frame with `label`, e.g. a sprite, for [`Window::describe_frame`],
+    /// replacing the region previously tagged with the same label.
+    pub fn tag_region(&mut self, label: &str, rect: Rect) {
+        match self.tags.iter_mut().find(|(tag, _)| tag == label) {
+            Some((_, tagged)) => *tagged = rect,
+            None => self.tags.push((label.to_owned(), rect)),
+        }
+    }
+
+    /// Removes the tag `label`, see [`Window::tag_region`].
+    pub fn untag_region(&mut self, label: &str) {
+        self.tags.retain(|(tag, _)| tag != label);
+    }
+
+    /// Describes the pixels in text, e.g. to be read by a screen reader or a braille display.
+    ///
+    /// The description lists the dominant colors, the dominant color of each third of the
+    /// window, and the tagged regions with their position, see [`Window::tag_region`].
+    pub fn describe_frame(&self) -> String {
+        let (height, width) = (self.height(), self.width());
+        let mut description = format!("Frame of {height} by {width} pixels");
+        if height == 0 || width == 0 {
+            description.push('.');
+            return description;
+        }
+        let dominant = self.dominant_colors(Rect::new(0, 0, height, width));
+        let total: usize = dominant.iter().map(|(_, count)| count).sum();
+        let colors: Vec<String> = dominant
+            .iter()
+            .take(3)
+            .map(|(name, count)| format!("{name} {}%", count * 100 / total))
+            .collect();
+        let _ = writeln!(description, ", mostly {}.", colors.join(", "));
+        let mut regions = Vec::new();
+        for (third_y, vertical) in VERTICAL_THIRDS.iter().enumerate() {
+            for (third_x, horizontal) in HORIZONTAL_THIRDS.iter().enumerate() {
+                let rect = third(height, width, third_y, third_x);
+                if let Some((name, _)) = self.dominant_colors(rect).first() {
+                    regions.push(format!("{vertical} {horizontal} {name}"));
+                }
+            }
+        }
+        let _ = writeln!(description, "Regions: {}.", regions.join(", "));
+        for (label, rect) in &self.tags {
+            let center_y = rect.y.saturating_add(rect.height / 2);
+            let center_x = rect.x.saturating_add(rect.width / 2);
+            if center_y >= height || center_x >= width {
+                let _ = writeln!(description, "{label}: outside of the frame.");
+                continue;
+            }
+            let vertical = VERTICAL_THIRDS[center_y as usize * 3 / height as usize];
+            let horizontal = HORIZONTAL_THIRDS[center_x as usize * 3 / width as usize];
+            let _ = writeln!(
+                description,
+                "{label}: {vertical} {horizontal}, at y {} and x {}, {} by {} pixels.",
+                rect.y, rect.x, rect.height, rect.width
+            );
+        }
+        description.truncate(description.trim_end().len());
+        description
+    }
+
+    /// Gets the names of the colors of `rect` with their number of pixels, most frequent first.
+    fn dominant_colors(&self, rect: Rect) -> Vec<(&'static str, usize)> {
+        let mut counts: Vec<(&'static str, usize)> = Vec::new();
+        for y in rect.y..rect.end_y().min(self.height()) {
+            for x in rect.x..rect.end_x().min(self.width()) {
+                let name = color_name(self.get_pixel(y, x));
+                match counts.iter_mut().find(|(counted, _)| *counted == name) {
+                    Some((_, count)) => *count += 1,
+                    None => counts.push((name, 1)),
+                }
+            }
+        }
+        counts.sort_by_key(|&(_, count)| Reverse(count));
+        counts
+    }
+}
+
+/// Gets the rectangle of a third of the window, possibly empty for tiny windows.
+fn third(height: u16, width: u16, third_y: usize, third_x: usize) -> Rect {
+    let bound = |size: u16, third: usize| (size as usize * third / 3) as u16;
+    let (y, x) = (bound(height, third_y), bound(width, third_x));
+    Rect::new(
+        y,
+        x,
+        bound(height, third_y + 1) - y,
+        bound(width, third_x + 1) - x,
+    )
+}
@@ -0,0 +1,125 @@
+use std::cmp;
+use std::io::Result;
+
+use crate::ansi::{queue, MoveTo, Print, SetColors};
+use crate::input::{Event, KeyEventKind};
+use crate::{Color, Key, Window};
+
+impl Window {
+    /// Displays a message box over the current frame and waits for one of `buttons` to be chosen.
+    ///
+    /// The buttons are selected with the arrow keys or Tab and chosen with Enter.
+    /// Returns the index of the chosen button, or `None` if the message box was closed with Escape.
+    pub fn message_box(
+        &mut self,
+        title: &str,
+        text: &str,
+        buttons: &[&str],
+    ) -> Result<Option<usize>> {
+        let mut selected = 0;
+        let choice = loop {
+            self.queue_message_box(title, text, buttons, selected)?;
+            self.flush_output()?;
+            match self.terminal.backend.read_event()? {
+                Event::Key(key_event) if key_event.kind != KeyEventKind::Release => {
+                    match key_event.code {
+                        Key::Left | Key::Up | Key::BackTab => {
+                            selected = selected
+                                .checked_sub(1)
+                                .unwrap_or(buttons.len().saturating_sub(1));
+                        }
+                        Key::Right | Key::Down | Key::Tab => {
+                            selected = (selected + 1) % cmp::max(buttons.len(), 1);
+                        }
+                        Key::Enter => break (!buttons.is_empty()).then_some(selected),
+                        Key::Esc => break None,
+                        _ => (),
+                    }
+                }
+                Event::Resize(columns, rows) => {
+                    self.terminal_size.x = columns;
+                    self.terminal_size.y = rows;
+                    self.calculate_origin();
+                    self.redraw_all()?;
+                }
+                _ => (),
+            }
+        };
+        self.redraw_all()?;
+        Ok(choice)
+    }
+
+    fn queue_message_box(
+        &mut self,
+        title: &str,
+        text: &str,
+        buttons: &[&str],
+        selected: usize,
+    ) -> Result<()> {
+        let buttons_width: usize = buttons
+            .iter()
+            .map(|button| button.chars().count() + 5)
+            .sum();
+        let content_width = text
+            .lines()
+            .map(|line| line.chars().count())
+            .chain([title.chars().count() + 2, buttons_width.saturating_sub(1)])
+            .max()
+            .unwrap_or(0);
+        let inner_width = cmp::min(
+            content_width + 2,
+            (self.terminal_size.x as usize).saturating_sub(2),
+        );
+        let height = text.lines().count() + 4;
+        let x = (self.terminal_size.x as usize).saturating_sub(inner_width + 2) as u16 / 2;
+        let mut y = (self.terminal_size.y as usize).saturating_sub(height) as u16 / 2;
+        let colors = self.overlay_colors();
+        let selected_colors = SetColors(colors.1, colors.0);
+        let mut lines = vec![format!(
+            "┌{:─^inner_width$.inner_width$}┐",
+            format!(" {title} ")
+        )];
+        lines.extend(text.lines().map(|line| {
+            format!(
+                "│ {line:<width$.width$} │",
+                width = inner_width.saturating_sub(2)
+            )
+        }));
+        lines.push(format!("│{:inner_width$}│", ""));
+        queue!(self.terminal, colors)?;
+        for line in lines {
+            queue!(self.terminal, MoveTo(x, y), Print(line))?;
+            y += 1;
+        }
+        queue!(
+            self.terminal,
+            MoveTo(x, y),
+            Print(format!("│{:inner_width$}│", "")),
+            MoveTo(
+                x + 1 + (inner_width.saturating_sub(buttons_width.saturating_sub(1)) / 2) as u16,
+                y
+            )
+        )?;
+        for (i, button) in buttons.iter().enumerate() {
+            let button_colors = if i == selected {
+                selected_colors
+            } else {
+                colors
+            };
+            queue!(
+                self.terminal,
+                button_colors,
+                Print(format!("[ {button} ]")),
+                colors,
+                Print(" ")
+            )?;
+        }
+        queue!(
+            self.terminal,
+            MoveTo(x, y + 1),
+            Print(format!("└{:─<inner_width$}┘", "")),
+            SetColors(Color::Reset, Color::Reset)
+        )?;
+        Ok(())
+    }
+}
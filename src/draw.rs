@@ -0,0 +1,419 @@
+use std::cmp;
+
+use crate::raster::{self, Shading, Vertex};
+use crate::widgets::FillDirection;
+use crate::{color, Assets, Canvas, Filter, Handle, Rect, Window};
+use crate::{
+    font::{self, BitmapFont, ADVANCE_X, ADVANCE_Y, GLYPH_HEIGHT, GLYPH_WIDTH},
+    Color,
+};
+
+/// 4x4 Bayer matrix, the thresholds of the ordered dithering.
+const BAYER_MATRIX: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Pixel buffer that can be drawn into.
+///
+/// Drawing outside of the buffer is ignored by the provided methods.
+pub trait Draw {
+    /// Gets the buffer height.
+    fn height(&self) -> u16;
+
+    /// Gets the buffer width.
+    fn width(&self) -> u16;
+
+    /// Gets a pixel color.
+    fn get_pixel(&self, y: u16, x: u16) -> Color;
+
+    /// Sets a pixel color.
+    fn set_pixel(&mut self, y: u16, x: u16, color: Color);
+
+    /// Fills a rectangle with `color`.
+    fn fill_rect(&mut self, rect: Rect, color: Color) {
+        for y in rect.y..cmp::min(rect.end_y(), self.height()) {
+            for x in rect.x..cmp::min(rect.end_x(), self.width()) {
+                self.set_pixel(y, x, color);
+            }
+        }
+    }
+
+    /// Fills a rectangle with a linear gradient from `from` to `to`, in `direction`.
+    fn fill_rect_gradient(&mut self, rect: Rect, from: Color, to: Color, direction: FillDirection) {
+        fill_gradient(self, rect, [from, to], direction, None);
+    }
+
+    /// Fills a rectangle like [`Draw::fill_rect_gradient`], each channel being reduced to
+    /// `levels` values with an ordered dithering, e.g. to avoid banding with a limited palette.
+    fn fill_rect_gradient_dithered(
+        &mut self,
+        rect: Rect,
+        from: Color,
+        to: Color,
+        direction: FillDirection,
+        levels: u8,
+    ) {
+        fill_gradient(self, rect, [from, to], direction, Some(levels));
+    }
+
+    /// Fills a rectangle with `pattern` repeated, its top left corner being at the top left
+    /// corner of the rectangle.
+    fn fill_rect_pattern(&mut self, rect: Rect, pattern: &Canvas) {
+        if pattern.height() == 0 || pattern.width() == 0 {
+            return;
+        }
+        for y in rect.y..cmp::min(rect.end_y(), self.height()) {
+            for x in rect.x..cmp::min(rect.end_x(), self.width()) {
+                let color = pattern.get_pixel(
+                    (y - rect.y) % pattern.height(),
+                    (x - rect.x) % pattern.width(),
+                );
+                self.set_pixel(y, x, color);
+            }
+        }
+    }
+
+    /// Draws the outline of a rectangle with `color`.
+    fn draw_rect(&mut self, rect: Rect, color: Color) {
+        if rect.height == 0 || rect.width == 0 {
+            return;
+        }
+        self.fill_rect(Rect::new(rect.y, rect.x, 1, rect.width), color);
+        self.fill_rect(Rect::new(rect.end_y() - 1, rect.x, 1, rect.width), color);
+        self.fill_rect(Rect::new(rect.y, rect.x, rect.height, 1), color);
+        self.fill_rect(Rect::new(rect.y, rect.end_x() - 1, rect.height, 1), color);
+    }
+
+    /// Draws a line from `y0` and `x0` to `y1` and `x1`, both ends included, with `color`.
+    ///
+    /// The coordinates can be outside of the buffer, only the visible part being drawn.
+    fn draw_line(&mut self, y0: i32, x0: i32, y1: i32, x1: i32, color: Color) {
+        let (height, width) = (i32::from(self.height()), i32::from(self.width()));
+        if (y0 < 0 && y1 < 0)
+            || (x0 < 0 && x1 < 0)
+            || (y0 >= height && y1 >= height)
+            || (x0 >= width && x1 >= width)
+        {
+            return;
+        }
+        let (delta_y, delta_x) = (-(y1 - y0).abs(), (x1 - x0).abs());
+        let (step_y, step_x) = ((y1 - y0).signum(), (x1 - x0).signum());
+        let (mut y, mut x) = (y0, x0);
+        let mut error = delta_x + delta_y;
+        loop {
+            if (0..height).contains(&y) && (0..width).contains(&x) {
+                self.set_pixel(y as u16, x as u16, color);
+            }
+            if y == y1 && x == x1 {
+                break;
+            }
+            let double_error = 2 * error;
+            if double_error >= delta_y {
+                error += delta_y;
+                x += step_x;
+            }
+            if double_error <= delta_x {
+                error += delta_x;
+                y += step_y;
+            }
+        }
+    }
+
+    /// Draws a line `width` pixels wide, filling the quad around the segment between the two
+    /// pixels, or a [`Draw::draw_line`] if `width` is at most 1.
+    fn draw_thick_line(&mut self, y0: i32, x0: i32, y1: i32, x1: i32, width: f32, color: Color) {
+        if width <= 1. || (y0 == y1 && x0 == x1) {
+            self.draw_line(y0, x0, y1, x1, color);
+            return;
+        }
+        let (height, target_width) = (i32::from(self.height()), i32::from(self.width()));
+        let (delta_y, delta_x) = ((y1 - y0) as f32, (x1 - x0) as f32);
+        let length = delta_y.hypot(delta_x);
+        let half_width = width / 2.;
+        let margin = half_width.ceil() as i32;
+        let rows =
+            cmp::max(cmp::min(y0, y1) - margin, 0)..cmp::min(cmp::max(y0, y1) + margin + 1, height);
+        let columns = cmp::max(cmp::min(x0, x1) - margin, 0)
+            ..cmp::min(cmp::max(x0, x1) + margin + 1, target_width);
+        for y in rows {
+            for x in columns.clone() {
+                let (offset_y, offset_x) = ((y - y0) as f32, (x - x0) as f32);
+                let along = (offset_y * delta_y + offset_x * delta_x) / length;
+                let across = (offset_x * delta_y - offset_y * delta_x) / length;
+                // Half open so that even widths cover exactly `width` pixels across.
+                if (-0.5..=length + 0.5).contains(&along)
+                    && across > -half_width
+                    && across <= half_width
+                {
+                    self.set_pixel(y as u16, x as u16, color);
+                }
+            }
+        }
+    }
+
+    /// Fills an ellipse centered on the pixel at `y` and `x`, with `radii` along the y and x axes,
+    /// rotated clockwise by `rotation` radians.
+    ///
+    /// Pixels are square, so a circle only needs equal radii.
+    fn fill_ellipse(&mut self, y: i32, x: i32, radii: (f32, f32), rotation: f32, color: Color) {
+        ellipse(self, (y, x), radii, rotation, color, false);
+    }
+
+    /// Draws the outline of an ellipse, see [`Draw::fill_ellipse`].
+    fn draw_ellipse(&mut self, y: i32, x: i32, radii: (f32, f32), rotation: f32, color: Color) {
+        ellipse(self, (y, x), radii, rotation, color, true);
+    }
+
+    /// Fills a circle centered on the pixel at `y` and `x`.
+    fn fill_circle(&mut self, y: i32, x: i32, radius: f32, color: Color) {
+        self.fill_ellipse(y, x, (radius, radius), 0., color);
+    }
+
+    /// Draws the outline of a circle centered on the pixel at `y` and `x`.
+    fn draw_circle(&mut self, y: i32, x: i32, radius: f32, color: Color) {
+        self.draw_ellipse(y, x, (radius, radius), 0., color);
+    }
+
+    /// Fills a triangle, each pixel whose center is inside of it being colored by `shading`.
+    ///
+    /// The interpolation of the vertices colors and texture coordinates is perspective correct,
+    /// using their view depth [`Vertex::w`].
+    fn fill_triangle(&mut self, vertices: [Vertex; 3], shading: Shading) {
+        raster::fill_triangle(self, vertices, shading);
+    }
+
+    /// Draws `canvas` with its top left corner at `y` and `x`.
+    fn blit(&mut self, canvas: &Canvas, y: u16, x: u16) {
+        self.blit_region(
+            canvas,
+            Rect::new(0, 0, canvas.height(), canvas.width()),
+            y,
+            x,
+        );
+    }
+
+    /// Draws the canvas of `handle` in `assets` with its top left corner at `y` and `x`, see
+    /// [`Draw::blit`].
+    fn blit_asset(&mut self, assets: &Assets, handle: Handle<Canvas>, y: u16, x: u16) {
+        self.blit(assets.canvas(handle), y, x);
+    }
+
+    /// Draws the `source` rectangle of `canvas`, e.g. a sprite of an atlas, with its top left
+    /// corner at `y` and `x`.
+    fn blit_region(&mut self, canvas: &Canvas, source: Rect, y: u16, x: u16) {
+        let end_y = cmp::min(source.end_y(), canvas.height());
+        let end_x = cmp::min(source.end_x(), canvas.width());
+        let height = cmp::min(
+            end_y.saturating_sub(source.y),
+            self.height().saturating_sub(y),
+        );
+        let width = cmp::min(
+            end_x.saturating_sub(source.x),
+            self.width().saturating_sub(x),
+        );
+        for offset_y in 0..height {
+            for offset_x in 0..width {
+                self.set_pixel(
+                    y + offset_y,
+                    x + offset_x,
+                    canvas.get_pixel(source.y + offset_y, source.x + offset_x),
+                );
+            }
+        }
+    }
+
+    /// Draws `world` scaled down into `destination` as a minimap, with the outline of the `view`
+    /// rectangle of `world`, e.g. the camera viewport, in `color`.
+    fn draw_minimap(&mut self, world: &Canvas, destination: Rect, view: Rect, color: Color) {
+        if world.height() == 0 || world.width() == 0 {
+            return;
+        }
+        let minimap = world.resized(destination.height, destination.width, Filter::Nearest);
+        self.blit(&minimap, destination.y, destination.x);
+        let scale = |position: u16, size: u16, world_size: u16, round: fn(f32) -> f32| {
+            let scaled = round(f32::from(position) * f32::from(size) / f32::from(world_size));
+            cmp::min(scaled as u16, size)
+        };
+        let (start_y, start_x) = (
+            scale(view.y, destination.height, world.height(), f32::floor),
+            scale(view.x, destination.width, world.width(), f32::floor),
+        );
+        let (end_y, end_x) = (
+            scale(view.end_y(), destination.height, world.height(), f32::ceil),
+            scale(view.end_x(), destination.width, world.width(), f32::ceil),
+        );
+        if start_y >= destination.height || start_x >= destination.width {
+            return;
+        }
+        self.draw_rect(
+            Rect::new(
+                destination.y.saturating_add(start_y),
+                destination.x.saturating_add(start_x),
+                cmp::max(end_y - start_y, 1),
+                cmp::max(end_x - start_x, 1),
+            ),
+            color,
+        );
+    }
+
+    /// Draws `text` with the built-in [`font`], its top left corner at `y` and `x`.
+    fn draw_text(&mut self, y: u16, x: u16, text: &str, color: Color) {
+        for (line_y, line) in (0..).step_by(ADVANCE_Y.into()).zip(text.lines()) {
+            for (char_x, char) in (0..).step_by(ADVANCE_X.into()).zip(line.chars()) {
+                for (glyph_y, row) in (0..GLYPH_HEIGHT).zip(font::glyph(char)) {
+                    for glyph_x in 0..GLYPH_WIDTH {
+                        if row & (0b100 >> glyph_x) == 0 {
+                            continue;
+                        }
+                        let pixel_y = y as u32 + line_y + glyph_y as u32;
+                        let pixel_x = x as u32 + char_x + glyph_x as u32;
+                        if pixel_y < self.height().into() && pixel_x < self.width().into() {
+                            self.set_pixel(pixel_y as u16, pixel_x as u16, color);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Draws `text` with `font`, its top left corner at `y` and `x`, the characters without a
+    /// glyph being left blank.
+    fn draw_text_with(&mut self, font: &BitmapFont, y: u16, x: u16, text: &str, color: Color) {
+        let (glyph_height, glyph_width) = (font.glyph_height(), font.glyph_width());
+        for (line_y, line) in (0..).step_by(glyph_height.into()).zip(text.lines()) {
+            for (char_x, char) in (0..).step_by(glyph_width.into()).zip(line.chars()) {
+                let Some(glyph) = font.glyph(char) else {
+                    continue;
+                };
+                for glyph_y in 0..glyph_height {
+                    for glyph_x in 0..glyph_width {
+                        if !font.is_set(glyph, glyph_y, glyph_x) {
+                            continue;
+                        }
+                        let pixel_y = y as u32 + line_y + glyph_y as u32;
+                        let pixel_x = x as u32 + char_x + glyph_x as u32;
+                        if pixel_y < self.height().into() && pixel_x < self.width().into() {
+                            self.set_pixel(pixel_y as u16, pixel_x as u16, color);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Draws `text` with the font of `handle` in `assets`, see [`Draw::draw_text_with`].
+    fn draw_asset_text(
+        &mut self,
+        assets: &Assets,
+        handle: Handle<BitmapFont>,
+        y: u16,
+        x: u16,
+        text: &str,
+        color: Color,
+    ) {
+        self.draw_text_with(assets.font(handle), y, x, text, color);
+    }
+}
+
+impl Draw for Window {
+    fn height(&self) -> u16 {
+        self.height()
+    }
+
+    fn width(&self) -> u16 {
+        self.width()
+    }
+
+    fn get_pixel(&self, y: u16, x: u16) -> Color {
+        self.get_pixel(y, x)
+    }
+
+    fn set_pixel(&mut self, y: u16, x: u16, color: Color) {
+        self.set_pixel(y, x, color);
+    }
+}
+
+fn fill_gradient<D: Draw + ?Sized>(
+    target: &mut D,
+    rect: Rect,
+    [from, to]: [Color; 2],
+    direction: FillDirection,
+    levels: Option<u8>,
+) {
+    let from = color::to_rgb(from).map(f32::from);
+    let to = color::to_rgb(to).map(f32::from);
+    let horizontal = matches!(
+        direction,
+        FillDirection::LeftToRight | FillDirection::RightToLeft
+    );
+    let length = if horizontal { rect.width } else { rect.height };
+    let (start, end) = match direction {
+        FillDirection::RightToLeft | FillDirection::BottomToTop => (to, from),
+        FillDirection::LeftToRight | FillDirection::TopToBottom => (from, to),
+    };
+    for y in rect.y..cmp::min(rect.end_y(), target.height()) {
+        for x in rect.x..cmp::min(rect.end_x(), target.width()) {
+            let position = if horizontal { x - rect.x } else { y - rect.y };
+            let t = position as f32 / length.saturating_sub(1).max(1) as f32;
+            let rgb = [0, 1, 2].map(|channel| {
+                let value = start[channel] + (end[channel] - start[channel]) * t;
+                match levels {
+                    Some(levels) if levels >= 2 => {
+                        let step = 255. / (levels - 1) as f32;
+                        let threshold =
+                            (BAYER_MATRIX[(y % 4) as usize][(x % 4) as usize] as f32 + 0.5) / 16.;
+                        ((value / step + threshold - 0.5).round() * step).clamp(0., 255.) as u8
+                    }
+                    _ => value.round() as u8,
+                }
+            });
+            target.set_pixel(y, x, color::from_rgb(rgb));
+        }
+    }
+}
+
+/// Fills or outlines an ellipse, an outline pixel having a 4-neighbor outside of the ellipse.
+fn ellipse<D: Draw + ?Sized>(
+    target: &mut D,
+    (center_y, center_x): (i32, i32),
+    (radius_y, radius_x): (f32, f32),
+    rotation: f32,
+    color: Color,
+    outline: bool,
+) {
+    if radius_y < 0. || radius_x < 0. {
+        return;
+    }
+    let (sin, cos) = rotation.sin_cos();
+    let inside = |y: i32, x: i32| {
+        let (dy, dx) = ((y - center_y) as f32, (x - center_x) as f32);
+        let along_x = dx * cos + dy * sin;
+        let along_y = dy * cos - dx * sin;
+        let (radius_y, radius_x) = (radius_y + 0.5, radius_x + 0.5);
+        (along_x / radius_x).powi(2) + (along_y / radius_y).powi(2) <= 1.
+    };
+    let half_height = ((radius_x * sin).powi(2) + (radius_y * cos).powi(2))
+        .sqrt()
+        .ceil() as i32;
+    let half_width = ((radius_x * cos).powi(2) + (radius_y * sin).powi(2))
+        .sqrt()
+        .ceil() as i32;
+    let rows = cmp::max(center_y - half_height, 0)
+        ..cmp::min(center_y + half_height + 1, target.height().into());
+    let columns = cmp::max(center_x - half_width, 0)
+        ..cmp::min(center_x + half_width + 1, target.width().into());
+    for y in rows {
+        for x in columns.clone() {
+            if !inside(y, x) {
+                continue;
+            }
+            if outline
+                && inside(y - 1, x)
+                && inside(y + 1, x)
+                && inside(y, x - 1)
+                && inside(y, x + 1)
+            {
+                continue;
+            }
+            target.set_pixel(y as u16, x as u16, color);
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use std::time::Duration;
+
+use crate::input::{Event, KeyEventKind, MouseEventKind};
+use crate::{Instant, Key, Modifiers, Window};
+
+/// Event of a window, see [`Window::events`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum WindowEvent {
+    /// A key was pressed, or repeated while held.
+    KeyPressed { code: Key, modifiers: Modifiers },
+    /// A key was released, only reported by some terminals.
+    KeyReleased { code: Key, modifiers: Modifiers },
+    /// The mouse moved over the pixel at `y` and `x`, see [`Window::mouse_position`].
+    MouseMoved { y: u16, x: u16 },
+    /// The terminal was resized to `columns` and `rows`.
+    Resized { columns: u16, rows: u16 },
+    /// The terminal gained focus, `true`, or lost it, `false`.
+    FocusChanged(bool),
+    /// Text was pasted into the terminal.
+    Paste(String),
+    /// A timer elapsed, see [`Window::add_timer`].
+    Timer(TimerId),
+}
+
+/// Identifier of a timer, see [`Window::add_timer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimerId {
+    index: usize,
+    generation: u32,
+}
+
+/// Timer elapsing every `interval`.
+#[derive(Debug, Clone)]
+pub(crate) struct Timer {
+    interval: Duration,
+    next: Instant,
+    elapsed: bool,
+    removed: bool,
+    /// Number of timers previously added in the same slot, for their identifiers not to match
+    /// the new one.
+    generation: u32,
+}
+
+impl Window {
+    /// Adds a timer elapsing every `interval`, reported by [`Window::get_timer`] and
+    /// [`Window::events`] after the next call to [`Window::poll_events`].
+    ///
+    /// A timer elapses at most once per call to [`Window::poll_events`].
+    pub fn add_timer(&mut self, interval: Duration) -> TimerId {
+        let mut timer = Timer {
+            interval,
+            next: Instant::now() + interval,
+            elapsed: false,
+            removed: false,
+            generation: 0,
+        };
+        // The slots of the removed timers are reused.
+        let index = match self.timers.iter().position(|timer| timer.removed) {
+            Some(index) => {
+                timer.generation = self.timers[index].generation.wrapping_add(1);
+                self.timers[index] = timer;
+                index
+            }
+            None => {
+                self.timers.push(timer);
+                self.timers.len() - 1
+            }
+        };
+        TimerId {
+            index,
+            generation: self.timers[index].generation,
+        }
+    }
+
+    /// Removes a timer, which never elapses again.
+    pub fn remove_timer(&mut self, id: TimerId) {
+        if let Some(timer) = self.timer_mut(id) {
+            timer.elapsed = false;
+            timer.removed = true;
+        }
+    }
+
+    /// Returns `true` if the timer elapsed during the last call to [`Window::poll_events`].
+    pub fn get_timer(&self, id: TimerId) -> bool {
+        self.timers
+            .get(id.index)
+            .is_some_and(|timer| timer.generation == id.generation && timer.elapsed)
+    }
+
+    /// Gets a timer, `None` if it was removed.
+    fn timer_mut(&mut self, id: TimerId) -> Option<&mut Timer> {
+        self.timers
+            .get_mut(id.index)
+            .filter(|timer| timer.generation == id.generation && !timer.removed)
+    }
+
+    /// Updates the elapsed timers.
+    pub(crate) fn update_timers(&mut self) {
+        let now = Instant::now();
+        for timer in self.timers.iter_mut().filter(|timer| !timer.removed) {
+            timer.elapsed = now >= timer.next;
+            if timer.elapsed {
+                timer.next += timer.interval;
+                if timer.next <= now {
+                    timer.next = now + timer.interval;
+                }
+            }
+        }
+    }
+
+    /// Gets the events read during the last call to [`Window::poll_events`], then the elapsed
+    /// timers.
+    ///
+    /// Mouse events other than movements, and movements outside of the window, are not reported.
+    pub fn events(&self) -> Vec<WindowEvent> {
+        let mut events: Vec<WindowEvent> = self
+            .last_events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Key(key_event) => Some(if key_event.kind == KeyEventKind::Release {
+                    WindowEvent::KeyReleased {
+                        code: key_event.code,
+                        modifiers: key_event.modifiers,
+                    }
+                } else {
+                    WindowEvent::KeyPressed {
+                        code: key_event.code,
+                        modifiers: key_event.modifiers,
+                    }
+                }),
+                Event::Mouse(mouse_event) => match mouse_event.kind {
+                    MouseEventKind::Moved | MouseEventKind::Drag(_) => {
+                        let (y, x) = self.cell_to_pixel(mouse_event.column, mouse_event.row)?;
+                        Some(WindowEvent::MouseMoved { y, x })
+                    }
+                    _ => None,
+                },
+                Event::Resize(columns, rows) => Some(WindowEvent::Resized {
+                    columns: *columns,
+                    rows: *rows,
+                }),
+                Event::FocusGained => Some(WindowEvent::FocusChanged(true)),
+                Event::FocusLost => Some(WindowEvent::FocusChanged(false)),
+                Event::Paste(text) => Some(WindowEvent::Paste(text.clone())),
+            })
+            .collect();
+        events.extend(
+            self.timers
+                .iter()
+                .enumerate()
+                .filter(|(_, timer)| timer.elapsed)
+                .map(|(index, timer)| {
+                    WindowEvent::Timer(TimerId {
+                        index,
+                        generation: timer.generation,
+                    })
+                }),
+        );
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::backend::TestBackend;
+    use crate::input::{Event, KeyEvent};
+    use crate::{Key, Modifiers, Window, WindowEvent};
+
+    #[test]
+    fn key_events_use_the_winterm_types() {
+        let backend = TestBackend::default();
+        let mut window = Window::with_backend(2, 2, backend.clone()).unwrap();
+        backend.push(Event::Key(KeyEvent::new(
+            Key::Char('a'),
+            Modifiers::CONTROL,
+        )));
+        window.poll_events().unwrap();
+        assert_eq!(
+            window.events(),
+            [WindowEvent::KeyPressed {
+                code: Key::Char('a'),
+                modifiers: Modifiers::CONTROL,
+            }]
+        );
+        assert!(window.get_key(Key::Char('a')));
+        assert!(window.get_modifiers(Modifiers::CONTROL));
+    }
+
+    #[test]
+    fn removed_timer_slots_are_reused() {
+        let mut window = Window::with_backend(2, 2, TestBackend::default()).unwrap();
+        let kept = window.add_timer(Duration::from_secs(60));
+        for _ in 0..100 {
+            let timer = window.add_timer(Duration::ZERO);
+            window.remove_timer(timer);
+        }
+        assert_eq!(window.timers.len(), 2);
+        assert!(!window.get_timer(kept));
+    }
+
+    #[test]
+    fn stale_identifiers_do_not_match_the_new_timer() {
+        let mut window = Window::with_backend(2, 2, TestBackend::default()).unwrap();
+        let old = window.add_timer(Duration::ZERO);
+        window.remove_timer(old);
+        let new = window.add_timer(Duration::ZERO);
+        assert_ne!(old, new);
+        window.remove_timer(old);
+        window.poll_events().unwrap();
+        assert!(window.get_timer(new));
+        assert!(!window.get_timer(old));
+        assert_eq!(window.events(), [WindowEvent::Timer(new)]);
+    }
+}
@@ -0,0 +1,203 @@
+//! Tiny 3x5 pixel font used by [`Draw::draw_text`](crate::Draw::draw_text), and bitmap fonts
+//! read from sheets of glyphs.
+//!
+//! Letters are drawn in uppercase, characters without a glyph are drawn as a filled block.
+
+use crate::{color, Canvas, Rect};
+
+/// Width of a glyph in pixels.
+pub const GLYPH_WIDTH: u16 = 3;
+
+/// Height of a glyph in pixels.
+pub const GLYPH_HEIGHT: u16 = 5;
+
+/// Horizontal distance between two glyphs.
+pub(crate) const ADVANCE_X: u16 = GLYPH_WIDTH + 1;
+
+/// Vertical distance between two lines.
+pub(crate) const ADVANCE_Y: u16 = GLYPH_HEIGHT + 1;
+
+/// Gets the height and width in pixels of `text` once drawn.
+pub fn text_size(text: &str) -> (u16, u16) {
+    let lines = text.lines().count() as u16;
+    let columns = text
+        .lines()
+        .map(|line| line.chars().count() as u16)
+        .max()
+        .unwrap_or(0);
+    (
+        (lines * ADVANCE_Y).saturating_sub(1),
+        (columns * ADVANCE_X).saturating_sub(1),
+    )
+}
+
+/// Gets the rows of a glyph, from top to bottom, the most significant of the 3 bits being the
+/// leftmost pixel.
+pub(crate) fn glyph(char: char) -> [u8; 5] {
+    match char.to_ascii_uppercase() {
+        ' ' => [0b000, 0b000, 0b000, 0b000, 0b000],
+        'A' => [0b010, 0b101, 0b111, 0b101, 0b101],
+        'B' => [0b110, 0b101, 0b110, 0b101, 0b110],
+        'C' => [0b011, 0b100, 0b100, 0b100, 0b011],
+        'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+        'E' => [0b111, 0b100, 0b110, 0b100, 0b111],
+        'F' => [0b111, 0b100, 0b110, 0b100, 0b100],
+        'G' => [0b011, 0b100, 0b101, 0b101, 0b011],
+        'H' => [0b101, 0b101, 0b111, 0b101, 0b101],
+        'I' => [0b111, 0b010, 0b010, 0b010, 0b111],
+        'J' => [0b001, 0b001, 0b001, 0b101, 0b010],
+        'K' => [0b101, 0b101, 0b110, 0b101, 0b101],
+        'L' => [0b100, 0b100, 0b100, 0b100, 0b111],
+        'M' => [0b101, 0b111, 0b111, 0b101, 0b101],
+        'N' => [0b110, 0b101, 0b101, 0b101, 0b101],
+        'O' => [0b010, 0b101, 0b101, 0b101, 0b010],
+        'P' => [0b110, 0b101, 0b110, 0b100, 0b100],
+        'Q' => [0b010, 0b101, 0b101, 0b110, 0b011],
+        'R' => [0b110, 0b101, 0b110, 0b101, 0b101],
+        'S' => [0b011, 0b100, 0b010, 0b001, 0b110],
+        'T' => [0b111, 0b010, 0b010, 0b010, 0b010],
+        'U' => [0b101, 0b101, 0b101, 0b101, 0b111],
+        'V' => [0b101, 0b101, 0b101, 0b101, 0b010],
+        'W' => [0b101, 0b101, 0b111, 0b111, 0b101],
+        'X' => [0b101, 0b101, 0b010, 0b101, 0b101],
+        'Y' => [0b101, 0b101, 0b010, 0b010, 0b010],
+        'Z' => [0b111, 0b001, 0b010, 0b100, 0b111],
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b110, 0b001, 0b010, 0b100, 0b111],
+        '3' => [0b110, 0b001, 0b010, 0b001, 0b110],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b110, 0b001, 0b110],
+        '6' => [0b011, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b010, 0b010, 0b010],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b110],
+        '!' => [0b010, 0b010, 0b010, 0b000, 0b010],
+        '"' => [0b101, 0b101, 0b000, 0b000, 0b000],
+        '#' => [0b101, 0b111, 0b101, 0b111, 0b101],
+        '$' => [0b011, 0b110, 0b010, 0b011, 0b110],
+        '%' => [0b101, 0b001, 0b010, 0b100, 0b101],
+        '&' => [0b010, 0b101, 0b010, 0b101, 0b011],
+        '\'' => [0b010, 0b010, 0b000, 0b000, 0b000],
+        '(' => [0b001, 0b010, 0b010, 0b010, 0b001],
+        ')' => [0b100, 0b010, 0b010, 0b010, 0b100],
+        '*' => [0b000, 0b101, 0b010, 0b101, 0b000],
+        '+' => [0b000, 0b010, 0b111, 0b010, 0b000],
+        ',' => [0b000, 0b000, 0b000, 0b010, 0b100],
+        '-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+        '.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+        '/' => [0b001, 0b001, 0b010, 0b100, 0b100],
+        ':' => [0b000, 0b010, 0b000, 0b010, 0b000],
+        ';' => [0b000, 0b010, 0b000, 0b010, 0b100],
+        '<' => [0b001, 0b010, 0b100, 0b010, 0b001],
+        '=' => [0b000, 0b111, 0b000, 0b111, 0b000],
+        '>' => [0b100, 0b010, 0b001, 0b010, 0b100],
+        '?' => [0b110, 0b001, 0b010, 0b000, 0b010],
+        '@' => [0b010, 0b101, 0b111, 0b100, 0b011],
+        '[' => [0b011, 0b010, 0b010, 0b010, 0b011],
+        ']' => [0b110, 0b010, 0b010, 0b010, 0b110],
+        '_' => [0b000, 0b000, 0b000, 0b000, 0b111],
+        _ => [0b111, 0b111, 0b111, 0b111, 0b111],
+    }
+}
+
+/// First character of the sheet of a [`BitmapFont`].
+const FIRST_SHEET_CHAR: char = ' ';
+
+/// Last character of the sheet of a [`BitmapFont`].
+const LAST_SHEET_CHAR: char = '~';
+
+/// Font read from a sheet of glyphs, drawn by
+/// [`Draw::draw_text_with`](crate::Draw::draw_text_with).
+///
+/// The sheet holds the glyphs of the printable ASCII characters, from `' '` to `'~'`, in rows of
+/// glyphs of the same size, as many per row as fit in the width of the sheet. The pixels of a
+/// glyph are the ones that are not black, and the glyphs are drawn next to each other, their
+/// spacing being part of the sheet.
+///
+/// ```
+/// use winterm::Color;
+/// use winterm::font::BitmapFont;
+/// use winterm::{Canvas, Draw};
+///
+/// // 2x2 glyphs, 48 per row, '!' being the second one.
+/// let mut sheet = Canvas::new(4, 96, Color::Black);
+/// sheet.set_pixel(0, 2, Color::White);
+/// let font = BitmapFont::new(sheet, 2, 2);
+///
+/// let mut target = Canvas::new(2, 4, Color::Black);
+/// target.draw_text_with(&font, 0, 0, " !", Color::Red);
+/// assert_eq!(target.get_pixel(0, 2), Color::Red);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitmapFont {
+    sheet: Canvas,
+    glyph_height: u16,
+    glyph_width: u16,
+}
+
+impl BitmapFont {
+    /// Creates a font from a sheet of `glyph_height` by `glyph_width` glyphs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the glyphs are empty or wider than the sheet.
+    pub fn new(sheet: Canvas, glyph_height: u16, glyph_width: u16) -> Self {
+        assert!(
+            glyph_height > 0 && glyph_width > 0 && glyph_width <= sheet.width(),
+            "the glyphs must be non-empty and fit in the width of the sheet"
+        );
+        BitmapFont {
+            sheet,
+            glyph_height,
+            glyph_width,
+        }
+    }
+
+    /// Gets the sheet of glyphs.
+    pub fn sheet(&self) -> &Canvas {
+        &self.sheet
+    }
+
+    /// Gets the height of a glyph, which is also the distance between two lines.
+    pub fn glyph_height(&self) -> u16 {
+        self.glyph_height
+    }
+
+    /// Gets the width of a glyph, which is also the distance between two glyphs.
+    pub fn glyph_width(&self) -> u16 {
+        self.glyph_width
+    }
+
+    /// Gets the rectangle of the sheet holding the glyph of `char`, `None` if the sheet has none.
+    pub fn glyph(&self, char: char) -> Option<Rect> {
+        if !(FIRST_SHEET_CHAR..=LAST_SHEET_CHAR).contains(&char) {
+            return None;
+        }
+        let index = u32::from(char) - u32::from(FIRST_SHEET_CHAR);
+        let columns = u32::from(self.sheet.width() / self.glyph_width);
+        let y = index / columns * u32::from(self.glyph_height);
+        let x = index % columns * u32::from(self.glyph_width);
+        (y + u32::from(self.glyph_height) <= u32::from(self.sheet.height()))
+            .then(|| Rect::new(y as u16, x as u16, self.glyph_height, self.glyph_width))
+    }
+
+    /// Checks if the pixel at `y` and `x` of `glyph`, see [`BitmapFont::glyph`], is set.
+    pub(crate) fn is_set(&self, glyph: Rect, y: u16, x: u16) -> bool {
+        color::to_rgb(self.sheet.get_pixel(glyph.y + y, glyph.x + x)) != [0, 0, 0]
+    }
+
+    /// Gets the height and width in pixels of `text` once drawn.
+    pub fn text_size(&self, text: &str) -> (u16, u16) {
+        let lines = text.lines().count() as u16;
+        let columns = text
+            .lines()
+            .map(|line| line.chars().count() as u16)
+            .max()
+            .unwrap_or(0);
+        (
+            lines.saturating_mul(self.glyph_height),
+            columns.saturating_mul(self.glyph_width),
+        )
+    }
+}
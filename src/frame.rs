@@ -0,0 +1,138 @@
+use std::io::Result;
+
+use crate::{color, Color, Window};
+
+impl Window {
+    /// Gets the pixels as RGBA bytes, row by row, mirroring the `pixels` and `minifb` crates.
+    ///
+    /// The bytes are read from the pixels on each call, so that they include the pixels drawn
+    /// since. Changes are applied to the window by [`Window::present`], the alpha channel is
+    /// ignored.
+    pub fn frame_mut(&mut self) -> &mut [u8] {
+        self.frame.clear();
+        for &pixel in self.pixels.pixels() {
+            self.frame.extend(color::to_rgb(pixel));
+            self.frame.push(u8::MAX);
+        }
+        self.frame_snapshot.clone_from(&self.frame);
+        &mut self.frame
+    }
+
+    /// Applies the changes made to [`Window::frame_mut`] since its last call, if any, and redraws
+    /// the window to the terminal.
+    ///
+    /// Only the pixels whose bytes were changed are set, the other ones keeping their color, e.g.
+    /// [`Color::Red`] instead of its RGB value.
+    pub fn present(&mut self) -> Result<()> {
+        let mut frame = std::mem::take(&mut self.frame);
+        let snapshot = std::mem::take(&mut self.frame_snapshot);
+        let chunks = frame.chunks_exact(4).zip(snapshot.chunks_exact(4));
+        for (i, (rgba, snapshot)) in chunks.enumerate() {
+            if rgba[..3] == snapshot[..3] {
+                continue;
+            }
+            let y = (i / self.width() as usize) as u16;
+            let x = (i % self.width() as usize) as u16;
+            self.set_pixel(y, x, color::from_rgb([rgba[0], rgba[1], rgba[2]]));
+        }
+        // The frame is emptied for a later present not to write it back over newer pixels.
+        frame.clear();
+        self.frame = frame;
+        self.redraw()
+    }
+
+    /// Sets the pixels from a packed 1-bit frame of `width` pixels per row, as used by CHIP-8
+    /// emulators, each bit being drawn as a square of `scale` pixels.
+    ///
+    /// The bits are read row by row, the most significant bit of each byte first. The pixels
+    /// outside of the scaled frame are left untouched.
+    pub fn set_frame_bits(
+        &mut self,
+        bits: &[u8],
+        width: u16,
+        scale: u16,
+        on_color: Color,
+        off_color: Color,
+    ) {
+        if width == 0 || scale == 0 {
+            return;
+        }
+        let height = bits.len() * 8 / width as usize;
+        let end_y = (height * scale as usize).min(self.height().into()) as u16;
+        let end_x = (width as usize * scale as usize).min(self.width().into()) as u16;
+        for y in 0..end_y {
+            for x in 0..end_x {
+                let index = (y / scale) as usize * width as usize + (x / scale) as usize;
+                let color = if bits[index / 8] & (0x80 >> (index % 8)) != 0 {
+                    on_color
+                } else {
+                    off_color
+                };
+                if self.get_pixel(y, x) != color {
+                    self.set_pixel(y, x, color);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Color;
+
+    use crate::backend::TestBackend;
+    use crate::Window;
+
+    #[test]
+    fn frame_mut_includes_the_pixels_set_since_the_last_call() {
+        let mut window = Window::with_backend(2, 2, TestBackend::default()).unwrap();
+        window.frame_mut();
+        window.set_pixel(
+            0,
+            1,
+            Color::Rgb {
+                r: 10,
+                g: 20,
+                b: 30,
+            },
+        );
+        let frame = window.frame_mut();
+        assert_eq!(frame[4..8], [10, 20, 30, 255]);
+        frame[0..3].copy_from_slice(&[1, 2, 3]);
+        window.present().unwrap();
+        assert_eq!(window.get_pixel(0, 0), Color::Rgb { r: 1, g: 2, b: 3 });
+        assert_eq!(
+            window.get_pixel(0, 1),
+            Color::Rgb {
+                r: 10,
+                g: 20,
+                b: 30
+            }
+        );
+    }
+
+    #[test]
+    fn present_does_not_revert_the_pixels_set_after_it() {
+        let mut window = Window::with_backend(2, 2, TestBackend::default()).unwrap();
+        window.frame_mut()[0..3].copy_from_slice(&[1, 2, 3]);
+        window.present().unwrap();
+        window.set_pixel(0, 0, Color::Red);
+        window.set_pixel(1, 1, Color::Blue);
+        window.present().unwrap();
+        assert_eq!(window.get_pixel(0, 0), Color::Red);
+        assert_eq!(window.get_pixel(1, 1), Color::Blue);
+    }
+
+    #[test]
+    fn present_keeps_the_untouched_named_colors() {
+        let mut window = Window::with_backend(2, 2, TestBackend::default()).unwrap();
+        window.set_pixel(0, 0, Color::Red);
+        window.set_pixel(1, 0, Color::Reset);
+        window.redraw().unwrap();
+        window.frame_mut()[4..7].copy_from_slice(&[1, 2, 3]);
+        window.present().unwrap();
+        assert_eq!(window.get_pixel(0, 0), Color::Red);
+        assert_eq!(window.get_pixel(1, 0), Color::Reset);
+        assert_eq!(window.get_pixel(0, 1), Color::Rgb { r: 1, g: 2, b: 3 });
+    }
+}
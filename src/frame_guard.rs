@@ -0,0 +1,65 @@
+use std::io::Result;
+use std::ops::{Deref, DerefMut};
+
+use crate::Window;
+
+/// Frame being drawn, started with [`Window::begin_frame`] and presented when dropped.
+///
+/// The frame dereferences to the window, so that it is drawn like it.
+#[derive(Debug)]
+pub struct Frame<'a> {
+    window: Option<&'a mut Window>,
+}
+
+impl Frame<'_> {
+    /// Presents the frame with [`Window::redraw`], which dropping it also does but ignoring the
+    /// errors.
+    pub fn end(mut self) -> Result<()> {
+        match self.window.take() {
+            Some(window) => window.redraw(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Deref for Frame<'_> {
+    type Target = Window;
+
+    fn deref(&self) -> &Window {
+        self.window.as_deref().expect("frame already ended")
+    }
+}
+
+impl DerefMut for Frame<'_> {
+    fn deref_mut(&mut self) -> &mut Window {
+        self.window.as_deref_mut().expect("frame already ended")
+    }
+}
+
+impl Drop for Frame<'_> {
+    fn drop(&mut self) {
+        if let Some(window) = self.window.take() {
+            let _ = window.redraw();
+        }
+    }
+}
+
+impl Window {
+    /// Starts a frame, presented when the returned guard is dropped, so that a redraw cannot be
+    /// forgotten.
+    ///
+    /// ```
+    /// use winterm::Color;
+    /// use winterm::{Draw, Rect, Window};
+    ///
+    /// # let mut window = Window::new(9, 16)?;
+    /// let mut frame = window.begin_frame();
+    /// frame.fill_rect(Rect::new(0, 0, 4, 4), Color::Red);
+    /// frame.set_pixel(5, 5, Color::Blue);
+    /// frame.end()?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn begin_frame(&mut self) -> Frame<'_> {
+        Frame { window: Some(self) }
+    }
+}
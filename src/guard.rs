@@ -0,0 +1,66 @@
+use std::io::Result;
+
+use crate::{Vector2, Window};
+
+/// Terminal released by [`Window::release_terminal`], re-acquired when the guard is dropped.
+///
+/// While released, the terminal is restored as before the window creation, e.g. out of the
+/// alternate screen and of the raw mode, so that a subprocess can use it.
+#[derive(Debug)]
+pub struct TerminalGuard<'a> {
+    window: Option<&'a mut Window>,
+}
+
+impl TerminalGuard<'_> {
+    /// Re-acquires the terminal and redraws the window entirely, which dropping the guard also
+    /// does but ignoring the errors.
+    pub fn reacquire(mut self) -> Result<()> {
+        match self.window.take() {
+            Some(window) => window.acquire_terminal(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for TerminalGuard<'_> {
+    fn drop(&mut self) {
+        if let Some(window) = self.window.take() {
+            let _ = window.acquire_terminal();
+        }
+    }
+}
+
+impl Window {
+    /// Restores the terminal, as when the window is dropped, until the returned guard re-acquires
+    /// it, e.g. to spawn an editor or a shell.
+    ///
+    /// To restore the terminal from a panic hook, which cannot borrow the window, call
+    /// [`Backend::leave`](crate::Backend::leave) on a [`CrosstermBackend`](crate::CrosstermBackend).
+    ///
+    /// ```no_run
+    /// use std::process::Command;
+    /// # use winterm::Window;
+    ///
+    /// # let mut window = Window::new(9, 16)?;
+    /// let guard = window.release_terminal()?;
+    /// Command::new("vi").status()?;
+    /// guard.reacquire()?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn release_terminal(&mut self) -> Result<TerminalGuard<'_>> {
+        self.flush_output()?;
+        self.terminal.backend.leave()?;
+        Ok(TerminalGuard { window: Some(self) })
+    }
+
+    fn acquire_terminal(&mut self) -> Result<()> {
+        self.terminal.backend.enter()?;
+        if self.mouse_capture {
+            self.terminal.backend.set_mouse_capture(true)?;
+        }
+        let (columns, rows) = self.terminal.backend.size()?;
+        self.terminal_size = Vector2::new(columns, rows);
+        self.calculate_origin();
+        self.redraw_all()
+    }
+}
@@ -0,0 +1,68 @@
+use std::collections::VecDeque;
+
+use crate::{color, Canvas, Window};
+
+/// Last presented frames, newest first, see [`Window::set_frame_history`].
+#[derive(Debug, Default)]
+pub(crate) struct FrameHistory {
+    pub(crate) frames: VecDeque<Canvas>,
+    capacity: usize,
+}
+
+impl Window {
+    /// Keeps the last `frames` frames presented by [`Window::redraw`], 0 by default, dropping
+    /// the recorded ones if less are kept.
+    pub fn set_frame_history(&mut self, frames: usize) {
+        self.history.capacity = frames;
+        self.history.frames.truncate(frames);
+    }
+
+    /// Gets the recorded frames, newest first, see [`Window::set_frame_history`].
+    pub fn frame_history(&self) -> impl ExactSizeIterator<Item = &Canvas> {
+        self.history.frames.iter()
+    }
+
+    /// Records the front buffer in the history, reusing the oldest frame when it is full.
+    pub(crate) fn record_frame(&mut self) {
+        if self.history.capacity == 0 {
+            return;
+        }
+        let frame = if self.history.frames.len() == self.history.capacity {
+            let mut frame = self.history.frames.pop_back().expect("the history is full");
+            frame.clone_from(&self.front);
+            frame
+        } else {
+            self.front.clone()
+        };
+        self.history.frames.push_front(frame);
+    }
+
+    /// Blends the recorded frames into the pixels, for motion blur and ghosting effects,
+    /// usually right before [`Window::redraw`].
+    ///
+    /// Each frame weighs `alpha` times the next one, the pixels weighing 1, so an `alpha` near 1
+    /// leaves long trails, and 0 none. The recorded frames being the presented ones, the trails
+    /// also blend the previous trails. Like [`Window::pixels_mut`], it ignores the clip rectangle.
+    pub fn trail(&mut self, alpha: f32) {
+        let alpha = alpha.clamp(0., 1.);
+        if self.history.frames.is_empty() || alpha == 0. {
+            return;
+        }
+        let weights: Vec<f32> = (1..=self.history.frames.len())
+            .map(|age| alpha.powi(age as i32))
+            .collect();
+        let total_weight = 1. + weights.iter().sum::<f32>();
+        let history = std::mem::take(&mut self.history.frames);
+        for (i, pixel) in self.pixels_mut().iter_mut().enumerate() {
+            let mut sum = color::to_rgb(*pixel).map(f32::from);
+            for (frame, weight) in history.iter().zip(&weights) {
+                let rgb = color::to_rgb(frame.pixels()[i]);
+                for channel in 0..3 {
+                    sum[channel] += f32::from(rgb[channel]) * weight;
+                }
+            }
+            *pixel = color::from_rgb(sum.map(|channel| (channel / total_weight).round() as u8));
+        }
+        self.history.frames = history;
+    }
+}
@@ -0,0 +1,176 @@
+use std::io::Result;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::ansi::{queue, MoveTo, Print};
+use crate::Window;
+
+/// Position of a label around the window, see [`Window::set_label`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Anchor {
+    /// Above the border, aligned on its left end.
+    TopLeft,
+    /// Above the border, centered.
+    Top,
+    /// Above the border, aligned on its right end.
+    TopRight,
+    /// Left of the border, on the middle row.
+    Left,
+    /// Right of the border, on the middle row.
+    Right,
+    /// Below the border and the status lines, aligned on the left end of the border.
+    BottomLeft,
+    /// Below the border and the status lines, centered.
+    Bottom,
+    /// Below the border and the status lines, aligned on the right end of the border.
+    BottomRight,
+}
+
+impl Window {
+    /// Sets the text of the label at `anchor`, drawn in the terminal cells around the window and
+    /// moved with it when the terminal is resized.
+    ///
+    /// The parts of the label outside of the terminal are not drawn.
+    ///
+    /// ```
+    /// use winterm::{Anchor, Window};
+    ///
+    /// # let mut window = Window::new(9, 16)?;
+    /// window.set_label(Anchor::Top, "LEVEL 1");
+    /// window.set_label(Anchor::BottomRight, "SCORE 120");
+    /// window.redraw()?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn set_label(&mut self, anchor: Anchor, text: &str) {
+        let text = text.lines().next().unwrap_or_default();
+        match self.labels.iter().position(|(label, _)| *label == anchor) {
+            Some(i) if self.labels[i].1 == text => {}
+            Some(i) => {
+                self.mark_label_stale(i);
+                self.labels[i].1 = text.to_owned();
+            }
+            None => self.labels.push((anchor, text.to_owned())),
+        }
+    }
+
+    /// Removes the label at `anchor`, see [`Window::set_label`].
+    pub fn remove_label(&mut self, anchor: Anchor) {
+        if let Some(i) = self.labels.iter().position(|(label, _)| *label == anchor) {
+            self.mark_label_stale(i);
+            self.labels.remove(i);
+        }
+    }
+
+    /// Records the cells covered by the label at `i`, for the next redraw to clear them.
+    fn mark_label_stale(&mut self, i: usize) {
+        if self.dump_frames {
+            return;
+        }
+        let (anchor, text) = &self.labels[i];
+        let length = text.chars().count();
+        let (row, column) = self.label_position(*anchor, length as i32);
+        if !self.stale_labels.contains(&(row, column, length)) {
+            self.stale_labels.push((row, column, length));
+        }
+    }
+
+    /// Gets the row and column of a label of `length` characters at `anchor`.
+    fn label_position(&self, anchor: Anchor, length: i32) -> (i32, i32) {
+        let (left, right) = (
+            i32::from(self.origin.x) - 1,
+            i32::from(self.origin.x) + i32::from(self.scaled_width()),
+        );
+        let top = i32::from(self.origin.y) - 2;
+        let middle = i32::from(self.origin.y) + i32::from(self.scaled_height().div_ceil(2)) / 2;
+        // The bottom border takes its own row under an even number of pixels rows.
+        let bottom = i32::from(self.origin.y)
+            + i32::from(self.scaled_height().div_ceil(2))
+            + i32::from(self.scaled_height().is_multiple_of(2))
+            + self.status_lines.len() as i32;
+        match anchor {
+            Anchor::TopLeft => (top, left),
+            Anchor::Top => (top, (left + right + 1 - length) / 2),
+            Anchor::TopRight => (top, right + 1 - length),
+            Anchor::Left => (middle, left - 1 - length),
+            Anchor::Right => (middle, right + 2),
+            Anchor::BottomLeft => (bottom, left),
+            Anchor::Bottom => (bottom, (left + right + 1 - length) / 2),
+            Anchor::BottomRight => (bottom, right + 1 - length),
+        }
+    }
+
+    pub(crate) fn queue_labels(&mut self) -> Result<()> {
+        // The cells of the previous texts are overwritten with spaces.
+        for (row, column, length) in std::mem::take(&mut self.stale_labels) {
+            self.queue_clipped_text(row, column, &" ".repeat(length))?;
+        }
+        let labels = std::mem::take(&mut self.labels);
+        let result = labels.iter().try_for_each(|(anchor, text)| {
+            let (row, column) = self.label_position(*anchor, text.chars().count() as i32);
+            self.queue_clipped_text(row, column, text)
+        });
+        self.labels = labels;
+        result
+    }
+
+    /// Queues `text` at `row` and `column`, without the characters outside of the terminal.
+    fn queue_clipped_text(&mut self, row: i32, column: i32, text: &str) -> Result<()> {
+        if row < 0 || row >= i32::from(self.terminal_size.y) {
+            return Ok(());
+        }
+        let skipped = column.min(0).unsigned_abs() as usize;
+        let column = column.max(0);
+        let width = (i32::from(self.terminal_size.x) - column).max(0) as usize;
+        let visible: String = text.chars().skip(skipped).take(width).collect();
+        if !visible.is_empty() {
+            queue!(
+                self.terminal,
+                MoveTo(column as u16, row as u16),
+                Print(visible)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Anchor;
+    use crate::backend::TestBackend;
+    use crate::Window;
+
+    fn contains(output: &[u8], bytes: &[u8]) -> bool {
+        output.windows(bytes.len()).any(|window| window == bytes)
+    }
+
+    #[test]
+    fn changing_a_label_clears_only_its_previous_text() {
+        let backend = TestBackend::default();
+        let mut window = Window::with_backend(4, 4, backend.clone()).unwrap();
+        window.set_label(Anchor::TopLeft, "SCORE 100");
+        window.redraw().unwrap();
+        backend.take_output();
+
+        window.set_label(Anchor::TopLeft, "SCORE 5");
+        window.redraw().unwrap();
+        let output = backend.take_output();
+        assert!(!contains(&output, b"\x1b[2J"));
+        let spaces = output
+            .windows(9)
+            .position(|window| window == b"         ")
+            .unwrap();
+        let text = output
+            .windows(7)
+            .position(|window| window == b"SCORE 5")
+            .unwrap();
+        assert!(spaces < text);
+
+        window.remove_label(Anchor::TopLeft);
+        window.redraw().unwrap();
+        let output = backend.take_output();
+        assert!(!contains(&output, b"\x1b[2J"));
+        assert!(contains(&output, b"       ") && !contains(&output, b"SCORE"));
+    }
+}
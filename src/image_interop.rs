@@ -0,0 +1,168 @@
+use std::io;
+use std::io::Result;
+use std::path::Path;
+
+#[cfg(feature = "crossterm")]
+use crossterm::terminal;
+use image::{GenericImageView, Rgb, Rgba, RgbaImage};
+
+use crate::{color, Canvas, Color, Draw, Filter, Window};
+
+macro_rules! impl_image_interop {
+    ($type:ty) => {
+        impl GenericImageView for $type {
+            type Pixel = Rgb<u8>;
+
+            fn dimensions(&self) -> (u32, u32) {
+                (self.width().into(), self.height().into())
+            }
+
+            fn get_pixel(&self, x: u32, y: u32) -> Self::Pixel {
+                Rgb(color::to_rgb(<$type>::get_pixel(self, y as u16, x as u16)))
+            }
+        }
+
+        impl $type {
+            /// Converts the pixels to an opaque RGBA image.
+            pub fn to_rgba_image(&self) -> RgbaImage {
+                RgbaImage::from_fn(self.width().into(), self.height().into(), |x, y| {
+                    let [r, g, b] = color::to_rgb(<$type>::get_pixel(self, y as u16, x as u16));
+                    Rgba([r, g, b, u8::MAX])
+                })
+            }
+
+            /// Draws `image` with its top left corner at `y` and `x`, its pixels being blended
+            /// over the current ones by their alpha.
+            pub fn copy_from_rgba(&mut self, image: &RgbaImage, y: u16, x: u16) {
+                let height = u32::from(self.height().saturating_sub(y)).min(image.height());
+                let width = u32::from(self.width().saturating_sub(x)).min(image.width());
+                for image_y in 0..height {
+                    for image_x in 0..width {
+                        let (y, x) = (y + image_y as u16, x + image_x as u16);
+                        let color = blend(
+                            *image.get_pixel(image_x, image_y),
+                            <$type>::get_pixel(self, y, x),
+                        );
+                        <$type>::set_pixel(self, y, x, color);
+                    }
+                }
+            }
+        }
+    };
+}
+
+impl_image_interop!(Window);
+impl_image_interop!(Canvas);
+
+/// Blends `pixel` over `color` by the alpha of `pixel`.
+fn blend(pixel: Rgba<u8>, color: Color) -> Color {
+    let [r, g, b, alpha] = pixel.0;
+    match alpha {
+        u8::MAX => color::from_rgb([r, g, b]),
+        0 => color,
+        _ => {
+            let (alpha, background) = (u16::from(alpha), color::to_rgb(color));
+            let mix = |foreground: u8, background: u8| {
+                ((u16::from(foreground) * alpha + u16::from(background) * (255 - alpha) + 127)
+                    / 255) as u8
+            };
+            color::from_rgb([
+                mix(r, background[0]),
+                mix(g, background[1]),
+                mix(b, background[2]),
+            ])
+        }
+    }
+}
+
+#[cfg(feature = "crossterm")]
+impl Window {
+    /// Creates a window sized to the image at `path`, scaled down to fit the terminal if needed
+    /// while keeping its aspect ratio, and draws the image into it.
+    ///
+    /// The supported formats are the ones enabled with the features of the image crate, e.g.
+    /// `image/png`.
+    ///
+    /// ```no_run
+    /// # use winterm::Window;
+    /// let mut window = Window::from_image("picture.png")?;
+    /// window.redraw()?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn from_image(path: impl AsRef<Path>) -> Result<Self> {
+        let mut canvas = open_image(path.as_ref())?;
+        // The border takes 2 columns and 1 or 2 rows, each row holding 2 pixels.
+        let (columns, rows) = terminal::size().unwrap_or((u16::MAX, u16::MAX));
+        let (max_height, max_width) = (
+            rows.saturating_sub(2).saturating_mul(2).max(1),
+            columns.saturating_sub(2).max(1),
+        );
+        let scale = (f32::from(max_height) / f32::from(canvas.height().max(1)))
+            .min(f32::from(max_width) / f32::from(canvas.width().max(1)));
+        if scale < 1. {
+            let height = ((f32::from(canvas.height()) * scale).round() as u16).clamp(1, max_height);
+            let width = ((f32::from(canvas.width()) * scale).round() as u16).clamp(1, max_width);
+            canvas = canvas.resized(height, width, Filter::Bilinear);
+        }
+        let mut window = Window::new(canvas.height(), canvas.width())?;
+        window.blit(&canvas, 0, 0);
+        Ok(window)
+    }
+}
+
+/// Loads an image file into a canvas, scaled down to the largest size of a canvas if needed.
+pub(crate) fn open_image(path: &Path) -> io::Result<Canvas> {
+    let mut image = image::open(path).map_err(io::Error::other)?;
+    let max = u32::from(u16::MAX);
+    if image.height() > max || image.width() > max {
+        image = image.thumbnail(max, max);
+    }
+    let image = image.to_rgb8();
+    let mut canvas = Canvas::new(image.height() as u16, image.width() as u16, Color::Black);
+    for (x, y, rgb) in image.enumerate_pixels() {
+        canvas.set_pixel(y as u16, x as u16, color::from_rgb(rgb.0));
+    }
+    Ok(canvas)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Color;
+    use image::{GenericImageView, Rgb, Rgba, RgbaImage};
+
+    use crate::Canvas;
+
+    #[test]
+    fn rgba_round_trip() {
+        let mut canvas = Canvas::new(2, 3, Color::Rgb { r: 1, g: 2, b: 3 });
+        canvas.set_pixel(1, 2, Color::Rgb { r: 9, g: 8, b: 7 });
+        let image = canvas.to_rgba_image();
+        assert_eq!(image.dimensions(), (3, 2));
+        assert_eq!(*image.get_pixel(2, 1), Rgba([9, 8, 7, 255]));
+        let mut copy = Canvas::new(2, 3, Color::Black);
+        copy.copy_from_rgba(&image, 0, 0);
+        assert_eq!(copy, canvas);
+        assert_eq!(GenericImageView::get_pixel(&copy, 2, 1), Rgb([9, 8, 7]));
+    }
+
+    #[test]
+    fn copy_from_rgba_blends_by_alpha_and_clips() {
+        let mut canvas = Canvas::new(2, 2, Color::Rgb { r: 0, g: 0, b: 200 });
+        let image = RgbaImage::from_fn(3, 3, |x, _| match x {
+            0 => Rgba([255, 0, 0, 0]),
+            1 => Rgba([255, 0, 0, 128]),
+            _ => Rgba([255, 0, 0, 255]),
+        });
+        canvas.copy_from_rgba(&image, 1, 0);
+        assert_eq!(canvas.get_pixel(0, 0), Color::Rgb { r: 0, g: 0, b: 200 });
+        assert_eq!(canvas.get_pixel(1, 0), Color::Rgb { r: 0, g: 0, b: 200 });
+        assert_eq!(
+            canvas.get_pixel(1, 1),
+            Color::Rgb {
+                r: 128,
+                g: 0,
+                b: 100
+            }
+        );
+    }
+}
@@ -0,0 +1,145 @@
+//! Events read from the terminal by a [`Backend`](crate::Backend), before the window turns them
+//! into [`WindowEvent`](crate::WindowEvent)s.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Key, Modifiers};
+
+/// Event read from the terminal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Event {
+    /// The terminal gained focus.
+    FocusGained,
+    /// The terminal lost focus.
+    FocusLost,
+    Key(KeyEvent),
+    /// Reported while the mouse capture is enabled, see
+    /// [`Window::set_mouse_capture`](crate::Window::set_mouse_capture).
+    Mouse(MouseEvent),
+    /// Text pasted into the terminal.
+    Paste(String),
+    /// The terminal was resized to columns and rows.
+    Resize(u16, u16),
+}
+
+/// Key pressed, repeated or released.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct KeyEvent {
+    pub code: Key,
+    pub modifiers: Modifiers,
+    pub kind: KeyEventKind,
+}
+
+impl KeyEvent {
+    /// Creates the press of `code` with `modifiers`.
+    pub const fn new(code: Key, modifiers: Modifiers) -> Self {
+        KeyEvent::new_with_kind(code, modifiers, KeyEventKind::Press)
+    }
+
+    pub const fn new_with_kind(code: Key, modifiers: Modifiers, kind: KeyEventKind) -> Self {
+        KeyEvent {
+            code,
+            modifiers,
+            kind,
+        }
+    }
+}
+
+impl From<Key> for KeyEvent {
+    fn from(code: Key) -> Self {
+        KeyEvent::new(code, Modifiers::NONE)
+    }
+}
+
+/// Kind of a [`KeyEvent`], only some terminals reporting repeats and releases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum KeyEventKind {
+    Press,
+    Repeat,
+    Release,
+}
+
+/// Mouse event over the terminal cell at `column` and `row`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MouseEvent {
+    pub kind: MouseEventKind,
+    pub column: u16,
+    pub row: u16,
+    pub modifiers: Modifiers,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MouseEventKind {
+    Down(MouseButton),
+    Up(MouseButton),
+    /// The mouse moved while a button was held.
+    Drag(MouseButton),
+    Moved,
+    ScrollDown,
+    ScrollUp,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+}
+
+/// Converts a crossterm event, `None` for the ones added after crossterm 0.25, e.g. horizontal
+/// scrolls.
+#[cfg(feature = "crossterm")]
+pub(crate) fn from_crossterm(event: crossterm::event::Event) -> Option<Event> {
+    use crossterm::event::Event as CrosstermEvent;
+
+    Some(match event {
+        CrosstermEvent::FocusGained => Event::FocusGained,
+        CrosstermEvent::FocusLost => Event::FocusLost,
+        CrosstermEvent::Key(key_event) => Event::Key(KeyEvent {
+            code: key_event.code.into(),
+            modifiers: key_event.modifiers.into(),
+            kind: match key_event.kind {
+                crossterm::event::KeyEventKind::Press => KeyEventKind::Press,
+                crossterm::event::KeyEventKind::Repeat => KeyEventKind::Repeat,
+                crossterm::event::KeyEventKind::Release => KeyEventKind::Release,
+            },
+        }),
+        CrosstermEvent::Mouse(mouse_event) => Event::Mouse(MouseEvent {
+            kind: mouse_kind_from_crossterm(mouse_event.kind)?,
+            column: mouse_event.column,
+            row: mouse_event.row,
+            modifiers: mouse_event.modifiers.into(),
+        }),
+        CrosstermEvent::Paste(text) => Event::Paste(text),
+        CrosstermEvent::Resize(columns, rows) => Event::Resize(columns, rows),
+    })
+}
+
+#[cfg(feature = "crossterm")]
+fn mouse_kind_from_crossterm(kind: crossterm::event::MouseEventKind) -> Option<MouseEventKind> {
+    use crossterm::event::MouseEventKind as CrosstermKind;
+
+    let button = |button| match button {
+        crossterm::event::MouseButton::Left => MouseButton::Left,
+        crossterm::event::MouseButton::Right => MouseButton::Right,
+        crossterm::event::MouseButton::Middle => MouseButton::Middle,
+    };
+    // The wildcard matches the horizontal scrolls of the newer versions of crossterm.
+    #[allow(unreachable_patterns)]
+    Some(match kind {
+        CrosstermKind::Down(pressed) => MouseEventKind::Down(button(pressed)),
+        CrosstermKind::Up(released) => MouseEventKind::Up(button(released)),
+        CrosstermKind::Drag(held) => MouseEventKind::Drag(button(held)),
+        CrosstermKind::Moved => MouseEventKind::Moved,
+        CrosstermKind::ScrollDown => MouseEventKind::ScrollDown,
+        CrosstermKind::ScrollUp => MouseEventKind::ScrollUp,
+        _ => return None,
+    })
+}
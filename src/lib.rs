@@ -1,13 +1,14 @@
 //! # winterm
 //! A Rust library to create a pixelated window inside a terminal.
 //!
-//! It uses [crossterm] as a backend.
+//! It uses [crossterm](https://docs.rs/crossterm) as a backend, any version from 0.25 to 0.28,
+//! so it shares the one of the other terminal dependencies of an application. Other terminals,
+//! e.g. xterm.js in a web page, are drawn to through a [`CallbackBackend`].
 //!
 //! # Adding winterm as a dependency
 //!
 //! ```sh
 //! cargo add winterm@0.6.0
-//! cargo add crossterm@0.25.0
 //! ```
 //!
 //! # Create a window
@@ -18,13 +19,13 @@
 //! # let height = 9;
 //! # let width = 16;
 //! let mut window = Window::new(height, width)?;
-//! # Ok::<(), crossterm::ErrorKind>(())
+//! # Ok::<(), std::io::Error>(())
 //! ```
 //!
 //! # Render the next frame
 //!
 //! ```
-//! use crossterm::style::Color;
+//! use winterm::Color;
 //! # use winterm::Window;
 //!
 //! # let mut window = Window::new(9, 16)?;
@@ -41,41 +42,42 @@
 //!     },
 //! );
 //! window.redraw()?;
-//! # Ok::<(), crossterm::ErrorKind>(())
+//! # Ok::<(), std::io::Error>(())
 //! ```
 //!
 //! # React to events
 //!
 //! ```
-//! use crossterm::event::KeyCode;
+//! use winterm::Key;
 //! # use winterm::Window;
 //!
 //! # let mut window = Window::new(9, 16)?;
 //! window.poll_events()?;
-//! if window.get_key(KeyCode::Esc) {
+//! if window.get_key(Key::Esc) {
 //!     // the Escape key has been pressed
 //! }
-//! if window.get_key(KeyCode::Char('w')) {
+//! if window.get_key(Key::Char('w')) {
 //!     // the W key has been pressed
 //! }
-//! # Ok::<(), crossterm::ErrorKind>(())
+//! # Ok::<(), std::io::Error>(())
 //! ```
 //!
 //! # Example
 //!
 //! ```no_run
-//! use crossterm::{event::KeyCode, style::Color, Result};
-//! use winterm::Window;
+//! use std::io::Result;
+//!
+//! use winterm::{Color, Key, Window};
 //!
 //! fn main() -> Result<()> {
 //!     let mut window = Window::new(9, 16)?;
 //!     let mut color = Color::Black;
 //!     loop {
 //!         window.poll_events()?;
-//!         if window.get_key(KeyCode::Esc) {
+//!         if window.get_key(Key::Esc) {
 //!             break;
 //!         }
-//!         if window.get_key(KeyCode::Char('n')) {
+//!         if window.get_key(Key::Char('n')) {
 //!             color = match color {
 //!                 Color::Black => Color::Red,
 //!                 Color::Red => Color::Rgb {
@@ -107,54 +109,561 @@
 //! ```
 //! You can now use `cat logs` after execution or `tail -f logs` in another terminal to get your output while the code is still running.
 //!
+//! With the `log` feature, records of the [log](https://docs.rs/log) crate can also be shown over the window :
+//! install the logger with `winterm::logger::Logger::init` and toggle the console with the `` ` `` key.
+//! The logger can also append the records to a file with `Logger::with_file`, and the recent ones can be
+//! read with `winterm::logger::recent_entries`.
+//!
+//! Rendering glitches lasting a single frame can be inspected with the frame debugger, which freezes the
+//! application and steps through the last frames : see `Window::set_debugger_key`.
+//!
 //! [stderr]: https://en.wikipedia.org/wiki/Standard_streams#Standard_error_(stderr)
+//!
+//! # Features
+//!
+//! - `crossterm` (default) : `Window::new` and the `CrosstermBackend` drawing to the standard output with [crossterm](https://docs.rs/crossterm).
+//! - `wasm` : the `xterm` module, drawing to an [xterm.js](https://xtermjs.org) terminal on `wasm32` targets, usually with `--no-default-features --features wasm`.
+//! - `nalgebra` (default) : conversions between the pixels and [nalgebra](https://docs.rs/nalgebra) matrices, heatmaps, and the `wireframe` module.
+//! - `log` : logger and on-screen console for the records of the [log](https://docs.rs/log) crate.
+//! - `image` : [`Window`] and [`Canvas`] implement the `GenericImageView` trait of the [image](https://docs.rs/image) crate and convert from and to RGBA images, and `Window::from_image` displays an image file with the `crossterm` feature.
+//! - `bevy_winterm` : plugin presenting a [Bevy](https://bevyengine.org) image and feeding it the keyboard input.
+//! - `ndarray` : conversions between the pixels and [ndarray](https://docs.rs/ndarray) arrays.
+//! - `ratatui` : `WintermWidget` renders pixels inside a [ratatui](https://docs.rs/ratatui) user interface.
+//! - `obj` : loading of Wavefront OBJ meshes, drawn with flat shading by the `mesh` module.
+//! - `qr` : drawing of QR codes with the `qr` module, using the [qrcode](https://docs.rs/qrcode) crate.
+//! - `tracing` : [tracing](https://docs.rs/tracing) spans around the redraws, split into encoding and flushing, and the events polling, for profiling.
+//! - `rayon` : large frames are encoded in parallel with [rayon](https://docs.rs/rayon).
+//! - `stream` : streaming of the frames over TCP, watched with `cargo run --example stream_viewer --features stream`.
+//! - `remote` : remote control of a window with input events received over TCP, with the `remote` module.
+//! - `script` : scripting of the drawing and input with [Rhai](https://rhai.rs), with the `script` module.
+//! - `play` : `winterm-play` animated GIF player, installed with `cargo install winterm --features play`.
+//! - `view` : `winterm-view` image viewer, installed with `cargo install winterm --features view`.
+//! - `notify` : hot reloading of canvases when their files change with the `watch` module, using [notify](https://docs.rs/notify).
+//! - `ui` : immediate mode buttons, checkboxes and sliders with the `ui` module.
+//! - `serde` : [`Canvas`], [`Rect`] and the widgets implement `Serialize` and `Deserialize` from [serde](https://docs.rs/serde).
 
-use std::io::{stdout, Write};
+use std::cmp;
+#[cfg(feature = "crossterm")]
+use std::io::{stdout, IsTerminal};
+use std::io::{Result, Write};
+use std::ops::Range;
 use std::time::Duration;
-use std::{cmp, iter};
-
-use crossterm::cursor::{Hide, MoveTo, Show};
-use crossterm::event::KeyModifiers;
-use crossterm::event::{self, Event, Event::Key, Event::Resize, KeyCode};
-use crossterm::style::{Color, Colors, Print, SetBackgroundColor, SetColors, SetForegroundColor};
-use crossterm::terminal::{
-    Clear, ClearType, DisableLineWrap, EnableLineWrap, EnterAlternateScreen, LeaveAlternateScreen,
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use std::time::{Instant, SystemTime, UNIX_EPOCH};
+#[cfg(target_arch = "wasm32")]
+pub(crate) use web_time::{Instant, SystemTime, UNIX_EPOCH};
+
+use ansi::{
+    queue, BeginSynchronizedUpdate, ClearAll, EndSynchronizedUpdate, MoveTo, Print,
+    SetBackgroundColor, SetColors, SetForegroundColor,
 };
-use crossterm::{execute, queue, terminal, Result};
+use input::{Event, Event::Mouse, Event::Resize};
+
+mod ansi;
+mod assets;
+mod atlas;
+mod backend;
+#[cfg(feature = "bevy_winterm")]
+pub mod bevy;
+mod brush;
+mod callback;
+mod canvas;
+mod clipboard;
+pub mod color;
+mod debounce;
+mod debugger;
+mod depth;
+mod describe;
+mod dialog;
+mod draw;
+mod event;
+pub mod font;
+mod frame;
+mod frame_guard;
+mod guard;
+mod history;
+mod hud;
+#[cfg(feature = "image")]
+mod image_interop;
+pub mod input;
+#[cfg(feature = "log")]
+pub mod logger;
+mod matrix;
+#[cfg(feature = "obj")]
+pub mod mesh;
+pub mod noise;
+mod pinned;
+pub mod plot;
+mod proxy;
+#[cfg(feature = "qr")]
+pub mod qr;
+pub mod raster;
+#[cfg(feature = "ratatui")]
+mod ratatui_widget;
+mod rect;
+#[cfg(feature = "remote")]
+pub mod remote;
+mod render_thread;
+mod scene;
+mod screenshot;
+#[cfg(feature = "script")]
+pub mod script;
+mod scroll;
+mod sequence;
+mod status;
+mod storage;
+#[cfg(feature = "stream")]
+pub mod stream;
+pub mod testing;
+pub mod turtle;
+mod types;
+#[cfg(feature = "ui")]
+pub mod ui;
+mod undo;
+#[cfg(feature = "notify")]
+pub mod watch;
+pub mod widgets;
+#[cfg(feature = "nalgebra")]
+pub mod wireframe;
+#[cfg(feature = "wasm")]
+pub mod xterm;
+pub use assets::{Assets, Handle};
+pub use atlas::Atlas;
+#[cfg(feature = "crossterm")]
+pub use backend::CrosstermBackend;
+#[cfg(feature = "crossterm")]
+use backend::DumpBackend;
+use backend::Terminal;
+pub use backend::{Backend, CallbackBackend, EventQueue};
+pub use callback::CallbackId;
+pub use canvas::{Canvas, Filter};
+pub use draw::Draw;
+pub use event::{TimerId, WindowEvent};
+pub use frame_guard::Frame;
+pub use guard::TerminalGuard;
+pub use hud::Anchor;
+pub use pinned::PinnedLabelId;
+pub use proxy::WindowProxy;
+#[cfg(feature = "ratatui")]
+pub use ratatui_widget::WintermWidget;
+pub use rect::{Margins, Rect};
+pub use render_thread::RenderThread;
+pub use scene::{Entity, EntityId, Scene};
+pub use sequence::SequenceId;
+pub use storage::{IndexedCanvas, Pixel, PixelBuffer, PixelSlice, PixelStorage};
+pub use types::{Color, Key, Modifiers};
 
+#[cfg(feature = "nalgebra")]
 extern crate nalgebra as na;
-use na::{DMatrix, Point2, Vector2};
 
 const UPPER_HALF_BLOCK: &str = "▀";
 const LOWER_HALF_BLOCK: &str = "▄";
 const FULL_BLOCK: &str = "█";
 
+/// Column `x` and row `y` of a terminal position or size.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct Vector2<T> {
+    x: T,
+    y: T,
+}
+
+impl<T> Vector2<T> {
+    fn new(x: T, y: T) -> Self {
+        Vector2 { x, y }
+    }
+}
+
+/// Colors last sent to the terminal, used to skip redundant color changes.
+#[derive(Debug, Default)]
+struct ColorsState {
+    foreground: Option<Color>,
+    background: Option<Color>,
+}
+
+impl ColorsState {
+    fn queue(
+        &mut self,
+        output: &mut impl Write,
+        foreground: Color,
+        background: Color,
+    ) -> Result<()> {
+        if self.foreground != Some(foreground) {
+            queue!(output, SetForegroundColor(foreground))?;
+            self.foreground = Some(foreground);
+        }
+        if self.background != Some(background) {
+            queue!(output, SetBackgroundColor(background))?;
+            self.background = Some(background);
+        }
+        Ok(())
+    }
+}
+
+/// Number of changed cells from which a frame is encoded in parallel.
+#[cfg(feature = "rayon")]
+const PARALLEL_ENCODING_CELLS: usize = 16 * 1024;
+
+/// How the cells are written to the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Encoding {
+    /// Half blocks with the colors of the pixels.
+    Full,
+    /// Half blocks with the colors reduced to the 256 colors palette, see
+    /// [`Window::set_adaptive_quality`].
+    Palette256,
+    /// ASCII characters with the 16 ANSI colors, see [`Window::set_legacy_console`].
+    Legacy,
+}
+
+impl Encoding {
+    /// Converts a color to the ones written with the encoding.
+    fn color(self, color: Color) -> Color {
+        match self {
+            Encoding::Full => color,
+            Encoding::Palette256 => color::to_ansi_256(color),
+            Encoding::Legacy => color::to_ansi(color),
+        }
+    }
+}
+
+/// Pixels drawn at the position of the window in the terminal.
+struct CellsLayout<'a> {
+    pixels: &'a Canvas,
+    origin: Vector2<i16>,
+    terminal_size: Vector2<u16>,
+    encoding: Encoding,
+    dim: f32,
+    /// Size in terminal pixels of each pixel, see [`Window::set_integer_scaling`].
+    scale: u16,
+}
+
+impl CellsLayout<'_> {
+    /// Queues the terminal cells of the row `cell_y`, each cell containing two pixels.
+    ///
+    /// When the pixels are scaled, the row and the columns are drawn on `scale` rows of cells.
+    fn queue_cells(
+        &self,
+        output: &mut impl Write,
+        colors: &mut ColorsState,
+        cell_y: u16,
+        columns: Range<u16>,
+    ) -> Result<()> {
+        let start_x = cmp::max((columns.start * self.scale) as i16, -self.origin.x);
+        let end_x = cmp::min(
+            (columns.end * self.scale) as i16,
+            self.terminal_size.x as i16 - self.origin.x,
+        );
+        let rows = (self.pixels.height() * self.scale).div_ceil(2);
+        for row in cell_y * self.scale..cmp::min((cell_y + 1) * self.scale, rows) {
+            let y = self.origin.y + row as i16;
+            if y < 0 || y >= self.terminal_size.y as i16 || start_x >= end_x {
+                continue;
+            }
+            queue!(output, MoveTo((self.origin.x + start_x) as u16, y as u16))?;
+            self.queue_cells_run(output, colors, row, start_x as u16..end_x as u16)?;
+        }
+        Ok(())
+    }
+
+    /// Queues the terminal cells of the row `row` from the current cursor position, `row` and
+    /// `columns` being in scaled cells.
+    fn queue_cells_run(
+        &self,
+        output: &mut impl Write,
+        colors: &mut ColorsState,
+        row: u16,
+        columns: Range<u16>,
+    ) -> Result<()> {
+        let (pixels, scale) = (self.pixels, self.scale);
+        let upper_y = row * 2;
+        let lower_y = upper_y + 1;
+        let cell = |x: u16| {
+            let cell = if lower_y < pixels.height() * scale {
+                (
+                    pixels.get_pixel(upper_y / scale, x / scale),
+                    pixels.get_pixel(lower_y / scale, x / scale),
+                    UPPER_HALF_BLOCK,
+                )
+            } else {
+                (
+                    Color::Reset,
+                    pixels.get_pixel(upper_y / scale, x / scale),
+                    LOWER_HALF_BLOCK,
+                )
+            };
+            let cell = if self.dim > 0. {
+                (darken(cell.0, self.dim), darken(cell.1, self.dim), cell.2)
+            } else {
+                cell
+            };
+            match self.encoding {
+                Encoding::Full => cell,
+                Encoding::Palette256 => (
+                    color::to_ansi_256(cell.0),
+                    color::to_ansi_256(cell.1),
+                    cell.2,
+                ),
+                Encoding::Legacy => legacy_cell(cell),
+            }
+        };
+        let end_x = columns.end;
+        let mut x = columns.start;
+        while x < end_x {
+            let (foreground, background, glyph) = cell(x);
+            let mut count = 1;
+            while x + count < end_x && cell(x + count) == (foreground, background, glyph) {
+                count += 1;
+            }
+            colors.queue(output, foreground, background)?;
+            queue!(output, Print(glyph.repeat(count.into())))?;
+            x += count;
+        }
+        Ok(())
+    }
+}
+
+/// Queues the whole pixels line by line, without moving the cursor.
+fn queue_text_frame(output: &mut impl Write, pixels: &Canvas, encoding: Encoding) -> Result<()> {
+    let layout = CellsLayout {
+        pixels,
+        origin: Vector2::default(),
+        terminal_size: Vector2::new(pixels.width(), pixels.height().div_ceil(2)),
+        encoding,
+        dim: 0.,
+        scale: 1,
+    };
+    for cell_y in 0..pixels.height().div_ceil(2) {
+        let mut colors = ColorsState::default();
+        layout.queue_cells_run(output, &mut colors, cell_y, 0..pixels.width())?;
+        colors.queue(output, Color::Reset, Color::Reset)?;
+        queue!(output, Print("\n"))?;
+    }
+    Ok(())
+}
+
+/// Darkens a color towards black by `level`, from 0 to 1, the default color being kept.
+fn darken(color: Color, level: f32) -> Color {
+    if color == Color::Reset {
+        return color;
+    }
+    color::from_rgb(color::to_rgb(color).map(|channel| (f32::from(channel) * (1. - level)) as u8))
+}
+
+/// Converts a cell to its ASCII and 16 colors version, see [`Window::set_legacy_console`].
+fn legacy_cell(
+    (foreground, background, glyph): (Color, Color, &'static str),
+) -> (Color, Color, &'static str) {
+    let foreground = color::to_ansi(foreground);
+    let background = color::to_ansi(background);
+    if glyph == UPPER_HALF_BLOCK && foreground != background {
+        (foreground, background, "#")
+    } else {
+        (Color::Reset, background, " ")
+    }
+}
+
 /// Window representation.
 /// Used for drawing and events handling.
 #[derive(Debug)]
 pub struct Window {
     terminal_size: Vector2<u16>,
-    origin: Point2<i16>,
-    pixels: DMatrix<Color>,
+    origin: Vector2<i16>,
+    pixels: Canvas,
+    front: Canvas,
+    clip: Option<Rect>,
+    wrap_mode: bool,
+    brush: Option<Canvas>,
     last_events: Vec<Event>,
+    case_insensitive_keys: bool,
+    mouse_capture: bool,
+    mouse_cell: Option<(u16, u16)>,
+    sequences: Vec<sequence::KeySequence>,
+    timers: Vec<event::Timer>,
+    debounce: debounce::Debounce,
+    callbacks: callback::Callbacks,
+    tags: Vec<(String, Rect)>,
+    status_lines: Vec<String>,
+    labels: Vec<(hud::Anchor, String)>,
+    /// Rows, columns and lengths of the previous texts of the labels, cleared by the next redraw.
+    stale_labels: Vec<(i32, i32, usize)>,
+    pinned_labels: pinned::PinnedLabels,
+    synchronized_output: bool,
+    legacy_console: bool,
+    dump_frames: bool,
+    terminal_background: Option<Color>,
+    terminal: Terminal,
+    dirty_rows: Vec<Option<Range<u16>>>,
+    should_redraw_all: bool,
+    show_fps: bool,
+    flash: Option<(Color, Instant)>,
+    last_redraw: Option<Instant>,
+    frame_time: Duration,
+    latency_budget: Option<Duration>,
+    flush_time: Duration,
+    reduced_colors: bool,
+    last_present: Option<Instant>,
+    clear_color: Color,
+    dim: f32,
+    integer_scaling: bool,
+    scale: u16,
+    frame: Vec<u8>,
+    /// Bytes of `frame` as filled by [`Window::frame_mut`], to find the changed pixels.
+    frame_snapshot: Vec<u8>,
+    depth: Vec<f32>,
+    proxy: Option<proxy::ProxyChannel>,
+    debugger_key: Option<Key>,
+    history: history::FrameHistory,
+    undo_stack: undo::UndoStack,
+    screenshots: screenshot::Screenshots,
+    #[cfg(feature = "stream")]
+    streamer: Option<stream::FrameStreamer>,
+    #[cfg(feature = "remote")]
+    remote: Option<remote::EventListener>,
+    #[cfg(feature = "log")]
+    show_console: bool,
+    #[cfg(feature = "log")]
+    console_key: Option<Key>,
+    #[cfg(feature = "log")]
+    console_lines: u16,
 }
 
 impl Window {
     fn calculate_origin(&mut self) {
-        self.origin.x = (self.terminal_size.x as f32 / 2. - self.width() as f32 / 2.) as i16;
-        self.origin.y = (self.terminal_size.y as f32 / 2. - self.height() as f32 / 4.) as i16;
+        let rows = self
+            .terminal_size
+            .y
+            .saturating_sub(self.status_lines.len() as u16);
+        self.scale = if self.integer_scaling {
+            // The border takes 2 columns and 1 or 2 rows.
+            let scale_x = self.terminal_size.x.saturating_sub(2) / cmp::max(self.width(), 1);
+            let scale_y = rows.saturating_sub(2) * 2 / cmp::max(self.height(), 1);
+            cmp::max(cmp::min(scale_x, scale_y), 1)
+        } else {
+            1
+        };
+        self.origin.x = (self.terminal_size.x as f32 / 2. - self.scaled_width() as f32 / 2.) as i16;
+        self.origin.y = (rows as f32 / 2. - self.scaled_height() as f32 / 4.) as i16;
+    }
+
+    /// Gets the window height in terminal pixels, see [`Window::set_integer_scaling`].
+    fn scaled_height(&self) -> u16 {
+        self.height() * self.scale
+    }
+
+    /// Gets the window width in terminal pixels, see [`Window::set_integer_scaling`].
+    fn scaled_width(&self) -> u16 {
+        self.width() * self.scale
     }
 
     /// Creates a window.
+    ///
+    /// Legacy Windows consoles without support for ANSI escape sequences are detected and drawn
+    /// to in legacy console mode, see [`Window::set_legacy_console`].
+    ///
+    /// When the standard output is not a terminal, e.g. when it is piped or redirected to a file,
+    /// the window instead writes one ANSI art dump of the whole frame per redraw and reads no
+    /// events, which is useful to generate demos in CI.
+    #[cfg(feature = "crossterm")]
     pub fn new(height: u16, width: u16) -> Result<Self> {
-        let (columns, rows) = terminal::size()?;
-        execute!(stdout(), EnterAlternateScreen, DisableLineWrap, Hide)?;
-        terminal::enable_raw_mode()?;
+        if !stdout().is_terminal() {
+            return Window::create(height, width, Box::new(DumpBackend), false, true);
+        }
+        #[cfg(windows)]
+        let legacy_console = !crossterm::ansi_support::supports_ansi();
+        #[cfg(not(windows))]
+        let legacy_console = false;
+        Window::create(
+            height,
+            width,
+            Box::new(CrosstermBackend),
+            legacy_console,
+            false,
+        )
+    }
+
+    /// Creates a window whose pixels are colored by `f`, called with their `y` and `x` row by row,
+    /// see [`Window::new`].
+    #[cfg(feature = "crossterm")]
+    pub fn from_fn(height: u16, width: u16, f: impl FnMut(u16, u16) -> Color) -> Result<Self> {
+        let mut window = Window::new(height, width)?;
+        window.blit(&Canvas::from_fn(height, width, f), 0, 0);
+        Ok(window)
+    }
+
+    /// Creates a window drawn to and receiving its events from `backend`.
+    pub fn with_backend(height: u16, width: u16, backend: impl Backend + 'static) -> Result<Self> {
+        Window::create(height, width, Box::new(backend), false, false)
+    }
+
+    fn create(
+        height: u16,
+        width: u16,
+        mut backend: Box<dyn Backend>,
+        legacy_console: bool,
+        dump_frames: bool,
+    ) -> Result<Self> {
+        let (columns, rows) = backend.size()?;
+        backend.enter()?;
+        let terminal_background = backend.query_background().unwrap_or(None);
+        let clear_color = Color::Black;
         let mut window = Window {
             terminal_size: Vector2::new(columns, rows),
-            origin: Point2::origin(),
-            pixels: DMatrix::from_element(height.into(), width.into(), Color::Black),
+            origin: Vector2::default(),
+            pixels: Canvas::new(height, width, clear_color),
+            front: Canvas::new(height, width, clear_color),
+            clip: None,
+            wrap_mode: false,
+            brush: None,
             last_events: Vec::new(),
+            case_insensitive_keys: false,
+            mouse_capture: false,
+            mouse_cell: None,
+            sequences: Vec::new(),
+            timers: Vec::new(),
+            debounce: debounce::Debounce::default(),
+            callbacks: callback::Callbacks::default(),
+            tags: Vec::new(),
+            status_lines: Vec::new(),
+            labels: Vec::new(),
+            stale_labels: Vec::new(),
+            pinned_labels: pinned::PinnedLabels::default(),
+            synchronized_output: true,
+            legacy_console,
+            dump_frames,
+            terminal_background,
+            terminal: Terminal::new(backend),
+            dirty_rows: vec![None; height.div_ceil(2).into()],
+            should_redraw_all: false,
+            show_fps: false,
+            flash: None,
+            last_redraw: None,
+            frame_time: Duration::ZERO,
+            latency_budget: None,
+            flush_time: Duration::ZERO,
+            reduced_colors: false,
+            last_present: None,
+            clear_color,
+            dim: 0.,
+            integer_scaling: false,
+            scale: 1,
+            frame: Vec::new(),
+            frame_snapshot: Vec::new(),
+            depth: Vec::new(),
+            proxy: None,
+            debugger_key: None,
+            history: history::FrameHistory::default(),
+            undo_stack: undo::UndoStack::default(),
+            screenshots: screenshot::Screenshots::default(),
+            #[cfg(feature = "stream")]
+            streamer: None,
+            #[cfg(feature = "remote")]
+            remote: None,
+            #[cfg(feature = "log")]
+            show_console: false,
+            #[cfg(feature = "log")]
+            console_key: Some(Key::Char('`')),
+            #[cfg(feature = "log")]
+            console_lines: 10,
         };
         window.calculate_origin();
         window.redraw_all()?;
@@ -163,175 +672,874 @@ impl Window {
 
     /// Gets the window width.
     pub fn width(&self) -> u16 {
-        self.pixels.ncols() as u16
+        self.pixels.width()
     }
 
     /// Gets the window height.
     pub fn height(&self) -> u16 {
-        self.pixels.nrows() as u16
+        self.pixels.height()
     }
 
     fn end_x(&self) -> u16 {
-        (self.origin.x + self.width() as i16) as u16
+        (self.origin.x + self.scaled_width() as i16) as u16
     }
 
     fn end_y(&self) -> u16 {
-        (self.origin.y + ((self.height() + 1) / 2) as i16) as u16
-    }
-
-    /// Sets a pixel color.
-    pub fn set_pixel(&mut self, y: u16, x: u16, color: Color) {
-        self.pixels[(y.into(), x.into())] = color;
-    }
-
-    /// Redraws the window to the terminal.
-    pub fn redraw(&self) -> Result<()> {
-        let skipable_rows_count = cmp::max(-self.origin.y, 0) as usize;
-        let skipable_columns_count = cmp::max(-self.origin.x, 0) as usize;
-        let start_x = cmp::max(self.origin.x, 0) as u16;
-        for (y, (upper, lower)) in iter::zip(
-            cmp::max(self.origin.y, 0) as u16..cmp::min(self.end_y(), self.terminal_size.y),
-            iter::zip(
-                self.pixels.row_iter().skip(skipable_rows_count).step_by(2),
-                self.pixels
-                    .row_iter()
-                    .skip(skipable_rows_count + 1)
-                    .step_by(2),
-            ),
-        ) {
-            queue!(stdout(), MoveTo(start_x, y))?;
-            for (foreground, background) in iter::zip(
-                upper
-                    .into_iter()
-                    .skip(skipable_columns_count)
-                    .take(self.terminal_size.x as usize),
-                lower
-                    .into_iter()
-                    .skip(skipable_columns_count)
-                    .take(self.terminal_size.x as usize),
-            ) {
-                queue!(
-                    stdout(),
-                    SetColors(Colors::new(*foreground, *background)),
-                    Print(UPPER_HALF_BLOCK),
-                )?;
+        (self.origin.y + self.scaled_height().div_ceil(2) as i16) as u16
+    }
+
+    /// Enables or disables integer scaling, disabled by default.
+    ///
+    /// While enabled, the window size is a fixed logical resolution, e.g. 144 by 160 pixels, each
+    /// pixel being drawn as a square of terminal pixels of the largest size fitting the terminal,
+    /// see [`Window::scale`]. The frame stays centered, surrounded by letterbox bars.
+    pub fn set_integer_scaling(&mut self, enabled: bool) {
+        if self.integer_scaling != enabled {
+            self.integer_scaling = enabled;
+            self.calculate_origin();
+            self.should_redraw_all = true;
+        }
+    }
+
+    /// Gets the size, in terminal pixels, of the side of each pixel, 1 unless integer scaling is
+    /// enabled, see [`Window::set_integer_scaling`].
+    pub fn scale(&self) -> u16 {
+        self.scale
+    }
+
+    /// Enables or disables synchronized output, enabled by default.
+    ///
+    /// When enabled, each redraw is wrapped in a synchronized update so that supporting terminals
+    /// never display a partially drawn frame.
+    pub fn set_synchronized_output(&mut self, enabled: bool) {
+        self.synchronized_output = enabled;
+    }
+
+    /// Enables or disables legacy console mode, for consoles without Unicode block characters or
+    /// more than 16 colors.
+    ///
+    /// Each cell is drawn with an ASCII character, a space for two pixels of the same color and a
+    /// `#` mixing them otherwise, and colors are reduced to the 16 ANSI ones.
+    pub fn set_legacy_console(&mut self, enabled: bool) {
+        if self.legacy_console != enabled {
+            self.legacy_console = enabled;
+            self.should_redraw_all = true;
+        }
+    }
+
+    /// Darkens the drawn frame towards black by `level`, from 0, the default, to 1, the pixels being
+    /// left untouched.
+    ///
+    /// It gives a paused look to the frame, e.g. under a menu drawn with labels.
+    pub fn set_dim(&mut self, level: f32) {
+        let level = level.clamp(0., 1.);
+        if self.dim != level {
+            self.dim = level;
+            self.should_redraw_all = true;
+        }
+    }
+
+    /// Gets the dim level, see [`Window::set_dim`].
+    pub fn dim(&self) -> f32 {
+        self.dim
+    }
+
+    /// Gets the terminal background color, detected when the window is created.
+    ///
+    /// It is `None` if the terminal did not answer the query, see [`Backend::query_background`].
+    pub fn terminal_background(&self) -> Option<Color> {
+        self.terminal_background
+    }
+
+    /// Gets the colors of the console and dialogs, dark text on a light background if the
+    /// terminal one is light, see [`color::is_light`].
+    fn overlay_colors(&self) -> SetColors {
+        if self.terminal_background.is_some_and(color::is_light) {
+            SetColors(Color::Black, Color::White)
+        } else {
+            SetColors(Color::White, Color::Black)
+        }
+    }
+
+    /// Enables adaptive quality with a latency budget, or disables it with `None`, the default.
+    ///
+    /// When writing a frame to the terminal takes longer than `budget` on average, e.g. over a
+    /// slow SSH connection, the colors are reduced to the 256 colors palette to shorten the output,
+    /// and the redraws are skipped while the terminal is busy with the previous frame, to keep the
+    /// input lag bounded. The changes of the skipped redraws are presented by the next ones.
+    pub fn set_adaptive_quality(&mut self, budget: Option<Duration>) {
+        self.latency_budget = budget;
+        if budget.is_none() && self.reduced_colors {
+            self.reduced_colors = false;
+            self.should_redraw_all = true;
+        }
+    }
+
+    /// Gets the time taken to write a frame to the terminal, smoothed over the last frames.
+    pub fn flush_time(&self) -> Duration {
+        self.flush_time
+    }
+
+    fn encoding(&self) -> Encoding {
+        if self.legacy_console {
+            Encoding::Legacy
+        } else if self.reduced_colors {
+            Encoding::Palette256
+        } else {
+            Encoding::Full
+        }
+    }
+
+    fn queue_begin_synchronized_update(&mut self) -> Result<()> {
+        if self.synchronized_output {
+            queue!(self.terminal, BeginSynchronizedUpdate)?;
+        }
+        Ok(())
+    }
+
+    /// Enables or disables wrap mode, disabled by default.
+    ///
+    /// When enabled, [`Window::get_pixel`] and [`Window::set_pixel`] wrap the coordinates
+    /// outside of the window around it, modulo its height and width, instead of panicking.
+    pub fn set_wrap_mode(&mut self, enabled: bool) {
+        self.wrap_mode = enabled;
+    }
+
+    fn wrap(&self, y: u16, x: u16) -> (u16, u16) {
+        if self.wrap_mode && self.height() > 0 && self.width() > 0 {
+            (y % self.height(), x % self.width())
+        } else {
+            (y, x)
+        }
+    }
+
+    /// Gets a pixel color.
+    pub fn get_pixel(&self, y: u16, x: u16) -> Color {
+        let (y, x) = self.wrap(y, x);
+        self.pixels.get_pixel(y, x)
+    }
+
+    /// Sets the color filling the pixels on [`Window::clear`], black by default.
+    ///
+    /// The terminal around the window is also painted with it when it is redrawn entirely, e.g.
+    /// after a resize.
+    pub fn set_clear_color(&mut self, color: impl Into<Color>) {
+        let color = color.into();
+        if self.clear_color != color {
+            self.clear_color = color;
+            self.should_redraw_all = true;
+        }
+    }
+
+    /// Gets the color filling the pixels on [`Window::clear`].
+    pub fn clear_color(&self) -> Color {
+        self.clear_color
+    }
+
+    /// Fills the pixels with the clear color, see [`Window::set_clear_color`], ignoring the clip
+    /// rectangle.
+    pub fn clear(&mut self) {
+        let color = self.clear_color;
+        self.pixels_mut().fill(color);
+    }
+
+    /// Gets the pixels, row by row.
+    pub fn pixels(&self) -> &[Color] {
+        self.pixels.pixels()
+    }
+
+    /// Gets the pixels mutably, row by row, the whole window being marked as dirty.
+    pub fn pixels_mut(&mut self) -> &mut [Color] {
+        self.mark_dirty(Rect::new(0, 0, self.height(), self.width()));
+        self.pixels.pixels_mut()
+    }
+
+    /// Iterates over the pixels row by row, with their `y` and `x`.
+    pub fn enumerate_pixels(&self) -> impl ExactSizeIterator<Item = (u16, u16, &Color)> {
+        self.pixels.enumerate_pixels()
+    }
+
+    /// Iterates mutably over the pixels row by row, with their `y` and `x`, the whole window
+    /// being marked as dirty.
+    ///
+    /// The iterator can be bridged to rayon with `par_bridge`, to compute the pixels in parallel.
+    pub fn enumerate_pixels_mut(
+        &mut self,
+    ) -> impl ExactSizeIterator<Item = (u16, u16, &mut Color)> {
+        self.mark_dirty(Rect::new(0, 0, self.height(), self.width()));
+        self.pixels.enumerate_pixels_mut()
+    }
+
+    /// Iterates over the rows of pixels, from top to bottom.
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = &[Color]> {
+        self.pixels.rows()
+    }
+
+    /// Iterates mutably over the rows of pixels, from top to bottom, the whole window being
+    /// marked as dirty.
+    pub fn rows_mut(&mut self) -> impl ExactSizeIterator<Item = &mut [Color]> {
+        self.mark_dirty(Rect::new(0, 0, self.height(), self.width()));
+        self.pixels.rows_mut()
+    }
+
+    /// Iterates over the columns of pixels, from left to right, each one from top to bottom.
+    pub fn columns(
+        &self,
+    ) -> impl ExactSizeIterator<Item = impl ExactSizeIterator<Item = &Color> + Clone> {
+        self.pixels.columns()
+    }
+
+    /// Replaces each pixel by the color returned by `f`, called with its `y`, `x` and color row
+    /// by row, e.g. for a tint or a fade, only the changed pixels being marked as dirty.
+    ///
+    /// ```
+    /// use winterm::{color, Window};
+    ///
+    /// # let mut window = Window::new(9, 16)?;
+    /// // Darkens the whole frame by half.
+    /// window.map_pixels(|_, _, pixel| color::from_rgb(color::to_rgb(pixel).map(|c| c / 2)));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn map_pixels(&mut self, mut f: impl FnMut(u16, u16, Color) -> Color) {
+        let width = self.width() as usize;
+        for y in 0..self.height() {
+            let start = y as usize * width;
+            let row = &mut self.pixels.pixels_mut()[start..start + width];
+            let mut changed: Option<Range<u16>> = None;
+            for (x, pixel) in (0..).zip(row) {
+                let color = f(y, x, *pixel);
+                if color != *pixel {
+                    *pixel = color;
+                    changed = Some(changed.map_or(x..x + 1, |changed| changed.start..x + 1));
+                }
+            }
+            if let Some(columns) = changed {
+                self.mark_cells_dirty(y / 2, columns);
             }
         }
-        if self.height() % 2 == 1 && self.end_y() <= self.terminal_size.y {
-            queue!(
-                stdout(),
-                MoveTo(start_x, self.end_y() - 1),
-                SetForegroundColor(Color::Reset)
-            )?;
-            for background in self
-                .pixels
-                .row_iter()
-                .last()
-                .unwrap()
-                .into_iter()
-                .skip(skipable_columns_count)
-                .take(self.terminal_size.x as usize)
+    }
+
+    /// Gets the RGB values of the pixels, row by row, see [`color::to_rgb`].
+    pub fn as_rgb_bytes(&self) -> Vec<u8> {
+        self.pixels.as_rgb_bytes()
+    }
+
+    /// Gets the pixels as a canvas, e.g. to keep a copy of the frame for [`Window::diff`].
+    pub fn canvas(&self) -> &Canvas {
+        &self.pixels
+    }
+
+    /// Gets the pixels that differ from `other`, with their coordinates, row by row.
+    ///
+    /// E.g. to send only the changes since the last frame sent over a network:
+    ///
+    /// ```no_run
+    /// # use winterm::{Color, Window};
+    /// let mut window = Window::new(50, 50)?;
+    /// let sent = window.canvas().clone();
+    /// window.set_pixel(0, 0, Color::Red);
+    /// assert_eq!(window.diff(&sent).count(), 1);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other` does not have the size of the window.
+    pub fn diff<'a>(&'a self, other: &'a Canvas) -> impl Iterator<Item = (u16, u16, Color)> + 'a {
+        assert!(
+            other.height() == self.height() && other.width() == self.width(),
+            "the canvas size differs from the window size"
+        );
+        let width = self.width() as usize;
+        self.pixels()
+            .iter()
+            .zip(other.pixels())
+            .enumerate()
+            .filter(|(_, (pixel, other))| pixel != other)
+            .map(move |(i, (pixel, _))| ((i / width) as u16, (i % width) as u16, *pixel))
+    }
+
+    /// Sets a pixel color, unless it is outside of the clip rectangle.
+    pub fn set_pixel(&mut self, y: u16, x: u16, color: impl Into<Color>) {
+        let (y, x) = self.wrap(y, x);
+        if self.is_clipped(y, x) {
+            return;
+        }
+        self.pixels.set_pixel(y, x, color.into());
+        self.mark_cells_dirty(y / 2, x..x + 1);
+    }
+
+    /// Restricts the pixels set, with [`Window::set_pixel`] and the [`Draw`] methods, to `rect`.
+    pub fn set_clip_rect(&mut self, rect: Rect) {
+        self.clip = Some(rect);
+    }
+
+    /// Removes the clip rectangle, see [`Window::set_clip_rect`].
+    pub fn clear_clip(&mut self) {
+        self.clip = None;
+    }
+
+    /// Gets the clip rectangle, see [`Window::set_clip_rect`].
+    pub fn clip_rect(&self) -> Option<Rect> {
+        self.clip
+    }
+
+    fn is_clipped(&self, y: u16, x: u16) -> bool {
+        self.clip.is_some_and(|clip| !clip.contains(y, x))
+    }
+
+    fn mark_cells_dirty(&mut self, cell_y: u16, columns: Range<u16>) {
+        let dirty = &mut self.dirty_rows[cell_y as usize];
+        *dirty = Some(match dirty.take() {
+            Some(dirty) => cmp::min(dirty.start, columns.start)..cmp::max(dirty.end, columns.end),
+            None => columns,
+        });
+    }
+
+    fn clamp_rect(&self, rect: Rect) -> (Range<u16>, Range<u16>) {
+        let end_y = cmp::min(rect.end_y(), self.height());
+        let end_x = cmp::min(rect.end_x(), self.width());
+        (
+            cmp::min(rect.y, end_y)..end_y,
+            cmp::min(rect.x, end_x)..end_x,
+        )
+    }
+
+    /// Marks a region as needing to be repainted by the next [`Window::redraw`].
+    ///
+    /// Pixels changed with [`Window::set_pixel`] are marked automatically.
+    pub fn mark_dirty(&mut self, rect: Rect) {
+        let (rows, columns) = self.clamp_rect(rect);
+        if rows.is_empty() || columns.is_empty() {
+            return;
+        }
+        for cell_y in rows.start / 2..rows.end.div_ceil(2) {
+            self.mark_cells_dirty(cell_y, columns.clone());
+        }
+    }
+
+    /// Presents the pixels changed since the last redraw, drawing them to the terminal.
+    ///
+    /// The pixels are double buffered: drawing only changes the back buffer, and the redraws copy
+    /// the changed cells to the front buffer, the one displayed. The terminal is thus repainted
+    /// from the last presented frame after a resize, never from a partially drawn one.
+    pub fn redraw(&mut self) -> Result<()> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("redraw").entered();
+        let now = Instant::now();
+        if let Some(last_redraw) = self.last_redraw {
+            self.frame_time = if self.frame_time.is_zero() {
+                now - last_redraw
+            } else {
+                self.frame_time.mul_f32(0.9) + (now - last_redraw).mul_f32(0.1)
+            };
+        }
+        self.last_redraw = Some(now);
+        self.apply_proxy_commands();
+        if let Some(budget) = self.latency_budget {
+            let reduced_colors = self.flush_time > budget;
+            if self.reduced_colors && !reduced_colors {
+                self.should_redraw_all = true;
+            }
+            self.reduced_colors = reduced_colors;
+            if reduced_colors
+                && self
+                    .last_present
+                    .is_some_and(|last| now - last < self.flush_time)
             {
-                queue!(
-                    stdout(),
-                    SetBackgroundColor(*background),
-                    Print(LOWER_HALF_BLOCK)
-                )?;
+                return Ok(());
+            }
+        }
+        self.last_present = Some(now);
+        let changes = self.swap_dirty_cells();
+        self.record_frame();
+        #[cfg(feature = "stream")]
+        if let Some(streamer) = &mut self.streamer {
+            streamer.send(&self.front);
+        }
+        if self.dump_frames {
+            return self.dump_frame();
+        }
+        self.queue_frame(changes)
+    }
+
+    /// Copies the dirty cells that differ from the front buffer to it, returning the changed
+    /// columns of each row of cells.
+    fn swap_dirty_cells(&mut self) -> Vec<Option<Range<u16>>> {
+        let mut changes = Vec::with_capacity(self.dirty_rows.len());
+        for cell_y in 0..self.dirty_rows.len() {
+            let rows = cell_y as u16 * 2..cmp::min(cell_y as u16 * 2 + 2, self.height());
+            let changed = |x: &u16| {
+                rows.clone()
+                    .any(|y| self.pixels.get_pixel(y, *x) != self.front.get_pixel(y, *x))
+            };
+            let columns = self.dirty_rows[cell_y].take().and_then(|columns| {
+                let start = columns.clone().find(changed)?;
+                let end = columns.rev().find(changed)? + 1;
+                Some(start..end)
+            });
+            if let Some(columns) = &columns {
+                self.copy_to_front(rows, columns.clone());
+            }
+            changes.push(columns);
+        }
+        changes
+    }
+
+    fn copy_to_front(&mut self, rows: Range<u16>, columns: Range<u16>) {
+        for y in rows {
+            for x in columns.clone() {
+                self.front.set_pixel(y, x, self.pixels.get_pixel(y, x));
+            }
+        }
+    }
+
+    /// Queues the `changes` of the front buffer, or all of it if it must be redrawn entirely.
+    fn queue_frame(&mut self, mut changes: Vec<Option<Range<u16>>>) -> Result<()> {
+        #[cfg(feature = "tracing")]
+        let span = tracing::debug_span!("encode").entered();
+        let all_cells = vec![Some(0..self.width()); self.dirty_rows.len()];
+        self.queue_begin_synchronized_update()?;
+        if self.should_redraw_all {
+            self.should_redraw_all = false;
+            self.stale_labels.clear();
+            // The terminal is cleared with the background set, for the letterbox bars and the
+            // cells around the window to have the clear color.
+            let mut background = self.clear_color;
+            if self.dim > 0. {
+                background = darken(background, self.dim);
+            }
+            let background = self.encoding().color(background);
+            queue!(self.terminal, SetColors(Color::Reset, background), ClearAll)?;
+            self.queue_border()?;
+            changes = all_cells.clone();
+        }
+        if let Some((color, end)) = self.flash {
+            if Instant::now() < end {
+                self.queue_flash(color)?;
+                #[cfg(feature = "tracing")]
+                drop(span);
+                return self.end_redraw();
+            }
+            self.flash = None;
+            changes = all_cells;
+        }
+        self.queue_changes(changes)?;
+        #[cfg(feature = "tracing")]
+        drop(span);
+        self.end_redraw()
+    }
+
+    /// Writes the whole window line by line, without moving the cursor, followed by an empty line.
+    fn dump_frame(&mut self) -> Result<()> {
+        let encoding = self.encoding();
+        queue_text_frame(&mut self.terminal, &self.front, encoding)?;
+        queue!(self.terminal, Print("\n"))?;
+        self.flush_output()
+    }
+
+    /// Presents a region of the window and redraws it to the terminal, whether it changed or not.
+    pub fn redraw_region(&mut self, rect: Rect) -> Result<()> {
+        let (rows, columns) = self.clamp_rect(rect);
+        self.copy_to_front(rows.clone(), columns.clone());
+        if self.dump_frames {
+            return self.dump_frame();
+        }
+        self.queue_begin_synchronized_update()?;
+        if !rows.is_empty() {
+            let mut colors = ColorsState::default();
+            for cell_y in rows.start / 2..rows.end.div_ceil(2) {
+                self.queue_cells(&mut colors, cell_y, columns.clone())?;
             }
         }
-        queue!(stdout(), SetColors(Colors::new(Color::Reset, Color::Reset)))?;
-        stdout().flush()?;
+        self.end_redraw()
+    }
+
+    /// Queues the terminal cells of the row `cell_y`, each cell containing two pixels.
+    fn queue_cells(
+        &mut self,
+        colors: &mut ColorsState,
+        cell_y: u16,
+        columns: Range<u16>,
+    ) -> Result<()> {
+        let encoding = self.encoding();
+        CellsLayout {
+            pixels: &self.front,
+            origin: self.origin,
+            terminal_size: self.terminal_size,
+            encoding,
+            dim: self.dim,
+            scale: self.scale,
+        }
+        .queue_cells(&mut self.terminal, colors, cell_y, columns)
+    }
+
+    /// Queues the `changes` of the front buffer, encoding large ones in parallel with rayon.
+    fn queue_changes(&mut self, changes: Vec<Option<Range<u16>>>) -> Result<()> {
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+
+            let cells: usize = changes.iter().flatten().map(ExactSizeIterator::len).sum();
+            if cells >= PARALLEL_ENCODING_CELLS {
+                let layout = CellsLayout {
+                    pixels: &self.front,
+                    origin: self.origin,
+                    terminal_size: self.terminal_size,
+                    encoding: self.encoding(),
+                    dim: self.dim,
+                    scale: self.scale,
+                };
+                let rows = changes
+                    .into_par_iter()
+                    .enumerate()
+                    .map(|(cell_y, columns)| {
+                        let mut output = Vec::new();
+                        if let Some(columns) = columns {
+                            let mut colors = ColorsState::default();
+                            layout.queue_cells(&mut output, &mut colors, cell_y as u16, columns)?;
+                        }
+                        Ok(output)
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                for row in rows {
+                    self.terminal.write_all(&row)?;
+                }
+                return Ok(());
+            }
+        }
+        let mut colors = ColorsState::default();
+        for (cell_y, columns) in changes.into_iter().enumerate() {
+            if let Some(columns) = columns {
+                self.queue_cells(&mut colors, cell_y as u16, columns)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn end_redraw(&mut self) -> Result<()> {
+        queue!(self.terminal, SetColors(Color::Reset, Color::Reset))?;
+        self.queue_pinned_labels()?;
+        #[cfg(feature = "log")]
+        if self.show_console {
+            self.queue_console()?;
+        }
+        self.queue_status_lines()?;
+        self.queue_labels()?;
+        if self.show_fps {
+            self.queue_fps()?;
+        }
+        if self.synchronized_output {
+            queue!(self.terminal, EndSynchronizedUpdate)?;
+        }
+        let start = Instant::now();
+        self.flush_output()?;
+        self.flush_time = self.flush_time.mul_f32(0.9) + start.elapsed().mul_f32(0.1);
+        Ok(())
+    }
+
+    /// Shows or hides an overlay with the frames per second and frame time, in the top left corner
+    /// of the terminal.
+    pub fn show_fps(&mut self, show: bool) {
+        if self.show_fps && !show {
+            self.should_redraw_all = true;
+        }
+        self.show_fps = show;
+    }
+
+    /// Rings the terminal bell.
+    pub fn bell(&mut self) -> Result<()> {
+        queue!(self.terminal, Print("\x07"))?;
+        self.flush_output()
+    }
+
+    /// Fills the window with `color` for `duration`, the pixels being left untouched.
+    ///
+    /// The flash is drawn by the redraws during that time, the pixels being drawn back by the
+    /// first one after it.
+    pub fn flash(&mut self, color: impl Into<Color>, duration: Duration) {
+        self.flash = Some((color.into(), Instant::now() + duration));
+    }
+
+    fn queue_flash(&mut self, color: Color) -> Result<()> {
+        let flash = Canvas::new(self.height(), self.width(), color);
+        let layout = CellsLayout {
+            pixels: &flash,
+            origin: self.origin,
+            terminal_size: self.terminal_size,
+            encoding: self.encoding(),
+            dim: self.dim,
+            scale: self.scale,
+        };
+        let mut colors = ColorsState::default();
+        for cell_y in 0..self.height().div_ceil(2) {
+            layout.queue_cells(&mut self.terminal, &mut colors, cell_y, 0..flash.width())?;
+        }
+        Ok(())
+    }
+
+    fn queue_fps(&mut self) -> Result<()> {
+        let frame_time = self.frame_time.as_secs_f32();
+        let fps = if frame_time > 0. { 1. / frame_time } else { 0. };
+        queue!(
+            self.terminal,
+            MoveTo(0, 0),
+            Print(format!("{:>5.0} FPS {:>6.1} ms", fps, frame_time * 1000.))
+        )?;
+        Ok(())
+    }
+
+    /// Shows or hides the console, displaying the last records of the [`logger::Logger`] over
+    /// the top of the terminal.
+    #[cfg(feature = "log")]
+    pub fn show_console(&mut self, show: bool) {
+        if self.show_console && !show {
+            self.should_redraw_all = true;
+        }
+        self.show_console = show;
+    }
+
+    /// Sets the key toggling the console during [`Window::poll_events`], `` ` `` by default.
+    #[cfg(feature = "log")]
+    pub fn set_console_key(&mut self, key: Option<Key>) {
+        self.console_key = key;
+    }
+
+    /// Sets the number of records displayed by the console, 10 by default.
+    #[cfg(feature = "log")]
+    pub fn set_console_lines(&mut self, lines: u16) {
+        if lines < self.console_lines {
+            self.should_redraw_all = true;
+        }
+        self.console_lines = lines;
+    }
+
+    #[cfg(feature = "log")]
+    fn queue_console(&mut self) -> Result<()> {
+        let width = self.terminal_size.x as usize;
+        let colors = self.overlay_colors();
+        queue!(self.terminal, colors)?;
+        for (y, record) in
+            (0..self.terminal_size.y).zip(logger::recent_entries(self.console_lines.into()))
+        {
+            queue!(
+                self.terminal,
+                MoveTo(0, y),
+                Print(format!("{record:<width$.width$}"))
+            )?;
+        }
+        queue!(self.terminal, SetColors(Color::Reset, Color::Reset))?;
         Ok(())
     }
 
-    fn redraw_border(&self, should_flush: bool) -> Result<()> {
+    fn flush_output(&mut self) -> Result<()> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("flush").entered();
+        self.terminal.flush()?;
+        Ok(())
+    }
+
+    fn queue_border(&mut self) -> Result<()> {
+        let (top, side, bottom) = if self.legacy_console {
+            ("-", "|", "-")
+        } else {
+            (LOWER_HALF_BLOCK, FULL_BLOCK, UPPER_HALF_BLOCK)
+        };
+        let start_x = cmp::max(self.origin.x - 1, 0) as u16;
+        let end_x = self.end_x();
+        let end_y = self.end_y();
+        let horizontal_length = cmp::min(self.scaled_width() + 2, self.terminal_size.x).into();
         if self.origin.y > 0 {
             queue!(
-                stdout(),
-                MoveTo(
-                    cmp::max(self.origin.x - 1, 0) as u16,
-                    (self.origin.y - 1) as u16
-                ),
-                Print(
-                    LOWER_HALF_BLOCK
-                        .repeat(cmp::min(self.width() + 2, self.terminal_size.x).into())
-                )
+                self.terminal,
+                MoveTo(start_x, (self.origin.y - 1) as u16),
+                Print(top.repeat(horizontal_length))
             )?;
         }
-        let range = cmp::max(self.origin.y, 0) as u16..cmp::min(self.end_y(), self.terminal_size.y);
+        let range = cmp::max(self.origin.y, 0) as u16..cmp::min(end_y, self.terminal_size.y);
         if self.origin.x > 0 {
             for y in range.clone() {
                 queue!(
-                    stdout(),
+                    self.terminal,
                     MoveTo((self.origin.x - 1) as u16, y),
-                    Print(FULL_BLOCK)
+                    Print(side)
                 )?;
             }
         }
-        if self.end_x() < self.terminal_size.x {
+        if end_x < self.terminal_size.x {
             for y in range {
-                queue!(stdout(), MoveTo(self.end_x(), y), Print(FULL_BLOCK))?;
+                queue!(self.terminal, MoveTo(end_x, y), Print(side))?;
             }
         }
-        if self.height() % 2 == 0 && self.end_y() < self.terminal_size.y {
+        if self.scaled_height().is_multiple_of(2) && end_y < self.terminal_size.y {
             queue!(
-                stdout(),
-                MoveTo(cmp::max(self.origin.x - 1, 0) as u16, self.end_y()),
-                Print(
-                    UPPER_HALF_BLOCK
-                        .repeat(cmp::min(self.width() + 2, self.terminal_size.x).into())
-                )
+                self.terminal,
+                MoveTo(start_x, end_y),
+                Print(bottom.repeat(horizontal_length))
             )?;
         }
-        if should_flush {
-            stdout().flush()?;
-        }
         Ok(())
     }
 
-    fn redraw_all(&self) -> Result<()> {
-        queue!(stdout(), Clear(ClearType::All))?;
-        self.redraw_border(false)?;
-        self.redraw()?;
-        Ok(())
+    /// Redraws the front buffer entirely, with the border.
+    fn redraw_all(&mut self) -> Result<()> {
+        // The dumped frames are only written by the redraws, without terminal control sequences.
+        if self.dump_frames {
+            return Ok(());
+        }
+        self.should_redraw_all = true;
+        self.queue_frame(Vec::new())
     }
 
-    /// Clears events and polls for newer events.
+    /// Clears events and polls for newer events, then runs the callbacks registered with
+    /// [`Window::on_event`].
     pub fn poll_events(&mut self) -> Result<()> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("poll_events").entered();
         self.last_events.clear();
-        while event::poll(Duration::from_secs(0))? {
-            self.last_events.push(event::read()?);
-            if let Resize(columns, rows) = self.last_events.last().unwrap() {
-                self.terminal_size.x = *columns;
-                self.terminal_size.y = *rows;
-                self.calculate_origin();
-                self.redraw_all()?;
+        while let Some(event) = self.terminal.backend.poll_event()? {
+            self.push_event(event, false)?;
+        }
+        #[cfg(feature = "remote")]
+        if let Some(remote) = &mut self.remote {
+            let mut events = Vec::new();
+            remote.receive(&mut events);
+            for event in events {
+                self.push_event(event, true)?;
+            }
+        }
+        self.update_sequences();
+        self.update_timers();
+        self.update_debugger()?;
+        self.update_screenshot();
+        #[cfg(feature = "log")]
+        if let Some(key) = self.console_key {
+            if self.get_key(key) {
+                self.show_console(!self.show_console);
             }
         }
+        self.run_callbacks();
+        Ok(())
+    }
+
+    /// Adds a polled event to the last events, unless it is debounced, tracking the mouse and
+    /// the terminal size.
+    ///
+    /// The resizes injected by a remote controller do not resize the actual terminal, so they
+    /// are only added to the last events.
+    fn push_event(&mut self, event: Event, remote: bool) -> Result<()> {
+        if !self.debounce.accept(&event, Instant::now()) {
+            return Ok(());
+        }
+        if let Mouse(mouse_event) = event {
+            self.mouse_cell = Some((mouse_event.column, mouse_event.row));
+        }
+        let resize = match event {
+            Resize(columns, rows) if !remote => Some((columns, rows)),
+            _ => None,
+        };
+        self.last_events.push(event);
+        if let Some((columns, rows)) = resize {
+            self.terminal_size.x = columns;
+            self.terminal_size.y = rows;
+            self.calculate_origin();
+            self.redraw_all()?;
+        }
+        Ok(())
+    }
+
+    /// Enables or disables the reporting of mouse events by [`Window::poll_events`], disabled by
+    /// default.
+    ///
+    /// While enabled, the terminal text selection usually requires holding Shift.
+    pub fn set_mouse_capture(&mut self, enabled: bool) -> Result<()> {
+        self.terminal.flush()?;
+        self.terminal.backend.set_mouse_capture(enabled)?;
+        self.mouse_capture = enabled;
         Ok(())
     }
 
+    /// Checks if the mouse events are reported, see [`Window::set_mouse_capture`].
+    pub fn mouse_capture(&self) -> bool {
+        self.mouse_capture
+    }
+
+    /// Gets the `y` and `x` coordinates of the pixel under the mouse, as of its last reported
+    /// event, see [`Window::set_mouse_capture`].
+    ///
+    /// A terminal cell containing two pixels, the upper one is returned. Returns `None` if the
+    /// mouse is outside of the window.
+    pub fn mouse_position(&self) -> Option<(u16, u16)> {
+        let (column, row) = self.mouse_cell?;
+        self.cell_to_pixel(column, row)
+    }
+
+    /// Gets the upper pixel of a terminal cell, `None` if it is outside of the window.
+    fn cell_to_pixel(&self, column: u16, row: u16) -> Option<(u16, u16)> {
+        let cell_y = row as i32 - self.origin.y as i32;
+        let x = column as i32 - self.origin.x as i32;
+        let y = cell_y * 2;
+        if y < 0 || y >= self.scaled_height().into() || x < 0 || x >= self.scaled_width().into() {
+            return None;
+        }
+        Some((y as u16 / self.scale, x as u16 / self.scale))
+    }
+
+    /// Gets the coordinates and color of the pixel under the mouse, see
+    /// [`Window::mouse_position`].
+    pub fn pixel_under_mouse(&self) -> Option<(u16, u16, Color)> {
+        let (y, x) = self.mouse_position()?;
+        Some((y, x, self.get_pixel(y, x)))
+    }
+
+    /// Enables or disables case insensitive matching of characters by [`Window::get_key`],
+    /// disabled by default.
+    pub fn set_case_insensitive_keys(&mut self, enabled: bool) {
+        self.case_insensitive_keys = enabled;
+    }
+
     /// Returns `true` if `key` was read during the last call to [`Window::poll_events`].
-    pub fn get_key(&mut self, key: KeyCode) -> bool {
-        self.last_events.iter().any(|event| {
-            if let Key(key_event) = *event {
-                if key_event.code == key {
-                    return true;
-                }
-                if let (KeyCode::Char(char), KeyCode::Char(event_char)) = (key, key_event.code) {
-                    if char.to_lowercase().to_string() == event_char.to_lowercase().to_string() {
-                        return true;
-                    }
-                }
+    ///
+    /// Characters match regardless of their case if enabled with
+    /// [`Window::set_case_insensitive_keys`].
+    pub fn get_key(&mut self, key: impl Into<Key>) -> bool {
+        let key = key.into();
+        if !self.case_insensitive_keys {
+            return self.get_key_exact(key);
+        }
+        self.last_events.iter().any(
+            |event| matches!(event, Event::Key(key_event) if self.keys_match(key, key_event.code)),
+        )
+    }
+
+    /// Checks if `code` is `key`, following the case sensitivity setting.
+    fn keys_match(&self, key: Key, code: Key) -> bool {
+        if code == key {
+            return true;
+        }
+        match (key, code) {
+            (Key::Char(char), Key::Char(event_char)) if self.case_insensitive_keys => {
+                char.to_lowercase().to_string() == event_char.to_lowercase().to_string()
             }
-            false
-        })
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if exactly `key` was read during the last call to [`Window::poll_events`],
+    /// whatever the case sensitivity setting.
+    pub fn get_key_exact(&self, key: impl Into<Key>) -> bool {
+        let key = key.into();
+        self.last_events
+            .iter()
+            .any(|event| matches!(event, Event::Key(key_event) if key_event.code == key))
     }
 
-    /// Returns `true` if `modifiers` was read during the last call to [`Window::poll_events`].
-    pub fn get_modifiers(&mut self, modifiers: KeyModifiers) -> bool {
+    /// Returns `true` if a key was read with exactly `modifiers` during the last call to
+    /// [`Window::poll_events`], e.g. `SHIFT` does not match Ctrl+Shift, see
+    /// [`Window::modifiers_contains`].
+    pub fn get_modifiers(&mut self, modifiers: Modifiers) -> bool {
         self.last_events.iter().any(|event| {
-            if let Key(key_event) = *event {
+            if let Event::Key(key_event) = *event {
                 if key_event.modifiers == modifiers {
                     return true;
                 }
@@ -339,11 +1547,71 @@ impl Window {
             false
         })
     }
+
+    /// Returns `true` if a key was read with at least `modifiers` during the last call to
+    /// [`Window::poll_events`], e.g. `SHIFT` matches Ctrl+Shift.
+    pub fn modifiers_contains(&self, modifiers: Modifiers) -> bool {
+        self.last_events
+            .iter()
+            .any(|event| matches!(event, Event::Key(key_event) if key_event.modifiers.contains(modifiers)))
+    }
 }
 
 impl Drop for Window {
     fn drop(&mut self) {
-        let _ = execute!(stdout(), LeaveAlternateScreen, EnableLineWrap, Show);
-        let _ = terminal::disable_raw_mode();
+        let _ = self.terminal.backend.leave();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ansi::{queue, ClearAll, SetColors};
+    use crate::backend::TestBackend;
+    use crate::{Color, Rect, Window};
+
+    fn contains(output: &[u8], bytes: &[u8]) -> bool {
+        output.windows(bytes.len()).any(|window| window == bytes)
+    }
+
+    #[test]
+    fn buffers_start_with_the_clear_color() {
+        let window = Window::with_backend(3, 4, TestBackend::default()).unwrap();
+        assert!(window
+            .pixels()
+            .iter()
+            .all(|&color| color == window.clear_color()));
+        assert_eq!(window.front.pixels(), window.pixels());
+    }
+
+    #[test]
+    fn terminal_is_cleared_with_the_clear_color() {
+        let backend = TestBackend::default();
+        let mut window = Window::with_backend(4, 4, backend.clone()).unwrap();
+        let mut clear = Vec::new();
+        queue!(clear, SetColors(Color::Reset, Color::Blue), ClearAll).unwrap();
+        window.set_clear_color(Color::Blue);
+        backend.take_output();
+        window.redraw().unwrap();
+        assert!(contains(&backend.take_output(), &clear));
+
+        // Setting the same color again does not redraw the whole terminal.
+        window.set_clear_color(Color::Blue);
+        window.redraw().unwrap();
+        assert!(!contains(&backend.take_output(), b"\x1b[2J"));
+    }
+
+    #[test]
+    fn dumped_frames_have_no_terminal_control_sequences() {
+        let backend = TestBackend::default();
+        let mut window = Window::create(2, 2, Box::new(backend.clone()), false, true).unwrap();
+        assert!(backend.take_output().is_empty());
+        window.pixels_mut().fill(Color::Rgb { r: 4, g: 5, b: 6 });
+        window.set_pixel(0, 0, Color::Rgb { r: 1, g: 2, b: 3 });
+        window.redraw().unwrap();
+        let frame =
+            "\x1b[38;2;1;2;3m\x1b[48;2;4;5;6m\u{2580}\x1b[38;2;4;5;6m\u{2580}\x1b[39m\x1b[49m\n\n";
+        assert_eq!(String::from_utf8(backend.take_output()).unwrap(), frame);
+        window.redraw_region(Rect::new(0, 0, 1, 1)).unwrap();
+        assert_eq!(String::from_utf8(backend.take_output()).unwrap(), frame);
     }
 }
@@ -0,0 +1,92 @@
+//! Logger capturing [`log`] records, since printing is unusable under the alternate screen.
+//!
+//! The records are kept in a ring buffer, read with [`recent_entries`] or displayed by the window
+//! console, and can also be appended to a file.
+
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+use log::{LevelFilter, Log, Metadata, Record, SetLoggerError};
+
+const CAPACITY: usize = 256;
+
+static RECORDS: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// Logger keeping the last records in memory.
+///
+/// The records are shown by the window console, see [`crate::Window::show_console`].
+#[derive(Debug)]
+pub struct Logger {
+    level: LevelFilter,
+    file: Option<Mutex<File>>,
+}
+
+impl Logger {
+    /// Creates a logger keeping the records up to `level`.
+    pub fn new(level: LevelFilter) -> Self {
+        Logger { level, file: None }
+    }
+
+    /// Also appends the records to the file at `path`, creating it if needed.
+    pub fn with_file(mut self, path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        self.file = Some(Mutex::new(file));
+        Ok(self)
+    }
+
+    /// Installs the logger as the global [`log`] logger.
+    pub fn install(self) -> Result<(), SetLoggerError> {
+        let level = self.level;
+        log::set_boxed_logger(Box::new(self))?;
+        log::set_max_level(level);
+        Ok(())
+    }
+
+    /// Creates a logger keeping the records up to `level` and installs it.
+    pub fn init(level: LevelFilter) -> Result<(), SetLoggerError> {
+        Logger::new(level).install()
+    }
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let entry = format!("{:<5} {}", record.level(), record.args());
+        if let Some(Ok(mut file)) = self.file.as_ref().map(Mutex::lock) {
+            let _ = writeln!(file, "{entry}");
+        }
+        if let Ok(mut records) = RECORDS.lock() {
+            if records.len() == CAPACITY {
+                records.pop_front();
+            }
+            records.push_back(entry);
+        }
+    }
+
+    fn flush(&self) {
+        if let Some(Ok(mut file)) = self.file.as_ref().map(Mutex::lock) {
+            let _ = file.flush();
+        }
+    }
+}
+
+/// Returns up to `count` of the most recent entries, oldest first.
+pub fn recent_entries(count: usize) -> Vec<String> {
+    match RECORDS.lock() {
+        Ok(records) => records
+            .iter()
+            .skip(records.len().saturating_sub(count))
+            .cloned()
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
@@ -0,0 +1,92 @@
+#[cfg(feature = "nalgebra")]
+use na::DMatrix;
+#[cfg(feature = "ndarray")]
+use ndarray::{Array2, ArrayView2};
+
+#[cfg(feature = "nalgebra")]
+use crate::color::Colormap;
+use crate::{Color, Window};
+
+impl Window {
+    /// Copies the pixels into a matrix, indexed by `(y, x)`.
+    #[cfg(feature = "nalgebra")]
+    pub fn matrix(&self) -> DMatrix<Color> {
+        DMatrix::from_row_slice(self.height().into(), self.width().into(), self.pixels())
+    }
+
+    /// Sets all pixels from a matrix indexed by `(y, x)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix dimensions are not the window dimensions.
+    #[cfg(feature = "nalgebra")]
+    pub fn set_from_matrix(&mut self, matrix: &DMatrix<Color>) {
+        assert_eq!(
+            matrix.shape(),
+            (self.height().into(), self.width().into()),
+            "matrix dimensions must be the window dimensions"
+        );
+        self.set_from_fn(|y, x| matrix[(y.into(), x.into())]);
+    }
+
+    /// Sets all pixels from the values of a matrix indexed by `(y, x)`, stretched to the window.
+    ///
+    /// The values are normalized between their minimum and maximum before being mapped by
+    /// `colormap`, the NaN ones getting the color of the minimum.
+    #[cfg(feature = "nalgebra")]
+    pub fn draw_heatmap(&mut self, matrix: &DMatrix<f64>, colormap: Colormap) {
+        let (rows, columns) = matrix.shape();
+        if rows == 0 || columns == 0 {
+            return;
+        }
+        let (min, max) = matrix
+            .iter()
+            .filter(|value| !value.is_nan())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &value| {
+                (min.min(value), max.max(value))
+            });
+        let span = if max > min { max - min } else { 1. };
+        let (height, width) = (self.height() as usize, self.width() as usize);
+        self.set_from_fn(|y, x| {
+            let value = matrix[(y as usize * rows / height, x as usize * columns / width)];
+            colormap.color((value - min) / span)
+        });
+    }
+
+    /// Copies the pixels into an array, indexed by `[y, x]`.
+    #[cfg(feature = "ndarray")]
+    pub fn to_array(&self) -> Array2<Color> {
+        Array2::from_shape_vec(
+            (self.height().into(), self.width().into()),
+            self.pixels().to_vec(),
+        )
+        .expect("pixels length is height * width")
+    }
+
+    /// Sets all pixels from an array indexed by `[y, x]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the array dimensions are not the window dimensions.
+    #[cfg(feature = "ndarray")]
+    pub fn set_from_array(&mut self, array: ArrayView2<Color>) {
+        assert_eq!(
+            array.dim(),
+            (self.height().into(), self.width().into()),
+            "array dimensions must be the window dimensions"
+        );
+        self.set_from_fn(|y, x| array[[y.into(), x.into()]]);
+    }
+
+    /// Sets the pixels that differ from `f(y, x)`, so that only they are marked as dirty.
+    pub(crate) fn set_from_fn(&mut self, f: impl Fn(u16, u16) -> Color) {
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let color = f(y, x);
+                if self.get_pixel(y, x) != color {
+                    self.set_pixel(y, x, color);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! Triangle meshes loaded from Wavefront OBJ files, drawn with flat shading.
+//!
+//! ```no_run
+//! use winterm::Color;
+//! use winterm::mesh::{self, Mesh};
+//! use winterm::wireframe::Camera;
+//! use winterm::Window;
+//!
+//! let mesh = Mesh::from_obj("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n")?;
+//! let camera = Camera::new([0.5, 0.5, 2.].into(), [0.5, 0.5, 0.].into());
+//! let mut window = Window::new(40, 80)?;
+//! mesh::draw_mesh(&mut window, &camera, &mesh, Color::Grey, &[-1., -1., -1.].into());
+//! window.redraw()?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::Path;
+
+use na::{Point3, Vector3};
+
+use crate::wireframe::Camera;
+use crate::{color, Draw};
+use crate::{
+    raster::{Shading, Vertex},
+    Color,
+};
+
+/// Part of the light reaching the faces turned away from it.
+const AMBIENT_LIGHT: f32 = 0.2;
+
+/// Triangle mesh, its faces being counter-clockwise when seen from the front.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Mesh {
+    /// Positions of the vertices.
+    pub vertices: Vec<Point3<f32>>,
+    /// Triangles, as indices into the vertices.
+    pub faces: Vec<[usize; 3]>,
+}
+
+impl Mesh {
+    /// Parses the vertices and faces of an OBJ file, the other statements being ignored.
+    ///
+    /// Faces with more than three vertices are split into triangles.
+    pub fn from_obj(text: &str) -> io::Result<Self> {
+        let invalid = |line: usize, message: &str| {
+            io::Error::new(ErrorKind::InvalidData, format!("line {line}: {message}"))
+        };
+        let mut mesh = Mesh::default();
+        for (i, line) in text.lines().enumerate() {
+            let mut words = line.split_whitespace();
+            match words.next() {
+                Some("v") => {
+                    let coordinates = words
+                        .take(3)
+                        .map(str::parse)
+                        .collect::<Result<Vec<f32>, _>>()
+                        .map_err(|_| invalid(i + 1, "invalid vertex coordinate"))?;
+                    let [x, y, z] = coordinates[..] else {
+                        return Err(invalid(i + 1, "vertex with less than 3 coordinates"));
+                    };
+                    mesh.vertices.push(Point3::new(x, y, z));
+                }
+                Some("f") => {
+                    let indices = words
+                        .map(|word| {
+                            let index: isize = word.split('/').next()?.parse().ok()?;
+                            match index {
+                                1.. => Some(index as usize - 1),
+                                ..=-1 => mesh.vertices.len().checked_add_signed(index),
+                                0 => None,
+                            }
+                            .filter(|&index| index < mesh.vertices.len())
+                        })
+                        .collect::<Option<Vec<usize>>>()
+                        .ok_or_else(|| invalid(i + 1, "invalid face vertex index"))?;
+                    if indices.len() < 3 {
+                        return Err(invalid(i + 1, "face with less than 3 vertices"));
+                    }
+                    for j in 1..indices.len() - 1 {
+                        mesh.faces.push([indices[0], indices[j], indices[j + 1]]);
+                    }
+                }
+                _ => (),
+            }
+        }
+        Ok(mesh)
+    }
+
+    /// Loads an OBJ file, see [`Mesh::from_obj`].
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_obj(&fs::read_to_string(path)?)
+    }
+}
+
+/// Draws the faces of `mesh` turned towards the camera with `color`, lit by a directional light
+/// going towards `light`.
+///
+/// The faces are drawn from the farthest to the nearest, and are skipped when a vertex is nearer
+/// than the near plane of the camera.
+pub fn draw_mesh<D: Draw + ?Sized>(
+    target: &mut D,
+    camera: &Camera,
+    mesh: &Mesh,
+    color: Color,
+    light: &Vector3<f32>,
+) {
+    let light = light.normalize();
+    let view = camera.view();
+    let rgb = color::to_rgb(color).map(f32::from);
+    let mut faces: Vec<(f32, Color, [Point3<f32>; 3])> = Vec::new();
+    for face in &mesh.faces {
+        let [a, b, c] = face.map(|index| mesh.vertices[index]);
+        let normal = (b - a).cross(&(c - a)).normalize();
+        if normal.dot(&(camera.position - a)) <= 0. {
+            continue;
+        }
+        let points = [a, b, c].map(|point| view * point);
+        if points.iter().any(|point| -point.z < camera.near) {
+            continue;
+        }
+        let diffuse = normal.dot(&-light).max(0.);
+        let intensity = AMBIENT_LIGHT + (1. - AMBIENT_LIGHT) * diffuse;
+        let shade = color::from_rgb(rgb.map(|channel| (channel * intensity).round() as u8));
+        let depth = points.iter().map(|point| -point.z).sum::<f32>() / 3.;
+        faces.push((depth, shade, points));
+    }
+    faces.sort_by(|(a, ..), (b, ..)| b.total_cmp(a));
+    let (height, width) = (target.height(), target.width());
+    for (_, shade, points) in faces {
+        let vertices = points.map(|point| {
+            let (y, x) = camera.project_view(&point, height, width);
+            Vertex::new(y, x)
+        });
+        target.fill_triangle(vertices, Shading::Flat(shade));
+    }
+}
@@ -0,0 +1,120 @@
+//! Seeded gradient noise, for terrain, clouds and other procedural effects.
+//!
+//! ```
+//! use winterm::noise::Perlin;
+//!
+//! let perlin = Perlin::new(42);
+//! let height = perlin.fractal_2d(1.5, 2.5, 4);
+//! assert!((-1. ..=1.).contains(&height));
+//! ```
+
+/// Perlin noise generator, the same seed always giving the same noise.
+///
+/// The noise is 0 at integer coordinates and varies smoothly between -1 and 1 in between.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Perlin {
+    permutation: Vec<u8>,
+}
+
+impl Perlin {
+    /// Creates a generator from a seed.
+    pub fn new(seed: u64) -> Self {
+        let mut state = seed;
+        let mut permutation: Vec<u8> = (0..=u8::MAX).collect();
+        for i in (1..permutation.len()).rev() {
+            let j = (split_mix(&mut state) % (i as u64 + 1)) as usize;
+            permutation.swap(i, j);
+        }
+        permutation.extend_from_within(..);
+        Perlin { permutation }
+    }
+
+    fn hash(&self, x: i32) -> usize {
+        self.permutation[(x & 0xFF) as usize] as usize
+    }
+
+    /// Gets the noise at `x`.
+    pub fn noise_1d(&self, x: f32) -> f32 {
+        let cell = x.floor();
+        let x = x - cell;
+        let cell = cell as i32;
+        let gradient = |cell: i32, x: f32| {
+            if self.hash(cell) & 1 == 0 {
+                x
+            } else {
+                -x
+            }
+        };
+        // A 1D gradient has a slope of at most 1, so the noise stays between -0.5 and 0.5.
+        2. * lerp(gradient(cell, x), gradient(cell + 1, x - 1.), fade(x))
+    }
+
+    /// Gets the noise at `x` and `y`.
+    pub fn noise_2d(&self, x: f32, y: f32) -> f32 {
+        let (cell_x, cell_y) = (x.floor(), y.floor());
+        let (x, y) = (x - cell_x, y - cell_y);
+        let (cell_x, cell_y) = (cell_x as i32, cell_y as i32);
+        let gradient = |offset_x: i32, offset_y: i32| {
+            let hash = self.hash(self.hash(cell_x + offset_x) as i32 + cell_y + offset_y);
+            let (x, y) = (x - offset_x as f32, y - offset_y as f32);
+            match hash & 3 {
+                0 => x + y,
+                1 => -x + y,
+                2 => x - y,
+                _ => -x - y,
+            }
+        };
+        let (fade_x, fade_y) = (fade(x), fade(y));
+        let top = lerp(gradient(0, 0), gradient(1, 0), fade_x);
+        let bottom = lerp(gradient(0, 1), gradient(1, 1), fade_x);
+        lerp(top, bottom, fade_y).clamp(-1., 1.)
+    }
+
+    /// Sums `octaves` layers of [`Perlin::noise_1d`], each with twice the frequency and half
+    /// the amplitude of the previous one, normalized between -1 and 1.
+    pub fn fractal_1d(&self, x: f32, octaves: u32) -> f32 {
+        fractal(octaves, |frequency| self.noise_1d(x * frequency))
+    }
+
+    /// Sums `octaves` layers of [`Perlin::noise_2d`], each with twice the frequency and half
+    /// the amplitude of the previous one, normalized between -1 and 1.
+    pub fn fractal_2d(&self, x: f32, y: f32, octaves: u32) -> f32 {
+        fractal(octaves, |frequency| {
+            self.noise_2d(x * frequency, y * frequency)
+        })
+    }
+}
+
+fn fractal(octaves: u32, noise: impl Fn(f32) -> f32) -> f32 {
+    let (mut sum, mut total_amplitude) = (0., 0.);
+    let (mut frequency, mut amplitude) = (1., 1.);
+    for _ in 0..octaves {
+        sum += noise(frequency) * amplitude;
+        total_amplitude += amplitude;
+        frequency *= 2.;
+        amplitude /= 2.;
+    }
+    if total_amplitude > 0. {
+        sum / total_amplitude
+    } else {
+        0.
+    }
+}
+
+/// Smoothstep with null first and second derivatives at 0 and 1.
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6. - 15.) + 10.)
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+/// Advances the SplitMix64 generator.
+fn split_mix(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
@@ -0,0 +1,179 @@
+use std::io::Result;
+use std::ops::Range;
+
+use crate::ansi::{queue, MoveTo, Print, SetColors};
+use crate::{darken, Color, ColorsState, Window};
+
+/// Identifier of a pinned label, see [`Window::pin_label`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PinnedLabelId(usize);
+
+#[derive(Debug, Clone)]
+struct PinnedLabel {
+    y: u16,
+    x: u16,
+    text: String,
+    color: Color,
+}
+
+/// Labels pinned over the pixels, and the cells they no longer cover.
+#[derive(Debug, Default)]
+pub(crate) struct PinnedLabels {
+    labels: Vec<Option<PinnedLabel>>,
+    /// Columns of the rows of cells to repaint from the pixels.
+    stale: Vec<(u16, Range<u16>)>,
+}
+
+impl Window {
+    /// Pins `text` over the pixels, in terminal characters, from the cell containing the pixel at
+    /// `y` and `x`, e.g. to name an entity.
+    ///
+    /// The label is drawn after every redraw in `color` over the color of the pixels beneath it,
+    /// the characters outside of the window being cut.
+    ///
+    /// ```
+    /// use winterm::Color;
+    /// # use winterm::Window;
+    ///
+    /// # let mut window = Window::new(9, 16)?;
+    /// let label = window.pin_label(4, 2, "Bob", Color::White);
+    /// window.redraw()?;
+    /// window.move_pinned_label(label, 6, 3);
+    /// window.redraw()?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn pin_label(&mut self, y: u16, x: u16, text: &str, color: Color) -> PinnedLabelId {
+        self.pinned_labels.labels.push(Some(PinnedLabel {
+            y,
+            x,
+            text: text.lines().next().unwrap_or_default().to_owned(),
+            color,
+        }));
+        PinnedLabelId(self.pinned_labels.labels.len() - 1)
+    }
+
+    /// Moves a pinned label to the cell containing the pixel at `y` and `x`.
+    pub fn move_pinned_label(&mut self, id: PinnedLabelId, y: u16, x: u16) {
+        if let Some(label) = self.take_pinned_label(id) {
+            self.pinned_labels.labels[id.0] = Some(PinnedLabel { y, x, ..label });
+        }
+    }
+
+    /// Replaces the text of a pinned label.
+    pub fn set_pinned_label_text(&mut self, id: PinnedLabelId, text: &str) {
+        if let Some(label) = self.take_pinned_label(id) {
+            let text = text.lines().next().unwrap_or_default().to_owned();
+            self.pinned_labels.labels[id.0] = Some(PinnedLabel { text, ..label });
+        }
+    }
+
+    /// Removes a pinned label, the pixels beneath it being drawn back by the next redraw.
+    pub fn unpin_label(&mut self, id: PinnedLabelId) {
+        self.take_pinned_label(id);
+    }
+
+    /// Removes a pinned label, marking its cells to be repainted.
+    fn take_pinned_label(&mut self, id: PinnedLabelId) -> Option<PinnedLabel> {
+        let label = self.pinned_labels.labels.get_mut(id.0)?.take()?;
+        if let Some(columns) = self.pinned_label_columns(&label) {
+            self.pinned_labels.stale.push((label.y / 2, columns));
+        }
+        Some(label)
+    }
+
+    /// Gets the columns of pixels covered by a label, `None` if it is outside of the window.
+    fn pinned_label_columns(&self, label: &PinnedLabel) -> Option<Range<u16>> {
+        if label.y >= self.height() || label.x >= self.width() {
+            return None;
+        }
+        let length = label.text.chars().count().min(u16::MAX.into()) as u16;
+        Some(label.x..label.x.saturating_add(length).min(self.width()))
+    }
+
+    pub(crate) fn queue_pinned_labels(&mut self) -> Result<()> {
+        let stale = std::mem::take(&mut self.pinned_labels.stale);
+        let mut colors = ColorsState::default();
+        for (cell_y, columns) in stale {
+            if usize::from(cell_y) < self.dirty_rows.len() {
+                let columns = columns.start.min(self.width())..columns.end.min(self.width());
+                self.queue_cells(&mut colors, cell_y, columns)?;
+            }
+        }
+        let labels = std::mem::take(&mut self.pinned_labels.labels);
+        let result = labels
+            .iter()
+            .flatten()
+            .try_for_each(|label| self.queue_pinned_label(label));
+        self.pinned_labels.labels = labels;
+        result?;
+        queue!(self.terminal, SetColors(Color::Reset, Color::Reset))?;
+        Ok(())
+    }
+
+    fn queue_pinned_label(&mut self, label: &PinnedLabel) -> Result<()> {
+        let Some(columns) = self.pinned_label_columns(label) else {
+            return Ok(());
+        };
+        // The label starts at the cell containing the top left terminal pixel of its pixel.
+        let scale = self.scale;
+        let upper_y = label.y * scale / 2 * 2;
+        let row = i32::from(self.origin.y) + i32::from(upper_y / 2);
+        if row < 0 || row >= i32::from(self.terminal_size.y) {
+            return Ok(());
+        }
+        let columns = columns.start * scale..columns.end * scale;
+        for (x, char) in columns.zip(label.text.chars()) {
+            let column = i32::from(self.origin.x) + i32::from(x);
+            if column < 0 || column >= i32::from(self.terminal_size.x) {
+                continue;
+            }
+            // The background matches the pixel drawn by `queue_cells`, dimmed and encoded.
+            let mut background = self.front.get_pixel(upper_y / scale, x / scale);
+            if self.dim > 0. {
+                background = darken(background, self.dim);
+            }
+            let encoding = self.encoding();
+            queue!(
+                self.terminal,
+                MoveTo(column as u16, row as u16),
+                SetColors(encoding.color(label.color), encoding.color(background)),
+                Print(char)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Color;
+
+    use crate::backend::TestBackend;
+    use crate::Window;
+
+    fn contains(output: &[u8], bytes: &[u8]) -> bool {
+        output.windows(bytes.len()).any(|window| window == bytes)
+    }
+
+    #[test]
+    fn label_background_is_dimmed_like_the_pixels() {
+        let backend = TestBackend::default();
+        let mut window = Window::with_backend(2, 4, backend.clone()).unwrap();
+        window.set_pixel(
+            0,
+            0,
+            Color::Rgb {
+                r: 200,
+                g: 100,
+                b: 50,
+            },
+        );
+        window.pin_label(0, 0, "A", Color::White);
+        window.set_dim(0.5);
+        backend.take_output();
+        window.redraw().unwrap();
+        let output = backend.take_output();
+        assert!(contains(&output, b"48;2;100;50;25mA"));
+        assert!(!contains(&output, b"48;2;200;100;50m"));
+    }
+}
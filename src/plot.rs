@@ -0,0 +1,170 @@
+//! Plots mapping data ranges to the pixels of a rectangle, with axes and series of points.
+//!
+//! ```no_run
+//! use winterm::Color;
+//! use winterm::plot::Plot;
+//! use winterm::{Rect, Window};
+//!
+//! let mut window = Window::new(40, 80)?;
+//! let points: Vec<_> = (0..100).map(|i| (i as f64 / 10., (i as f64 / 10.).sin())).collect();
+//! let plot = Plot::fit(Rect::new(0, 0, 40, 80), &points);
+//! plot.draw_axes(&mut window);
+//! plot.line(&mut window, &points, Color::Green);
+//! window.redraw()?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::ops::RangeInclusive;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Color, Draw, Rect};
+
+/// Length of the ticks, in pixels.
+const TICK_LENGTH: i32 = 2;
+
+/// Area of the pixels showing data, the `y` axis going up.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Plot {
+    rect: Rect,
+    x_range: RangeInclusive<f64>,
+    y_range: RangeInclusive<f64>,
+    axes_color: Color,
+    ticks: u16,
+}
+
+impl Plot {
+    /// Creates a plot showing `x_range` and `y_range` in `rect`, with grey axes and 5 ticks each.
+    pub fn new(rect: Rect, x_range: RangeInclusive<f64>, y_range: RangeInclusive<f64>) -> Self {
+        Plot {
+            rect,
+            x_range,
+            y_range,
+            axes_color: Color::Grey,
+            ticks: 5,
+        }
+    }
+
+    /// Creates a plot whose ranges are the bounds of `points`, see [`Plot::new`].
+    pub fn fit(rect: Rect, points: &[(f64, f64)]) -> Self {
+        let bounds = |values: &mut dyn Iterator<Item = f64>| {
+            let (min, max) = values
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+                    (min.min(value), max.max(value))
+                });
+            if min > max {
+                0.0..=1.
+            } else {
+                min..=max
+            }
+        };
+        let x_range = bounds(&mut points.iter().map(|&(x, _)| x));
+        let y_range = bounds(&mut points.iter().map(|&(_, y)| y));
+        Plot::new(rect, x_range, y_range)
+    }
+
+    /// Sets the axes color.
+    pub fn axes_color(mut self, color: Color) -> Self {
+        self.axes_color = color;
+        self
+    }
+
+    /// Sets the number of ticks on each axis, including both ends.
+    pub fn ticks(mut self, ticks: u16) -> Self {
+        self.ticks = ticks;
+        self
+    }
+
+    /// Gets the rectangle of the plot.
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    /// Maps a data point to the `y` and `x` coordinates of a pixel, possibly outside of the plot.
+    pub fn to_pixel(&self, x: f64, y: f64) -> (i32, i32) {
+        let scale = |value: f64, range: &RangeInclusive<f64>, length: u16| {
+            let span = range.end() - range.start();
+            let ratio = if span == 0. {
+                0.5
+            } else {
+                (value - range.start()) / span
+            };
+            (ratio * length.saturating_sub(1) as f64).round() as i32
+        };
+        (
+            self.rect.end_y() as i32 - 1 - scale(y, &self.y_range, self.rect.height),
+            self.rect.x as i32 + scale(x, &self.x_range, self.rect.width),
+        )
+    }
+
+    /// Draws the axes along the left and bottom sides, with ticks outside of the plot.
+    pub fn draw_axes<D: Draw + ?Sized>(&self, target: &mut D) {
+        if self.rect.height == 0 || self.rect.width == 0 {
+            return;
+        }
+        let (top, left) = (self.rect.y as i32, self.rect.x as i32);
+        let (bottom, right) = (self.rect.end_y() as i32 - 1, self.rect.end_x() as i32 - 1);
+        target.draw_line(top, left, bottom, left, self.axes_color);
+        target.draw_line(bottom, left, bottom, right, self.axes_color);
+        for i in 0..self.ticks {
+            let ratio = i as f64 / self.ticks.saturating_sub(1).max(1) as f64;
+            let y = bottom - (ratio * (bottom - top) as f64).round() as i32;
+            let x = left + (ratio * (right - left) as f64).round() as i32;
+            target.draw_line(y, left - TICK_LENGTH, y, left - 1, self.axes_color);
+            target.draw_line(bottom + 1, x, bottom + TICK_LENGTH, x, self.axes_color);
+        }
+    }
+
+    /// Draws each point inside of the plot with `color`.
+    pub fn scatter<D: Draw + ?Sized>(&self, target: &mut D, points: &[(f64, f64)], color: Color) {
+        let mut clipped = Clipped::new(target, self.rect);
+        for &(x, y) in points {
+            let (y, x) = self.to_pixel(x, y);
+            clipped.draw_line(y, x, y, x, color);
+        }
+    }
+
+    /// Draws lines joining consecutive points with `color`, clipped by the plot.
+    pub fn line<D: Draw + ?Sized>(&self, target: &mut D, points: &[(f64, f64)], color: Color) {
+        let mut clipped = Clipped::new(target, self.rect);
+        for pair in points.windows(2) {
+            let (y0, x0) = self.to_pixel(pair[0].0, pair[0].1);
+            let (y1, x1) = self.to_pixel(pair[1].0, pair[1].1);
+            clipped.draw_line(y0, x0, y1, x1, color);
+        }
+    }
+}
+
+/// Buffer restricted to a rectangle of another one, the pixels outside being ignored.
+struct Clipped<'a, D: Draw + ?Sized> {
+    target: &'a mut D,
+    rect: Rect,
+}
+
+impl<'a, D: Draw + ?Sized> Clipped<'a, D> {
+    fn new(target: &'a mut D, rect: Rect) -> Self {
+        Clipped { target, rect }
+    }
+}
+
+impl<D: Draw + ?Sized> Draw for Clipped<'_, D> {
+    fn height(&self) -> u16 {
+        self.target.height()
+    }
+
+    fn width(&self) -> u16 {
+        self.target.width()
+    }
+
+    fn get_pixel(&self, y: u16, x: u16) -> Color {
+        self.target.get_pixel(y, x)
+    }
+
+    fn set_pixel(&mut self, y: u16, x: u16, color: Color) {
+        if self.rect.contains(y, x) {
+            self.target.set_pixel(y, x, color);
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use std::io::{self, ErrorKind, Result};
+use std::sync::mpsc::{self, Receiver, Sender};
+
+use crate::{Canvas, Color, Draw, Rect, Window};
+
+/// Drawing command sent by a [`WindowProxy`].
+pub(crate) type ProxyCommand = Box<dyn FnOnce(&mut Window) + Send>;
+
+/// Channel between the proxies and their window.
+pub(crate) type ProxyChannel = (Sender<ProxyCommand>, Receiver<ProxyCommand>);
+
+/// Handle drawing into a window from other threads, see [`Window::proxy`].
+///
+/// The commands are sent over a channel and applied in order on the thread of the window, at the
+/// start of its next redraw.
+#[derive(Debug, Clone)]
+pub struct WindowProxy {
+    sender: Sender<ProxyCommand>,
+}
+
+impl WindowProxy {
+    /// Runs `f` on the window before its next redraw.
+    ///
+    /// Returns an error if the window was dropped.
+    pub fn run(&self, f: impl FnOnce(&mut Window) + Send + 'static) -> Result<()> {
+        self.sender
+            .send(Box::new(f))
+            .map_err(|_| io::Error::new(ErrorKind::BrokenPipe, "the window was dropped"))
+    }
+
+    /// Sets a pixel color, see [`Window::set_pixel`].
+    pub fn set_pixel(&self, y: u16, x: u16, color: Color) -> Result<()> {
+        self.run(move |window| window.set_pixel(y, x, color))
+    }
+
+    /// Fills a rectangle with `color`, see [`Draw::fill_rect`].
+    pub fn fill_rect(&self, rect: Rect, color: Color) -> Result<()> {
+        self.run(move |window| window.fill_rect(rect, color))
+    }
+
+    /// Draws `canvas` with its top left corner at `y` and `x`, see [`Draw::blit`].
+    pub fn blit(&self, canvas: Canvas, y: u16, x: u16) -> Result<()> {
+        self.run(move |window| window.blit(&canvas, y, x))
+    }
+}
+
+impl Window {
+    /// Creates a proxy to draw into the window from other threads.
+    pub fn proxy(&mut self) -> WindowProxy {
+        let (sender, _) = self.proxy.get_or_insert_with(mpsc::channel);
+        WindowProxy {
+            sender: sender.clone(),
+        }
+    }
+
+    /// Applies the commands sent by the proxies.
+    pub(crate) fn apply_proxy_commands(&mut self) {
+        let Some((_, receiver)) = &self.proxy else {
+            return;
+        };
+        let commands: Vec<ProxyCommand> = receiver.try_iter().collect();
+        for command in commands {
+            command(self);
+        }
+    }
+}
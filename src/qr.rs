@@ -0,0 +1,60 @@
+//! QR codes drawn into the pixels, e.g. to share a URL from the terminal.
+//!
+//! ```no_run
+//! use winterm::{qr, Rect, Window};
+//!
+//! let mut window = Window::new(50, 50)?;
+//! qr::draw_qr_code(&mut window, Rect::new(0, 0, 50, 50), "https://github.com/clbrunet/winterm")
+//!     .expect("the code fits the window");
+//! window.redraw()?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+pub use qrcode::types::QrError;
+use qrcode::QrCode;
+
+use crate::{Color, Draw, Rect};
+
+/// Width of the light margin around the code, in modules.
+const QUIET_ZONE: usize = 2;
+
+/// Draws the QR code of `data`, black on white, centered in `rect`.
+///
+/// Each module is a square of pixels, as big as `rect` allows, and the code is surrounded by a
+/// light margin. Returns the rectangle drawn, or [`QrError::DataTooLong`] if the code does not fit.
+pub fn draw_qr_code<D: Draw + ?Sized>(
+    target: &mut D,
+    rect: Rect,
+    data: &str,
+) -> Result<Rect, QrError> {
+    let code = QrCode::new(data)?;
+    let modules = code.width() + 2 * QUIET_ZONE;
+    let module_size = (rect.height.min(rect.width) as usize / modules) as u16;
+    if module_size == 0 {
+        return Err(QrError::DataTooLong);
+    }
+    let size = modules as u16 * module_size;
+    let drawn = Rect::new(
+        rect.y + (rect.height - size) / 2,
+        rect.x + (rect.width - size) / 2,
+        size,
+        size,
+    );
+    target.fill_rect(drawn, Color::White);
+    for module_y in 0..code.width() {
+        for module_x in 0..code.width() {
+            if code[(module_x, module_y)] == qrcode::Color::Dark {
+                target.fill_rect(
+                    Rect::new(
+                        drawn.y + (QUIET_ZONE + module_y) as u16 * module_size,
+                        drawn.x + (QUIET_ZONE + module_x) as u16 * module_size,
+                        module_size,
+                        module_size,
+                    ),
+                    Color::Black,
+                );
+            }
+        }
+    }
+    Ok(drawn)
+}
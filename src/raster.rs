@@ -0,0 +1,125 @@
+//! Triangle rasterization, the base of a small software 3D pipeline, see [`Draw::fill_triangle`].
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{color, Canvas, Color, Draw};
+
+/// Triangle vertex, in pixel coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Vertex {
+    /// Row of the vertex, `0.` being the top of the top row.
+    pub y: f32,
+    /// Column of the vertex, `0.` being the left of the left column.
+    pub x: f32,
+    /// View depth of the vertex, used to correct the perspective of the interpolation.
+    pub w: f32,
+    /// Color of the vertex, used by [`Shading::Gouraud`].
+    pub color: Color,
+    /// Texture coordinates of the vertex, from `0.` to `1.`, used by [`Shading::Textured`].
+    pub uv: [f32; 2],
+}
+
+impl Vertex {
+    /// Creates a white vertex with a view depth of `1.`, i.e. without perspective.
+    pub fn new(y: f32, x: f32) -> Self {
+        Vertex {
+            y,
+            x,
+            w: 1.,
+            color: Color::White,
+            uv: [0., 0.],
+        }
+    }
+
+    /// Sets the view depth, the distance from the camera.
+    pub fn w(mut self, w: f32) -> Self {
+        self.w = w;
+        self
+    }
+
+    /// Sets the color.
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Sets the texture coordinates, `u` going right and `v` going down.
+    pub fn uv(mut self, u: f32, v: f32) -> Self {
+        self.uv = [u, v];
+        self
+    }
+}
+
+/// How the pixels of a triangle are colored.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shading<'a> {
+    /// The whole triangle has one color.
+    Flat(Color),
+    /// The colors of the vertices are interpolated.
+    Gouraud,
+    /// The texture is sampled at the interpolated texture coordinates of the vertices.
+    Textured(&'a Canvas),
+}
+
+/// Fills the triangle, see [`Draw::fill_triangle`].
+pub(crate) fn fill_triangle<D: Draw + ?Sized>(
+    target: &mut D,
+    vertices: [Vertex; 3],
+    shading: Shading,
+) {
+    let [a, b, c] = vertices;
+    let edge = |from: &Vertex, to: &Vertex, y: f32, x: f32| {
+        (to.x - from.x) * (y - from.y) - (to.y - from.y) * (x - from.x)
+    };
+    let area = edge(&a, &b, c.y, c.x);
+    if area == 0. || !area.is_finite() {
+        return;
+    }
+    let min = |values: [f32; 3]| values.into_iter().fold(f32::INFINITY, f32::min);
+    let max = |values: [f32; 3]| values.into_iter().fold(f32::NEG_INFINITY, f32::max);
+    let start_y = min([a.y, b.y, c.y]).floor().max(0.) as u16;
+    let end_y = max([a.y, b.y, c.y]).ceil().min(target.height().into()) as u16;
+    let start_x = min([a.x, b.x, c.x]).floor().max(0.) as u16;
+    let end_x = max([a.x, b.x, c.x]).ceil().min(target.width().into()) as u16;
+    let inverse_w = vertices.map(|vertex| 1. / vertex.w);
+    let rgb = vertices.map(|vertex| color::to_rgb(vertex.color).map(f32::from));
+    for y in start_y..end_y {
+        for x in start_x..end_x {
+            let (center_y, center_x) = (y as f32 + 0.5, x as f32 + 0.5);
+            let weights = [
+                edge(&b, &c, center_y, center_x) / area,
+                edge(&c, &a, center_y, center_x) / area,
+                edge(&a, &b, center_y, center_x) / area,
+            ];
+            if weights.iter().any(|&weight| weight < 0.) {
+                continue;
+            }
+            let perspective: [f32; 3] = [0, 1, 2].map(|i| weights[i] * inverse_w[i]);
+            let sum: f32 = perspective.iter().sum();
+            let interpolate =
+                |values: [f32; 3]| (0..3).map(|i| perspective[i] * values[i]).sum::<f32>() / sum;
+            let color = match shading {
+                Shading::Flat(color) => color,
+                Shading::Gouraud => color::from_rgb(
+                    [0, 1, 2].map(|channel| interpolate(rgb.map(|rgb| rgb[channel])).round() as u8),
+                ),
+                Shading::Textured(texture) => {
+                    if texture.height() == 0 || texture.width() == 0 {
+                        return;
+                    }
+                    let u = interpolate(vertices.map(|vertex| vertex.uv[0]));
+                    let v = interpolate(vertices.map(|vertex| vertex.uv[1]));
+                    let texture_y = (v * texture.height() as f32) as u16;
+                    let texture_x = (u * texture.width() as f32) as u16;
+                    texture.get_pixel(
+                        texture_y.min(texture.height() - 1),
+                        texture_x.min(texture.width() - 1),
+                    )
+                }
+            };
+            target.set_pixel(y, x, color);
+        }
+    }
+}
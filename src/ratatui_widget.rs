@@ -0,0 +1,69 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Color as RatatuiColor;
+use ratatui::widgets::Widget;
+
+use crate::{Color, Draw, UPPER_HALF_BLOCK};
+
+/// [ratatui] widget rendering pixels with half blocks, two pixels per cell.
+///
+/// The pixels are drawn from the top left corner of the area and cropped to it.
+#[derive(Debug)]
+pub struct WintermWidget<'a, D: Draw + ?Sized> {
+    source: &'a D,
+}
+
+impl<'a, D: Draw + ?Sized> WintermWidget<'a, D> {
+    /// Creates a widget rendering the pixels of `source`, e.g. a [`crate::Window`] or a
+    /// [`crate::Canvas`].
+    pub fn new(source: &'a D) -> Self {
+        WintermWidget { source }
+    }
+}
+
+impl<D: Draw + ?Sized> Widget for WintermWidget<'_, D> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let rows = area.height.min(self.source.height().div_ceil(2));
+        let columns = area.width.min(self.source.width());
+        for row in 0..rows {
+            let upper_y = row * 2;
+            let lower_y = upper_y + 1;
+            for x in 0..columns {
+                let background = if lower_y < self.source.height() {
+                    to_ratatui_color(self.source.get_pixel(lower_y, x))
+                } else {
+                    RatatuiColor::Reset
+                };
+                if let Some(cell) = buf.cell_mut((area.x + x, area.y + row)) {
+                    cell.set_symbol(UPPER_HALF_BLOCK)
+                        .set_fg(to_ratatui_color(self.source.get_pixel(upper_y, x)))
+                        .set_bg(background);
+                }
+            }
+        }
+    }
+}
+
+fn to_ratatui_color(color: Color) -> RatatuiColor {
+    match color {
+        Color::Reset => RatatuiColor::Reset,
+        Color::Black => RatatuiColor::Black,
+        Color::DarkRed => RatatuiColor::Red,
+        Color::DarkGreen => RatatuiColor::Green,
+        Color::DarkYellow => RatatuiColor::Yellow,
+        Color::DarkBlue => RatatuiColor::Blue,
+        Color::DarkMagenta => RatatuiColor::Magenta,
+        Color::DarkCyan => RatatuiColor::Cyan,
+        Color::Grey => RatatuiColor::Gray,
+        Color::DarkGrey => RatatuiColor::DarkGray,
+        Color::Red => RatatuiColor::LightRed,
+        Color::Green => RatatuiColor::LightGreen,
+        Color::Yellow => RatatuiColor::LightYellow,
+        Color::Blue => RatatuiColor::LightBlue,
+        Color::Magenta => RatatuiColor::LightMagenta,
+        Color::Cyan => RatatuiColor::LightCyan,
+        Color::White => RatatuiColor::White,
+        Color::Rgb { r, g, b } => RatatuiColor::Rgb(r, g, b),
+        Color::AnsiValue(value) => RatatuiColor::Indexed(value),
+    }
+}
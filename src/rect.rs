@@ -0,0 +1,169 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rectangle of pixels, positioned by its top left corner.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Rect {
+    pub y: u16,
+    pub x: u16,
+    pub height: u16,
+    pub width: u16,
+}
+
+impl Rect {
+    /// Creates a rectangle.
+    pub fn new(y: u16, x: u16, height: u16, width: u16) -> Self {
+        Rect {
+            y,
+            x,
+            height,
+            width,
+        }
+    }
+
+    /// Gets the row just below the rectangle.
+    pub fn end_y(&self) -> u16 {
+        self.y.saturating_add(self.height)
+    }
+
+    /// Gets the column just right of the rectangle.
+    pub fn end_x(&self) -> u16 {
+        self.x.saturating_add(self.width)
+    }
+
+    /// Checks if the pixel at `y` and `x` is inside of the rectangle.
+    pub fn contains(&self, y: u16, x: u16) -> bool {
+        (self.y..self.end_y()).contains(&y) && (self.x..self.end_x()).contains(&x)
+    }
+
+    /// Checks if `other` is entirely inside of the rectangle, an empty one being inside of any
+    /// rectangle containing its corner.
+    pub fn contains_rect(&self, other: Rect) -> bool {
+        self.y <= other.y
+            && self.x <= other.x
+            && other.end_y() <= self.end_y()
+            && other.end_x() <= self.end_x()
+    }
+
+    /// Checks if the rectangles share at least one pixel.
+    ///
+    /// ```
+    /// use winterm::Rect;
+    ///
+    /// let player = Rect::new(10, 10, 8, 8);
+    /// assert!(player.intersects(Rect::new(16, 4, 4, 8)));
+    /// assert!(!player.intersects(Rect::new(18, 10, 4, 4)));
+    /// ```
+    pub fn intersects(&self, other: Rect) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Gets the pixels shared by the rectangles, `None` if there are none.
+    pub fn intersection(&self, other: Rect) -> Option<Rect> {
+        let (y, x) = (self.y.max(other.y), self.x.max(other.x));
+        let end_y = self.end_y().min(other.end_y());
+        let end_x = self.end_x().min(other.end_x());
+        (y < end_y && x < end_x).then(|| Rect::new(y, x, end_y - y, end_x - x))
+    }
+
+    /// Gets the smallest rectangle containing both rectangles.
+    pub fn union(&self, other: Rect) -> Rect {
+        let (y, x) = (self.y.min(other.y), self.x.min(other.x));
+        let end_y = self.end_y().max(other.end_y());
+        let end_x = self.end_x().max(other.end_x());
+        Rect::new(y, x, end_y - y, end_x - x)
+    }
+}
+
+/// Widths of the borders around a rectangle, see [`Canvas::padded`](crate::Canvas::padded).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Margins {
+    pub top: u16,
+    pub right: u16,
+    pub bottom: u16,
+    pub left: u16,
+}
+
+impl Margins {
+    /// Creates margins, in the CSS order.
+    pub fn new(top: u16, right: u16, bottom: u16, left: u16) -> Self {
+        Margins {
+            top,
+            right,
+            bottom,
+            left,
+        }
+    }
+
+    /// Creates margins of the same width on every side.
+    pub fn uniform(width: u16) -> Self {
+        Margins::new(width, width, width, width)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Rect;
+
+    #[test]
+    fn contains_excludes_the_end_row_and_column() {
+        let rect = Rect::new(2, 3, 4, 5);
+        assert!(rect.contains(2, 3));
+        assert!(rect.contains(5, 7));
+        assert!(!rect.contains(6, 7));
+        assert!(!rect.contains(5, 8));
+        assert!(!rect.contains(1, 3));
+        assert!(!Rect::new(2, 3, 0, 5).contains(2, 3));
+    }
+
+    #[test]
+    fn contains_rect_accepts_nested_and_empty_rectangles() {
+        let rect = Rect::new(2, 3, 4, 5);
+        assert!(rect.contains_rect(rect));
+        assert!(rect.contains_rect(Rect::new(3, 4, 2, 2)));
+        assert!(rect.contains_rect(Rect::new(6, 8, 0, 0)));
+        assert!(!rect.contains_rect(Rect::new(3, 4, 4, 2)));
+        assert!(!rect.contains_rect(Rect::new(1, 3, 2, 2)));
+    }
+
+    #[test]
+    fn intersection_of_overlapping_rectangles() {
+        let a = Rect::new(0, 0, 4, 6);
+        let b = Rect::new(2, 3, 5, 5);
+        assert_eq!(a.intersection(b), Some(Rect::new(2, 3, 2, 3)));
+        assert_eq!(b.intersection(a), a.intersection(b));
+        assert_eq!(
+            a.intersection(Rect::new(1, 1, 1, 1)),
+            Some(Rect::new(1, 1, 1, 1))
+        );
+    }
+
+    #[test]
+    fn touching_and_empty_rectangles_do_not_intersect() {
+        let a = Rect::new(0, 0, 4, 6);
+        assert_eq!(a.intersection(Rect::new(4, 0, 2, 6)), None);
+        assert_eq!(a.intersection(Rect::new(0, 6, 4, 2)), None);
+        assert_eq!(a.intersection(Rect::new(1, 1, 0, 3)), None);
+        assert!(!a.intersects(Rect::new(10, 10, 2, 2)));
+    }
+
+    #[test]
+    fn union_is_the_bounding_rectangle() {
+        let a = Rect::new(1, 2, 3, 4);
+        let b = Rect::new(5, 0, 2, 2);
+        let union = a.union(b);
+        assert_eq!(union, Rect::new(1, 0, 6, 6));
+        assert_eq!(b.union(a), union);
+        assert!(union.contains_rect(a) && union.contains_rect(b));
+        assert_eq!(a.union(a), a);
+    }
+
+    #[test]
+    fn end_saturates_at_the_maximum() {
+        let rect = Rect::new(u16::MAX - 1, 0, 10, 1);
+        assert_eq!(rect.end_y(), u16::MAX);
+        assert!(rect.contains(u16::MAX - 1, 0));
+    }
+}
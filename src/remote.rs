@@ -0,0 +1,197 @@
+//! Remote control of a window, injecting input events received over TCP, e.g. to drive an
+//! application from a bot or an integration test.
+//!
+//! Each event is a line of JSON, the serialization of an [`Event`] by serde_json.
+//!
+//! ```no_run
+//! use winterm::input::{Event, KeyEvent};
+//! use winterm::remote::RemoteControl;
+//! use winterm::{Key, Modifiers};
+//!
+//! let mut remote = RemoteControl::connect("127.0.0.1:7879")?;
+//! remote.send(&Event::Key(KeyEvent::new(Key::Esc, Modifiers::NONE)))?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::io::{self, ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+
+use crate::input::Event;
+use crate::Window;
+
+/// Longest line accepted from a controller, the ones sending longer lines being disconnected.
+const MAX_LINE_LENGTH: usize = 64 * 1024;
+
+/// Server side, receiving the events of the connected controllers.
+#[derive(Debug)]
+pub(crate) struct EventListener {
+    listener: TcpListener,
+    /// Controllers, with the bytes of their last incomplete line.
+    controllers: Vec<(TcpStream, Vec<u8>)>,
+}
+
+impl EventListener {
+    fn bind(address: impl ToSocketAddrs) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        listener.set_nonblocking(true)?;
+        Ok(EventListener {
+            listener,
+            controllers: Vec::new(),
+        })
+    }
+
+    /// Appends the events received since the last call to `events`, without waiting.
+    ///
+    /// Invalid lines are skipped, and controllers whose connection failed or was closed, or
+    /// sending lines longer than [`MAX_LINE_LENGTH`], are dropped.
+    pub(crate) fn receive(&mut self, events: &mut Vec<Event>) {
+        while let Ok((controller, _)) = self.listener.accept() {
+            if controller.set_nonblocking(true).is_ok() {
+                self.controllers.push((controller, Vec::new()));
+            }
+        }
+        self.controllers.retain_mut(|(controller, pending)| {
+            let mut buffer = [0; 1024];
+            loop {
+                match controller.read(&mut buffer) {
+                    Ok(0) => return false,
+                    Ok(length) => pending.extend_from_slice(&buffer[..length]),
+                    Err(error) if error.kind() == ErrorKind::WouldBlock => return true,
+                    Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                    Err(_) => return false,
+                }
+                while let Some(end) = pending.iter().position(|&byte| byte == b'\n') {
+                    let line: Vec<u8> = pending.drain(..=end).collect();
+                    if let Ok(event) = serde_json::from_slice(&line) {
+                        events.push(event);
+                    }
+                }
+                if pending.len() > MAX_LINE_LENGTH {
+                    return false;
+                }
+            }
+        });
+    }
+}
+
+/// Controller side, sending events to a window.
+#[derive(Debug)]
+pub struct RemoteControl {
+    stream: TcpStream,
+}
+
+impl RemoteControl {
+    /// Connects to a window accepting remote control, see [`Window::start_remote_control`].
+    pub fn connect(address: impl ToSocketAddrs) -> io::Result<Self> {
+        let stream = TcpStream::connect(address)?;
+        stream.set_nodelay(true)?;
+        Ok(RemoteControl { stream })
+    }
+
+    /// Sends an event, received by the next [`Window::poll_events`] after its arrival.
+    pub fn send(&mut self, event: &Event) -> io::Result<()> {
+        let mut line = serde_json::to_vec(event)?;
+        line.push(b'\n');
+        self.stream.write_all(&line)
+    }
+}
+
+impl Window {
+    /// Starts listening on `address` for controllers, the events they send being added to the
+    /// ones of the terminal by [`Window::poll_events`], and debounced and handled like them.
+    ///
+    /// Anyone able to connect can control the window, so `address` should usually be a local one.
+    pub fn start_remote_control(&mut self, address: impl ToSocketAddrs) -> io::Result<()> {
+        self.remote = Some(EventListener::bind(address)?);
+        Ok(())
+    }
+
+    /// Stops listening and disconnects the controllers.
+    pub fn stop_remote_control(&mut self) {
+        self.remote = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+    use std::time::{Duration, Instant};
+
+    use super::{EventListener, RemoteControl, MAX_LINE_LENGTH};
+    use crate::backend::TestBackend;
+    use crate::input::{Event, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+    use crate::{Key, Modifiers, Window};
+
+    /// Polls the events until `condition` holds, the events arriving asynchronously.
+    fn poll_until(window: &mut Window, mut condition: impl FnMut(&mut Window) -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            window.poll_events().unwrap();
+            if condition(window) {
+                break;
+            }
+            assert!(Instant::now() < deadline, "timed out");
+        }
+    }
+
+    #[test]
+    fn injected_events_are_handled_like_terminal_ones() {
+        let mut window = Window::with_backend(8, 8, TestBackend::default()).unwrap();
+        window.start_remote_control("127.0.0.1:0").unwrap();
+        let address = window
+            .remote
+            .as_ref()
+            .unwrap()
+            .listener
+            .local_addr()
+            .unwrap();
+        let mut remote = RemoteControl::connect(address).unwrap();
+        remote
+            .send(&Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Left),
+                column: 3,
+                row: 4,
+                modifiers: Modifiers::NONE,
+            }))
+            .unwrap();
+        remote.send(&Event::Resize(100, 40)).unwrap();
+        poll_until(&mut window, |window| {
+            window.last_events.contains(&Event::Resize(100, 40))
+        });
+        assert_eq!(window.mouse_cell, Some((3, 4)));
+        // The actual terminal keeps its size.
+        assert_eq!((window.terminal_size.x, window.terminal_size.y), (80, 24));
+
+        window.set_key_debounce(Some(Duration::from_secs(60)));
+        let key = Event::Key(KeyEvent::from(Key::Char('a')));
+        remote.send(&key).unwrap();
+        poll_until(&mut window, |window| window.get_key(Key::Char('a')));
+        remote.send(&key).unwrap();
+        remote.send(&Event::Resize(80, 24)).unwrap();
+        // The second press is debounced, in the same poll as the resize or an earlier one.
+        poll_until(&mut window, |window| {
+            assert!(!window.get_key(Key::Char('a')));
+            window.last_events.contains(&Event::Resize(80, 24))
+        });
+    }
+
+    #[test]
+    fn controller_sending_overlong_lines_is_dropped() {
+        let mut listener = EventListener::bind("127.0.0.1:0").unwrap();
+        let mut controller = TcpStream::connect(listener.listener.local_addr().unwrap()).unwrap();
+        controller
+            .write_all(&vec![b'x'; MAX_LINE_LENGTH + 1])
+            .unwrap();
+        controller.set_nonblocking(true).unwrap();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut events = Vec::new();
+        // The connection is closed by the listener once it dropped the controller.
+        while controller.read(&mut [0]).map_or(true, |length| length > 0) {
+            assert!(Instant::now() < deadline, "timed out");
+            listener.receive(&mut events);
+        }
+        assert!(listener.controllers.is_empty());
+        assert!(events.is_empty());
+    }
+}
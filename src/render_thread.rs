@@ -0,0 +1,180 @@
+use std::io::{self, ErrorKind, Result};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::{Canvas, Window, WindowEvent};
+
+/// Longest wait of the render thread for a frame before polling the events again.
+const EVENTS_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Frame exchange between the application and the render thread.
+#[derive(Debug, Default)]
+struct Exchange {
+    /// Latest frame submitted, not yet presented.
+    ready: Option<Canvas>,
+    /// Buffers already presented, reused for the next frames.
+    spare: Vec<Canvas>,
+    stop: bool,
+}
+
+#[derive(Debug, Default)]
+struct Shared {
+    exchange: Mutex<Exchange>,
+    condvar: Condvar,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, Exchange> {
+        self.exchange
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Window presented by a dedicated thread, see [`Window::spawn_render_thread`].
+///
+/// Frames are triple buffered: the application draws into its own canvas while the latest
+/// submitted frame waits for the render thread, which presents another one. A slow terminal thus
+/// never blocks the application, which never blocks the presentation either, frames submitted
+/// faster than they are presented being skipped.
+#[derive(Debug)]
+pub struct RenderThread {
+    canvas: Canvas,
+    shared: Arc<Shared>,
+    events: Receiver<WindowEvent>,
+    handle: Option<JoinHandle<Result<Window>>>,
+}
+
+impl Window {
+    /// Moves the window to a new thread presenting the frames submitted with
+    /// [`RenderThread::present`], and forwarding the terminal events.
+    ///
+    /// The canvas of the frames starts with the pixels of the window.
+    pub fn spawn_render_thread(mut self) -> RenderThread {
+        let canvas = self.pixels.clone();
+        let shared = Arc::new(Shared::default());
+        let (sender, events) = mpsc::channel();
+        let thread_shared = Arc::clone(&shared);
+        let handle = thread::spawn(move || {
+            self.run_render_thread(&thread_shared, &sender)?;
+            Ok(self)
+        });
+        RenderThread {
+            canvas,
+            shared,
+            events,
+            handle: Some(handle),
+        }
+    }
+
+    fn run_render_thread(&mut self, shared: &Shared, events: &Sender<WindowEvent>) -> Result<()> {
+        loop {
+            self.poll_events()?;
+            for event in self.events() {
+                let _ = events.send(event);
+            }
+            let frame = {
+                let mut exchange = shared.lock();
+                if exchange.ready.is_none() && !exchange.stop {
+                    exchange = shared
+                        .condvar
+                        .wait_timeout(exchange, EVENTS_INTERVAL)
+                        .unwrap_or_else(|poisoned| poisoned.into_inner())
+                        .0;
+                }
+                if exchange.stop {
+                    return Ok(());
+                }
+                exchange.ready.take()
+            };
+            let Some(frame) = frame else {
+                continue;
+            };
+            if frame.height() == self.height() && frame.width() == self.width() {
+                self.set_from_fn(|y, x| frame.get_pixel(y, x));
+            }
+            self.redraw()?;
+            shared.lock().spare.push(frame);
+        }
+    }
+}
+
+impl RenderThread {
+    /// Gets the canvas the next frame is drawn into, keeping the content of the last one.
+    pub fn canvas_mut(&mut self) -> &mut Canvas {
+        &mut self.canvas
+    }
+
+    /// Submits a copy of the canvas to be presented, replacing the previous frame if the render
+    /// thread did not present it yet.
+    ///
+    /// Returns an error if the render thread stopped, see [`RenderThread::stop`].
+    pub fn present(&mut self) -> Result<()> {
+        if self.handle.as_ref().is_none_or(JoinHandle::is_finished) {
+            return Err(io::Error::new(
+                ErrorKind::BrokenPipe,
+                "the render thread stopped",
+            ));
+        }
+        let mut exchange = self.shared.lock();
+        let mut frame = match exchange.ready.take() {
+            Some(frame) => frame,
+            None => exchange.spare.pop().unwrap_or_else(|| self.canvas.clone()),
+        };
+        frame.clone_from(&self.canvas);
+        exchange.ready = Some(frame);
+        self.shared.condvar.notify_one();
+        Ok(())
+    }
+
+    /// Gets the events received since the last call, see [`Window::events`].
+    pub fn events(&self) -> Vec<WindowEvent> {
+        self.events.try_iter().collect()
+    }
+
+    /// Stops the render thread, giving the window back, or the error that stopped the thread.
+    pub fn stop(mut self) -> Result<Window> {
+        self.join()
+            .unwrap_or_else(|| Err(io::Error::other("the render thread was already stopped")))
+    }
+
+    fn join(&mut self) -> Option<Result<Window>> {
+        let handle = self.handle.take()?;
+        self.shared.lock().stop = true;
+        self.shared.condvar.notify_one();
+        Some(
+            handle
+                .join()
+                .unwrap_or_else(|_| Err(io::Error::other("the render thread panicked"))),
+        )
+    }
+}
+
+impl Drop for RenderThread {
+    fn drop(&mut self) {
+        let _ = self.join();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Color;
+
+    use crate::backend::TestBackend;
+    use crate::Window;
+
+    #[test]
+    fn render_thread_starts_with_the_window_pixels() {
+        let mut window = Window::with_backend(2, 2, TestBackend::default()).unwrap();
+        window.set_clear_color(Color::Blue);
+        window.clear();
+        window.set_pixel(1, 1, Color::Red);
+        let mut render_thread = window.spawn_render_thread();
+        assert_eq!(render_thread.canvas_mut().get_pixel(0, 0), Color::Blue);
+        assert_eq!(render_thread.canvas_mut().get_pixel(1, 1), Color::Red);
+        let window = render_thread.stop().unwrap();
+        assert_eq!(window.get_pixel(1, 1), Color::Red);
+    }
+}
@@ -0,0 +1,106 @@
+use crate::{Canvas, Draw};
+
+/// Identifier of an entity of a [`Scene`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EntityId(usize);
+
+/// Sprite drawn by a [`Scene`] with its top left corner at `y` and `x`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Entity {
+    pub sprite: Canvas,
+    pub y: u16,
+    pub x: u16,
+    /// Drawing order, the entities with a higher `z` being drawn over the others.
+    pub z: i32,
+    pub visible: bool,
+}
+
+impl Entity {
+    /// Creates a visible entity at a `z` of 0.
+    pub fn new(sprite: Canvas, y: u16, x: u16) -> Self {
+        Entity {
+            sprite,
+            y,
+            x,
+            z: 0,
+            visible: true,
+        }
+    }
+
+    /// Sets the drawing order of the entity.
+    pub fn with_z(mut self, z: i32) -> Self {
+        self.z = z;
+        self
+    }
+}
+
+/// Retained list of entities, drawn in their `z` order.
+///
+/// ```
+/// use winterm::Color;
+/// use winterm::{Canvas, Entity, Scene};
+///
+/// let mut scene = Scene::new();
+/// let player = scene.add(Entity::new(Canvas::new(4, 4, Color::Green), 2, 2).with_z(1));
+/// scene.add(Entity::new(Canvas::new(8, 8, Color::Blue), 0, 0));
+/// scene.entity_mut(player).unwrap().x += 1;
+///
+/// let mut target = Canvas::new(16, 16, Color::Black);
+/// scene.draw(&mut target);
+/// assert_eq!(target.get_pixel(2, 3), Color::Green);
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Scene {
+    entities: Vec<Option<Entity>>,
+}
+
+impl Scene {
+    /// Creates an empty scene.
+    pub fn new() -> Self {
+        Scene::default()
+    }
+
+    /// Adds an entity to the scene.
+    pub fn add(&mut self, entity: Entity) -> EntityId {
+        self.entities.push(Some(entity));
+        EntityId(self.entities.len() - 1)
+    }
+
+    /// Removes an entity, returning it if it was in the scene.
+    pub fn remove(&mut self, id: EntityId) -> Option<Entity> {
+        self.entities.get_mut(id.0)?.take()
+    }
+
+    /// Gets an entity, `None` if it was removed.
+    pub fn entity(&self, id: EntityId) -> Option<&Entity> {
+        self.entities.get(id.0)?.as_ref()
+    }
+
+    /// Gets an entity mutably, `None` if it was removed.
+    pub fn entity_mut(&mut self, id: EntityId) -> Option<&mut Entity> {
+        self.entities.get_mut(id.0)?.as_mut()
+    }
+
+    /// Iterates over the entities with their identifiers, in the order they were added in.
+    pub fn entities(&self) -> impl Iterator<Item = (EntityId, &Entity)> {
+        self.entities
+            .iter()
+            .enumerate()
+            .filter_map(|(i, entity)| Some((EntityId(i), entity.as_ref()?)))
+    }
+
+    /// Draws the visible entities onto `target`, from the lowest `z` to the highest, the ones
+    /// of the same `z` in the order they were added in.
+    pub fn draw<D: Draw + ?Sized>(&self, target: &mut D) {
+        let mut entities: Vec<&Entity> = self
+            .entities
+            .iter()
+            .flatten()
+            .filter(|entity| entity.visible)
+            .collect();
+        entities.sort_by_key(|entity| entity.z);
+        for entity in entities {
+            target.blit(&entity.sprite, entity.y, entity.x);
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use std::fs;
+use std::io::Result;
+use std::path::{Path, PathBuf};
+
+use crate::{queue_text_frame, Key, SystemTime, Window, UNIX_EPOCH};
+
+/// Screenshot hotkey and directory, see [`Window::set_screenshot_key`].
+#[derive(Debug)]
+pub(crate) struct Screenshots {
+    key: Option<Key>,
+    directory: PathBuf,
+}
+
+impl Default for Screenshots {
+    fn default() -> Self {
+        Screenshots {
+            key: Some(Key::F(12)),
+            directory: PathBuf::from("screenshots"),
+        }
+    }
+}
+
+impl Window {
+    /// Sets the key saving a screenshot during [`Window::poll_events`], F12 by default, `None`
+    /// disabling it, see [`Window::save_screenshot`].
+    pub fn set_screenshot_key(&mut self, key: Option<Key>) {
+        self.screenshots.key = key;
+    }
+
+    /// Sets the directory of the screenshots, created if needed, `screenshots` in the current
+    /// directory by default.
+    pub fn set_screenshot_directory(&mut self, directory: impl AsRef<Path>) {
+        self.screenshots.directory = directory.as_ref().to_owned();
+    }
+
+    /// Saves the last presented frame in the screenshots directory, returning the path of the
+    /// ANSI art file, see [`Window::set_screenshot_directory`].
+    ///
+    /// The frame is saved as ANSI art with the `.ans` extension, which `cat` displays, and with
+    /// the `image` feature, as a PNG image with the same name.
+    pub fn save_screenshot(&self) -> Result<PathBuf> {
+        fs::create_dir_all(&self.screenshots.directory)?;
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let path = self
+            .screenshots
+            .directory
+            .join(format!("screenshot-{timestamp}.ans"));
+        let mut text = Vec::new();
+        queue_text_frame(&mut text, &self.front, self.encoding())?;
+        fs::write(&path, text)?;
+        #[cfg(feature = "image")]
+        image::RgbImage::from_raw(
+            self.width().into(),
+            self.height().into(),
+            self.front.as_rgb_bytes(),
+        )
+        .expect("the RGB bytes match the dimensions")
+        .save(path.with_extension("png"))
+        .map_err(std::io::Error::other)?;
+        Ok(path)
+    }
+
+    /// Saves a screenshot if its key was read.
+    ///
+    /// A failure does not interrupt the events polling, it is logged with the `log` feature.
+    pub(crate) fn update_screenshot(&mut self) {
+        let Some(key) = self.screenshots.key else {
+            return;
+        };
+        if !self.get_key_exact(key) {
+            return;
+        }
+        let result = self.save_screenshot();
+        #[cfg(feature = "log")]
+        if let Err(error) = result {
+            log::warn!("failed to save a screenshot: {error}");
+        }
+        #[cfg(not(feature = "log"))]
+        let _ = result;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use crate::backend::TestBackend;
+    use crate::input::{Event, KeyEvent};
+    use crate::{Color, Key, Window};
+
+    fn directory(name: &str) -> std::path::PathBuf {
+        let directory = env::temp_dir().join(format!("winterm-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&directory);
+        directory
+    }
+
+    #[test]
+    fn save_screenshot_writes_the_presented_frame() {
+        let directory = directory("screenshot");
+        let mut window = Window::with_backend(4, 3, TestBackend::default()).unwrap();
+        window.set_screenshot_directory(&directory);
+        window.set_pixel(0, 0, Color::Rgb { r: 1, g: 2, b: 3 });
+        window.redraw().unwrap();
+        window.set_pixel(0, 1, Color::Red);
+        let path = window.save_screenshot().unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(text.contains("1;2;3"));
+        assert_eq!(text.lines().count(), 2);
+        #[cfg(feature = "image")]
+        {
+            let image = image::open(path.with_extension("png")).unwrap().to_rgb8();
+            assert_eq!(image.dimensions(), (3, 4));
+            assert_eq!(image.get_pixel(0, 0).0, [1, 2, 3]);
+            // The pixel set after the redraw is not presented yet.
+            assert_eq!(image.get_pixel(1, 0).0, [0, 0, 0]);
+        }
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn screenshot_key_failure_does_not_fail_poll_events() {
+        let file = directory("screenshot-file");
+        std::fs::write(&file, "").unwrap();
+        let backend = TestBackend::default();
+        let mut window = Window::with_backend(4, 3, backend.clone()).unwrap();
+        // The directory cannot be created under a file.
+        window.set_screenshot_directory(file.join("screenshots"));
+        backend.push(Event::Key(KeyEvent::from(Key::F(12))));
+        assert!(window.poll_events().is_ok());
+        assert!(window.save_screenshot().is_err());
+        std::fs::remove_file(file).unwrap();
+    }
+}
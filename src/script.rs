@@ -0,0 +1,240 @@
+//! Scripting with [Rhai](https://rhai.rs), to live-code effects against a running window.
+//!
+//! A script defines a `frame` function, called by [`Script::run_frame`], and optionally an `init`
+//! one, called once. Both share their state through `this`, an object map:
+//!
+//! ```rhai
+//! fn init() {
+//!     this.x = 0;
+//! }
+//!
+//! fn frame() {
+//!     clear(rgb(0, 0, 0));
+//!     if key("Right") {
+//!         this.x += 1;
+//!     }
+//!     fill_rect(10, this.x, 5, 5, rgb(255, 0, 0));
+//! }
+//! ```
+//!
+//! The functions available are `width()`, `height()`, `key(name)`, with a character or a key
+//! name such as `"Esc"` or `"Left"`, `rgb(r, g, b)`, `clear(color)`, `set_pixel(y, x, color)`,
+//! `fill_rect(y, x, height, width, color)`, `draw_line(y0, x0, y1, x1, color)` and
+//! `draw_text(y, x, text, color)`.
+
+use std::cell::RefCell;
+use std::fs;
+use std::path::Path;
+use std::rc::Rc;
+
+pub use rhai::EvalAltResult;
+use rhai::{CallFnOptions, Dynamic, Engine, Map, Scope, AST};
+
+use crate::input::{Event, KeyEventKind};
+use crate::{Color, Draw, Key, Rect, Window};
+
+/// Drawing command of a script, applied to the window after the script returns.
+type ScriptCommand = Box<dyn FnOnce(&mut Window)>;
+
+/// Window state read by the scripts, and commands they queued.
+#[derive(Default)]
+struct ScriptState {
+    height: u16,
+    width: u16,
+    keys: Vec<Key>,
+    commands: Vec<ScriptCommand>,
+}
+
+/// Compiled script, with its engine and state.
+pub struct Script {
+    engine: Engine,
+    ast: AST,
+    this: Dynamic,
+    initialized: bool,
+    state: Rc<RefCell<ScriptState>>,
+}
+
+impl Script {
+    /// Compiles a script.
+    pub fn new(source: &str) -> Result<Self, Box<EvalAltResult>> {
+        let state = Rc::new(RefCell::new(ScriptState::default()));
+        let engine = create_engine(&state);
+        let ast = engine.compile(source)?;
+        Ok(Script {
+            engine,
+            ast,
+            this: Map::new().into(),
+            initialized: false,
+            state,
+        })
+    }
+
+    /// Compiles the script of a file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<EvalAltResult>> {
+        let source = fs::read_to_string(path).map_err(|error| error.to_string())?;
+        Script::new(&source)
+    }
+
+    /// Replaces the script by a new version, keeping `this`, so that `init` is not called again.
+    ///
+    /// The previous version is kept if `source` does not compile.
+    pub fn reload(&mut self, source: &str) -> Result<(), Box<EvalAltResult>> {
+        self.ast = self.engine.compile(source)?;
+        Ok(())
+    }
+
+    /// Calls the `frame` function of the script, after `init` for the first call, reading the
+    /// input of the last [`Window::poll_events`] and drawing into `window`.
+    pub fn run_frame(&mut self, window: &mut Window) -> Result<(), Box<EvalAltResult>> {
+        {
+            let mut state = self.state.borrow_mut();
+            state.height = window.height();
+            state.width = window.width();
+            state.keys = window
+                .last_events
+                .iter()
+                .filter_map(|event| match event {
+                    Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
+                        Some(key_event.code)
+                    }
+                    _ => None,
+                })
+                .collect();
+        }
+        let result = self.call_frame();
+        let commands = std::mem::take(&mut self.state.borrow_mut().commands);
+        for command in commands {
+            command(window);
+        }
+        result
+    }
+
+    fn call_frame(&mut self) -> Result<(), Box<EvalAltResult>> {
+        if !self.initialized {
+            self.initialized = true;
+            if self
+                .ast
+                .iter_functions()
+                .any(|function| function.name == "init")
+            {
+                self.call("init")?;
+            }
+        }
+        self.call("frame")
+    }
+
+    fn call(&mut self, name: &str) -> Result<(), Box<EvalAltResult>> {
+        let options = CallFnOptions::new().bind_this_ptr(&mut self.this);
+        self.engine
+            .call_fn_with_options(options, &mut Scope::new(), &self.ast, name, ())
+    }
+}
+
+impl std::fmt::Debug for Script {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Script")
+            .field("ast", &self.ast)
+            .field("this", &self.this)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Creates an engine whose functions read and queue commands into `state`.
+fn create_engine(state: &Rc<RefCell<ScriptState>>) -> Engine {
+    let mut engine = Engine::new();
+    engine.register_type_with_name::<Color>("Color");
+    engine.register_fn("rgb", |r: i64, g: i64, b: i64| Color::Rgb {
+        r: channel(r),
+        g: channel(g),
+        b: channel(b),
+    });
+    let state_ref = Rc::clone(state);
+    engine.register_fn("height", move || i64::from(state_ref.borrow().height));
+    let state_ref = Rc::clone(state);
+    engine.register_fn("width", move || i64::from(state_ref.borrow().width));
+    let state_ref = Rc::clone(state);
+    engine.register_fn("key", move |name: &str| {
+        key_code(name).is_some_and(|key| state_ref.borrow().keys.contains(&key))
+    });
+    let queue = |state: &Rc<RefCell<ScriptState>>| {
+        let state = Rc::clone(state);
+        move |command: ScriptCommand| state.borrow_mut().commands.push(command)
+    };
+    let push = queue(state);
+    engine.register_fn("clear", move |color: Color| {
+        push(Box::new(move |window| window.pixels_mut().fill(color)));
+    });
+    let push = queue(state);
+    engine.register_fn("set_pixel", move |y: i64, x: i64, color: Color| {
+        push(Box::new(move |window| {
+            if (0..i64::from(window.height())).contains(&y)
+                && (0..i64::from(window.width())).contains(&x)
+            {
+                window.set_pixel(y as u16, x as u16, color);
+            }
+        }));
+    });
+    let push = queue(state);
+    engine.register_fn(
+        "fill_rect",
+        move |y: i64, x: i64, height: i64, width: i64, color: Color| {
+            let (start_y, start_x) = (coordinate(y), coordinate(x));
+            let rect = Rect::new(
+                start_y,
+                start_x,
+                coordinate(y.saturating_add(height)).saturating_sub(start_y),
+                coordinate(x.saturating_add(width)).saturating_sub(start_x),
+            );
+            push(Box::new(move |window| window.fill_rect(rect, color)));
+        },
+    );
+    let push = queue(state);
+    engine.register_fn(
+        "draw_line",
+        move |y0: i64, x0: i64, y1: i64, x1: i64, color: Color| {
+            let [y0, x0, y1, x1] = [y0, x0, y1, x1]
+                .map(|value| value.clamp(i64::from(i32::MIN), i64::from(i32::MAX)) as i32);
+            push(Box::new(move |window| {
+                window.draw_line(y0, x0, y1, x1, color);
+            }));
+        },
+    );
+    let push = queue(state);
+    engine.register_fn(
+        "draw_text",
+        move |y: i64, x: i64, text: &str, color: Color| {
+            let text = text.to_owned();
+            let (y, x) = (coordinate(y), coordinate(x));
+            push(Box::new(move |window| window.draw_text(y, x, &text, color)));
+        },
+    );
+    engine
+}
+
+fn channel(value: i64) -> u8 {
+    value.clamp(0, u8::MAX.into()) as u8
+}
+
+fn coordinate(value: i64) -> u16 {
+    value.clamp(0, u16::MAX.into()) as u16
+}
+
+/// Parses a character, or a key name such as `"Esc"`.
+fn key_code(name: &str) -> Option<Key> {
+    let mut chars = name.chars();
+    if let (Some(char), None) = (chars.next(), chars.next()) {
+        return Some(Key::Char(char));
+    }
+    Some(match name {
+        "Esc" => Key::Esc,
+        "Enter" => Key::Enter,
+        "Space" => Key::Char(' '),
+        "Tab" => Key::Tab,
+        "Backspace" => Key::Backspace,
+        "Left" => Key::Left,
+        "Right" => Key::Right,
+        "Up" => Key::Up,
+        "Down" => Key::Down,
+        _ => return None,
+    })
+}
@@ -0,0 +1,90 @@
+use std::cmp;
+
+use crate::{Color, Rect, Window};
+
+impl Window {
+    /// Copies the pixels of `source` with their top left corner at `y` and `x`, the rectangles
+    /// possibly overlapping.
+    ///
+    /// The rows are moved in memory at once, so like [`Window::pixels_mut`], the copy ignores the
+    /// clip rectangle and the wrap mode. The parts outside of the window are skipped.
+    pub fn copy_region(&mut self, source: Rect, y: u16, x: u16) {
+        let (height, width) = (self.height(), self.width());
+        let end_y = cmp::min(source.end_y(), height);
+        let end_x = cmp::min(source.end_x(), width);
+        let copy_height = cmp::min(end_y.saturating_sub(source.y), height.saturating_sub(y));
+        let copy_width = cmp::min(end_x.saturating_sub(source.x), width.saturating_sub(x));
+        if copy_height == 0 || copy_width == 0 {
+            return;
+        }
+        let pixels = self.pixels.pixels_mut();
+        let row_start = |y: u16, x: u16| y as usize * width as usize + x as usize;
+        let mut copy_row = |row: u16| {
+            let start = row_start(source.y + row, source.x);
+            pixels.copy_within(start..start + copy_width as usize, row_start(y + row, x));
+        };
+        // Rows moving down are copied from the bottom, not to overwrite the ones left to copy.
+        if y > source.y {
+            (0..copy_height).rev().for_each(&mut copy_row);
+        } else {
+            (0..copy_height).for_each(&mut copy_row);
+        }
+        self.mark_dirty(Rect::new(y, x, copy_height, copy_width));
+    }
+
+    /// Shifts the pixels by `dy` rows down and `dx` columns right, negative values shifting them
+    /// up and left, the uncovered pixels being set to `fill`.
+    ///
+    /// Like [`Window::copy_region`], it ignores the clip rectangle and the wrap mode.
+    pub fn scroll(&mut self, dy: i32, dx: i32, fill: Color) {
+        let (height, width) = (i32::from(self.height()), i32::from(self.width()));
+        let (dy, dx) = (dy.clamp(-height, height), dx.clamp(-width, width));
+        if dy.abs() >= height || dx.abs() >= width {
+            self.pixels_mut().fill(fill);
+            return;
+        }
+        let source = Rect::new(
+            cmp::max(-dy, 0) as u16,
+            cmp::max(-dx, 0) as u16,
+            (height - dy.abs()) as u16,
+            (width - dx.abs()) as u16,
+        );
+        self.copy_region(source, cmp::max(dy, 0) as u16, cmp::max(dx, 0) as u16);
+        let rows = if dy > 0 { 0..dy } else { height + dy..height };
+        let columns = if dx > 0 { 0..dx } else { width + dx..width };
+        let pixels = self.pixels_mut();
+        for row in pixels.chunks_exact_mut(width as usize) {
+            row[columns.start as usize..columns.end as usize].fill(fill);
+        }
+        pixels[(rows.start * width) as usize..(rows.end * width) as usize].fill(fill);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Color;
+
+    use crate::backend::TestBackend;
+    use crate::Window;
+
+    #[test]
+    fn scroll_shifts_the_pixels_and_fills_the_uncovered_ones() {
+        let mut window = Window::with_backend(3, 3, TestBackend::default()).unwrap();
+        window.set_pixel(0, 0, Color::Red);
+        window.scroll(1, 2, Color::Blue);
+        assert_eq!(window.get_pixel(1, 2), Color::Red);
+        assert_eq!(window.get_pixel(0, 2), Color::Blue);
+        assert_eq!(window.get_pixel(1, 1), Color::Blue);
+        assert_eq!(window.get_pixel(2, 2), Color::Black);
+    }
+
+    #[test]
+    fn scroll_by_extreme_offsets_fills_the_window() {
+        let mut window = Window::with_backend(3, 3, TestBackend::default()).unwrap();
+        for (dy, dx) in [(i32::MIN, 0), (0, i32::MIN), (i32::MAX, i32::MAX)] {
+            window.set_pixel(1, 1, Color::Red);
+            window.scroll(dy, dx, Color::Blue);
+            assert!(window.pixels().iter().all(|&pixel| pixel == Color::Blue));
+        }
+    }
+}
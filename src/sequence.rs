@@ -0,0 +1,184 @@
+use std::time::Duration;
+
+use crate::input::{Event, KeyEventKind};
+use crate::{Instant, Key, Window};
+
+/// Identifier of a key sequence, see [`Window::register_sequence`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SequenceId(usize);
+
+/// Keys to press in order, each within `timeout` of the previous one.
+#[derive(Debug, Clone)]
+pub(crate) struct KeySequence {
+    keys: Vec<Key>,
+    /// Length of the longest proper prefix of `keys[..=i]` that is also a suffix of it, for each
+    /// `i`, the progress kept on a mismatch as in the Knuth-Morris-Pratt algorithm.
+    fallbacks: Vec<usize>,
+    timeout: Duration,
+    progress: usize,
+    last_key: Option<Instant>,
+    completed: bool,
+}
+
+impl KeySequence {
+    fn new(keys: &[Key], timeout: Duration) -> Self {
+        assert!(!keys.is_empty(), "the sequence must have at least one key");
+        let mut fallbacks = vec![0; keys.len()];
+        let mut length = 0;
+        for i in 1..keys.len() {
+            while length > 0 && keys[i] != keys[length] {
+                length = fallbacks[length - 1];
+            }
+            if keys[i] == keys[length] {
+                length += 1;
+            }
+            fallbacks[i] = length;
+        }
+        KeySequence {
+            keys: keys.to_vec(),
+            fallbacks,
+            timeout,
+            progress: 0,
+            last_key: None,
+            completed: false,
+        }
+    }
+
+    fn press(&mut self, key: Key, now: Instant) {
+        if self
+            .last_key
+            .is_some_and(|last_key| now - last_key > self.timeout)
+        {
+            self.progress = 0;
+        }
+        self.last_key = Some(now);
+        while self.progress > 0 && self.keys[self.progress] != key {
+            self.progress = self.fallbacks[self.progress - 1];
+        }
+        if self.keys[self.progress] == key {
+            self.progress += 1;
+        }
+        if self.progress == self.keys.len() {
+            self.progress = 0;
+            self.completed = true;
+        }
+    }
+}
+
+impl Window {
+    /// Registers a sequence of keys, e.g. `g g` or the Konami code, each key having to be pressed
+    /// within `timeout` of the previous one.
+    ///
+    /// Its completion is reported by [`Window::get_sequence`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `keys` is empty.
+    pub fn register_sequence(&mut self, keys: &[Key], timeout: Duration) -> SequenceId {
+        self.sequences.push(KeySequence::new(keys, timeout));
+        SequenceId(self.sequences.len() - 1)
+    }
+
+    /// Returns `true` if the sequence was completed during the last call to
+    /// [`Window::poll_events`].
+    pub fn get_sequence(&self, id: SequenceId) -> bool {
+        self.sequences
+            .get(id.0)
+            .is_some_and(|sequence| sequence.completed)
+    }
+
+    /// Advances the sequences with the keys pressed in the last events.
+    pub(crate) fn update_sequences(&mut self) {
+        let now = Instant::now();
+        for sequence in &mut self.sequences {
+            sequence.completed = false;
+            for event in &self.last_events {
+                if let Event::Key(key_event) = event {
+                    if key_event.kind == KeyEventKind::Press {
+                        sequence.press(key_event.code, now);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::KeySequence;
+    use crate::Key::{self, Char, Down, Left, Right, Up};
+
+    /// Presses `keys` in order, returning the indices of the presses completing the sequence.
+    fn completions(sequence: &[Key], keys: &[Key]) -> Vec<usize> {
+        let mut sequence = KeySequence::new(sequence, Duration::from_secs(1));
+        let now = Instant::now();
+        let mut completions = Vec::new();
+        for (i, &key) in keys.iter().enumerate() {
+            sequence.completed = false;
+            sequence.press(key, now);
+            if sequence.completed {
+                completions.push(i);
+            }
+        }
+        completions
+    }
+
+    #[test]
+    fn konami_code_completes_after_an_extra_up() {
+        let konami = [
+            Up,
+            Up,
+            Down,
+            Down,
+            Left,
+            Right,
+            Left,
+            Right,
+            Char('b'),
+            Char('a'),
+        ];
+        let mut keys = vec![Up];
+        keys.extend(konami);
+        assert_eq!(completions(&konami, &keys), [10]);
+    }
+
+    #[test]
+    fn overlapping_prefix_is_kept_on_a_mismatch() {
+        let sequence = [Char('a'), Char('b'), Char('a'), Char('c')];
+        let keys = [
+            Char('a'),
+            Char('b'),
+            Char('a'),
+            Char('b'),
+            Char('a'),
+            Char('c'),
+        ];
+        assert_eq!(completions(&sequence, &keys), [5]);
+    }
+
+    #[test]
+    fn mismatch_without_prefix_restarts_the_sequence() {
+        let sequence = [Char('g'), Char('g')];
+        let keys = [Char('g'), Char('x'), Char('g'), Char('g')];
+        assert_eq!(completions(&sequence, &keys), [3]);
+    }
+
+    #[test]
+    fn timeout_restarts_the_sequence() {
+        let mut sequence = KeySequence::new(&[Char('g'), Char('g')], Duration::from_secs(1));
+        let now = Instant::now();
+        sequence.press(Char('g'), now);
+        sequence.press(Char('g'), now + Duration::from_secs(2));
+        assert!(!sequence.completed);
+        sequence.press(Char('g'), now + Duration::from_secs(2));
+        assert!(sequence.completed);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one key")]
+    fn empty_sequence_is_rejected() {
+        KeySequence::new(&[], Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,65 @@
+use std::cmp;
+use std::io::Result;
+
+use crate::ansi::{queue, MoveTo, Print};
+use crate::Window;
+
+impl Window {
+    /// Sets the text of the status line `index`, the terminal rows under the window being
+    /// reserved for as many status lines as the highest index set.
+    ///
+    /// The status lines are repainted after every redraw, truncated to the width of the window
+    /// and its border.
+    ///
+    /// ```
+    /// # use winterm::Window;
+    /// # let mut window = Window::new(9, 16)?;
+    /// window.set_status_line(0, "HP: 10  Gold: 3");
+    /// window.redraw()?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn set_status_line(&mut self, index: u16, text: &str) {
+        let index = index as usize;
+        if index >= self.status_lines.len() {
+            self.status_lines.resize(index + 1, String::new());
+            self.calculate_origin();
+            self.should_redraw_all = true;
+        }
+        self.status_lines[index] = text.lines().next().unwrap_or_default().to_owned();
+    }
+
+    /// Removes the status lines, giving their rows back to the window.
+    pub fn clear_status_lines(&mut self) {
+        if !self.status_lines.is_empty() {
+            self.status_lines.clear();
+            self.calculate_origin();
+            self.should_redraw_all = true;
+        }
+    }
+
+    /// Gets the texts of the status lines, see [`Window::set_status_line`].
+    pub fn status_lines(&self) -> &[String] {
+        &self.status_lines
+    }
+
+    pub(crate) fn queue_status_lines(&mut self) -> Result<()> {
+        // The bottom border takes its own row under an even number of pixels rows.
+        let first_row = i32::from(self.end_y()) + i32::from(self.scaled_height().is_multiple_of(2));
+        let start_x = cmp::max(self.origin.x - 1, 0) as u16;
+        let width = cmp::min(
+            self.scaled_width() + 2,
+            self.terminal_size.x.saturating_sub(start_x),
+        ) as usize;
+        for (row, text) in (first_row..).zip(&self.status_lines) {
+            if row < 0 || row >= i32::from(self.terminal_size.y) {
+                continue;
+            }
+            queue!(
+                self.terminal,
+                MoveTo(start_x, row as u16),
+                Print(format!("{text:<width$.width$}"))
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,394 @@
+use std::hash::{Hash, Hasher};
+use std::io::Result;
+
+use crate::{
+    color::{self, Quantizer},
+    Color,
+};
+use crate::{Canvas, Draw, Window};
+
+/// Storage of pixels, which can be drawn into with the [`Draw`] methods and copied into the window
+/// with [`Window::redraw_from`].
+///
+/// Implementing it for the framebuffer of an existing engine saves converting it to a
+/// [`Canvas`](crate::Canvas) first, but the integration is not zero-copy: the window always
+/// presents its own canvas, into which it copies the pixels one by one with
+/// [`PixelStorage::get_pixel`].
+pub trait PixelStorage {
+    /// Gets the storage height.
+    fn height(&self) -> u16;
+
+    /// Gets the storage width.
+    fn width(&self) -> u16;
+
+    /// Gets a pixel color.
+    fn get_pixel(&self, y: u16, x: u16) -> Color;
+
+    /// Sets a pixel color.
+    fn set_pixel(&mut self, y: u16, x: u16, color: Color);
+}
+
+impl<S: PixelStorage + ?Sized> Draw for S {
+    fn height(&self) -> u16 {
+        PixelStorage::height(self)
+    }
+
+    fn width(&self) -> u16 {
+        PixelStorage::width(self)
+    }
+
+    fn get_pixel(&self, y: u16, x: u16) -> Color {
+        PixelStorage::get_pixel(self, y, x)
+    }
+
+    fn set_pixel(&mut self, y: u16, x: u16, color: Color) {
+        PixelStorage::set_pixel(self, y, x, color);
+    }
+}
+
+/// Pixels borrowed from a slice, row by row.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct PixelSlice<'a> {
+    height: u16,
+    width: u16,
+    pixels: &'a mut [Color],
+}
+
+impl<'a> PixelSlice<'a> {
+    /// Borrows `pixels` as `height` rows of `width` pixels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `pixels` is not `height * width`.
+    pub fn new(height: u16, width: u16, pixels: &'a mut [Color]) -> Self {
+        assert_eq!(
+            pixels.len(),
+            height as usize * width as usize,
+            "pixels length must be height * width"
+        );
+        PixelSlice {
+            height,
+            width,
+            pixels,
+        }
+    }
+}
+
+impl PixelStorage for PixelSlice<'_> {
+    fn height(&self) -> u16 {
+        self.height
+    }
+
+    fn width(&self) -> u16 {
+        self.width
+    }
+
+    fn get_pixel(&self, y: u16, x: u16) -> Color {
+        assert!(y < self.height && x < self.width, "pixel out of bounds");
+        self.pixels[y as usize * self.width as usize + x as usize]
+    }
+
+    fn set_pixel(&mut self, y: u16, x: u16, color: Color) {
+        assert!(y < self.height && x < self.width, "pixel out of bounds");
+        self.pixels[y as usize * self.width as usize + x as usize] = color;
+    }
+}
+
+/// Pixels stored as indices into a palette of up to 256 colors, one byte per pixel.
+///
+/// Changing a palette color changes all the pixels using it, e.g. for palette cycling effects.
+#[derive(Debug, Clone)]
+pub struct IndexedCanvas {
+    height: u16,
+    width: u16,
+    palette: Vec<Color>,
+    indices: Vec<u8>,
+    /// Lookup table of the palette, built by the first color set that is not in it.
+    quantizer: Option<Quantizer>,
+}
+
+impl IndexedCanvas {
+    /// Creates a canvas filled with the first color of `palette`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `palette` is empty or has more than 256 colors.
+    pub fn new(height: u16, width: u16, palette: Vec<Color>) -> Self {
+        assert!(
+            (1..=256).contains(&palette.len()),
+            "the palette must have between 1 and 256 colors"
+        );
+        IndexedCanvas {
+            height,
+            width,
+            palette,
+            indices: vec![0; height as usize * width as usize],
+            quantizer: None,
+        }
+    }
+
+    /// Creates a copy of `canvas` with a palette of up to `colors` colors, from 1 to 256,
+    /// extracted with [`color::median_cut`].
+    pub fn from_canvas(canvas: &Canvas, colors: usize) -> Self {
+        let mut palette = color::median_cut(canvas.pixels(), colors.clamp(1, 256));
+        if palette.is_empty() {
+            palette.push(Color::Black);
+        }
+        let quantizer = Quantizer::new(&palette);
+        IndexedCanvas {
+            height: canvas.height(),
+            width: canvas.width(),
+            indices: canvas
+                .pixels()
+                .iter()
+                .map(|&color| quantizer.nearest_index(color))
+                .collect(),
+            palette,
+            quantizer: Some(quantizer),
+        }
+    }
+
+    /// Gets the palette.
+    pub fn palette(&self) -> &[Color] {
+        &self.palette
+    }
+
+    /// Gets the palette mutably, to change its colors.
+    pub fn palette_mut(&mut self) -> &mut [Color] {
+        self.quantizer = None;
+        &mut self.palette
+    }
+
+    /// Gets the palette indices of the pixels, row by row.
+    pub fn indices(&self) -> &[u8] {
+        &self.indices
+    }
+
+    /// Gets the palette indices of the pixels mutably, row by row.
+    ///
+    /// Indices outside of the palette are drawn with its last color.
+    pub fn indices_mut(&mut self) -> &mut [u8] {
+        &mut self.indices
+    }
+
+    fn index(&self, y: u16, x: u16) -> usize {
+        assert!(y < self.height && x < self.width, "pixel out of bounds");
+        y as usize * self.width as usize + x as usize
+    }
+}
+
+impl PartialEq for IndexedCanvas {
+    fn eq(&self, other: &Self) -> bool {
+        (self.height, self.width, &self.palette, &self.indices)
+            == (other.height, other.width, &other.palette, &other.indices)
+    }
+}
+
+impl Eq for IndexedCanvas {}
+
+impl Hash for IndexedCanvas {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (self.height, self.width, &self.palette, &self.indices).hash(state);
+    }
+}
+
+impl PixelStorage for IndexedCanvas {
+    fn height(&self) -> u16 {
+        self.height
+    }
+
+    fn width(&self) -> u16 {
+        self.width
+    }
+
+    fn get_pixel(&self, y: u16, x: u16) -> Color {
+        let index = self.indices[self.index(y, x)] as usize;
+        self.palette[index.min(self.palette.len() - 1)]
+    }
+
+    /// Sets a pixel to the palette color nearest to `color`.
+    fn set_pixel(&mut self, y: u16, x: u16, color: Color) {
+        let index = self.index(y, x);
+        self.indices[index] = match self.palette.iter().position(|&entry| entry == color) {
+            Some(palette_index) => palette_index as u8,
+            None => self
+                .quantizer
+                .get_or_insert_with(|| Quantizer::new(&self.palette))
+                .nearest_index(color),
+        };
+    }
+}
+
+/// Pixel format of a [`PixelBuffer`], converted to colors when copied into the window with
+/// [`Window::redraw_from`].
+///
+/// The window itself is not generic over its pixel format, it only stores [`Color`]s.
+pub trait Pixel: Copy {
+    /// Converts the pixel to a color.
+    fn to_color(self) -> Color;
+
+    /// Converts a color to the nearest pixel.
+    fn from_color(color: Color) -> Self;
+}
+
+impl Pixel for Color {
+    fn to_color(self) -> Color {
+        self
+    }
+
+    fn from_color(color: Color) -> Self {
+        color
+    }
+}
+
+/// RGB values.
+impl Pixel for [u8; 3] {
+    fn to_color(self) -> Color {
+        color::from_rgb(self)
+    }
+
+    fn from_color(color: Color) -> Self {
+        color::to_rgb(color)
+    }
+}
+
+/// RGBA values, the alpha channel being ignored, and opaque once converted from a color.
+impl Pixel for [u8; 4] {
+    fn to_color(self) -> Color {
+        color::from_rgb([self[0], self[1], self[2]])
+    }
+
+    fn from_color(color: Color) -> Self {
+        let [r, g, b] = color::to_rgb(color);
+        [r, g, b, u8::MAX]
+    }
+}
+
+/// Luminance between 0, black, and 1, white.
+impl Pixel for f32 {
+    fn to_color(self) -> Color {
+        let value = (self.clamp(0., 1.) * f32::from(u8::MAX)).round() as u8;
+        color::from_rgb([value; 3])
+    }
+
+    fn from_color(color: Color) -> Self {
+        let [r, g, b] = color::to_rgb(color).map(f32::from);
+        (0.2126 * r + 0.7152 * g + 0.0722 * b) / f32::from(u8::MAX)
+    }
+}
+
+/// Monochrome pixel, white when set, e.g. for CHIP-8 emulators.
+impl Pixel for bool {
+    fn to_color(self) -> Color {
+        if self {
+            Color::White
+        } else {
+            Color::Black
+        }
+    }
+
+    fn from_color(color: Color) -> Self {
+        color::is_light(color)
+    }
+}
+
+/// Pixels stored in their native format `P`, row by row, e.g. the luminance of a simulation.
+///
+/// ```
+/// use winterm::PixelBuffer;
+///
+/// let mut buffer = PixelBuffer::new(2, 2, 0f32);
+/// buffer.pixels_mut()[3] = 1.;
+/// assert_eq!(buffer.get(1, 1), 1.);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PixelBuffer<P> {
+    height: u16,
+    width: u16,
+    pixels: Vec<P>,
+}
+
+impl<P: Pixel> PixelBuffer<P> {
+    /// Creates a buffer filled with `pixel`.
+    pub fn new(height: u16, width: u16, pixel: P) -> Self {
+        PixelBuffer {
+            height,
+            width,
+            pixels: vec![pixel; height as usize * width as usize],
+        }
+    }
+
+    /// Gets the buffer height.
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    /// Gets the buffer width.
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    /// Gets a pixel.
+    pub fn get(&self, y: u16, x: u16) -> P {
+        self.pixels[self.index(y, x)]
+    }
+
+    /// Sets a pixel.
+    pub fn set(&mut self, y: u16, x: u16, pixel: P) {
+        let index = self.index(y, x);
+        self.pixels[index] = pixel;
+    }
+
+    /// Gets the pixels, row by row.
+    pub fn pixels(&self) -> &[P] {
+        &self.pixels
+    }
+
+    /// Gets the pixels mutably, row by row.
+    pub fn pixels_mut(&mut self) -> &mut [P] {
+        &mut self.pixels
+    }
+
+    fn index(&self, y: u16, x: u16) -> usize {
+        assert!(y < self.height && x < self.width, "pixel out of bounds");
+        y as usize * self.width as usize + x as usize
+    }
+}
+
+impl<P: Pixel> PixelStorage for PixelBuffer<P> {
+    fn height(&self) -> u16 {
+        self.height
+    }
+
+    fn width(&self) -> u16 {
+        self.width
+    }
+
+    fn get_pixel(&self, y: u16, x: u16) -> Color {
+        self.get(y, x).to_color()
+    }
+
+    fn set_pixel(&mut self, y: u16, x: u16, color: Color) {
+        self.set(y, x, P::from_color(color));
+    }
+}
+
+impl Window {
+    /// Copies the pixels of `storage` that changed into the window, converted to colors, then
+    /// presents them, see [`Window::redraw`].
+    ///
+    /// It is a copy helper: the window is not generic over its storage and keeps its own canvas,
+    /// `storage` being read once per call.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the storage dimensions are not the window dimensions.
+    pub fn redraw_from<S: PixelStorage + ?Sized>(&mut self, storage: &S) -> Result<()> {
+        assert!(
+            storage.height() == self.height() && storage.width() == self.width(),
+            "storage dimensions must be the window dimensions"
+        );
+        self.set_from_fn(|y, x| storage.get_pixel(y, x));
+        self.redraw()
+    }
+}
@@ -0,0 +1,276 @@
+//! Streaming of the presented frames over TCP, to watch a window from another terminal.
+//!
+//! Each message starts with a kind byte, then the frame height and width as little endian `u16`.
+//! A full frame (kind 0) is followed by the RGB values of the pixels row by row.
+//! A diff (kind 1) is followed by the number of changed pixels as a little endian `u32`, then for
+//! each of them its `y` and `x` as little endian `u16` and its RGB values.
+
+use std::io::{self, ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+
+use crate::{color, Canvas, Color, Window};
+
+const FULL_FRAME: u8 = 0;
+const DIFF: u8 = 1;
+
+/// Frames a viewer can be behind before being disconnected, see [`Window::start_streaming`].
+const MAX_SKIPPED_FRAMES: u32 = 300;
+
+/// Connection of a viewer, written to without blocking.
+#[derive(Debug)]
+struct Viewer {
+    stream: TcpStream,
+    /// Message being sent, and how many of its bytes were written.
+    pending: Vec<u8>,
+    written: usize,
+    /// Frames skipped since the last message was queued.
+    skipped: u32,
+    /// Whether the viewer missed frames, the next one being sent in full.
+    needs_full_frame: bool,
+}
+
+impl Viewer {
+    /// Writes the pending message until the socket would block.
+    fn flush(&mut self) -> io::Result<()> {
+        while self.written < self.pending.len() {
+            match self.stream.write(&self.pending[self.written..]) {
+                Ok(0) => return Err(ErrorKind::WriteZero.into()),
+                Ok(written) => self.written += written,
+                Err(error) if error.kind() == ErrorKind::WouldBlock => return Ok(()),
+                Err(error) if error.kind() == ErrorKind::Interrupted => {}
+                Err(error) => return Err(error),
+            }
+        }
+        self.pending.clear();
+        self.written = 0;
+        Ok(())
+    }
+}
+
+/// Server side of the stream, sending each redrawn frame to the connected viewers.
+#[derive(Debug)]
+pub(crate) struct FrameStreamer {
+    listener: TcpListener,
+    viewers: Vec<Viewer>,
+    last_frame: Option<Canvas>,
+}
+
+impl FrameStreamer {
+    fn bind(address: impl ToSocketAddrs) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        listener.set_nonblocking(true)?;
+        Ok(FrameStreamer {
+            listener,
+            viewers: Vec::new(),
+            last_frame: None,
+        })
+    }
+
+    /// Sends `frame` to the viewers without blocking, new viewers and the ones that missed frames
+    /// receiving a full frame and the others a diff.
+    ///
+    /// Viewers still receiving a previous frame skip this one, and are dropped after
+    /// [`MAX_SKIPPED_FRAMES`] in a row, as are the ones whose connection failed.
+    pub(crate) fn send(&mut self, frame: &Canvas) {
+        while let Ok((stream, _)) = self.listener.accept() {
+            if stream.set_nonblocking(true).is_ok() && stream.set_nodelay(true).is_ok() {
+                self.viewers.push(Viewer {
+                    stream,
+                    pending: Vec::new(),
+                    written: 0,
+                    skipped: 0,
+                    needs_full_frame: true,
+                });
+            }
+        }
+        let mut diff = None;
+        let mut full_frame = None;
+        let last_frame = self.last_frame.as_ref();
+        self.viewers.retain_mut(|viewer| {
+            if viewer.flush().is_err() {
+                return false;
+            }
+            if !viewer.pending.is_empty() {
+                viewer.skipped += 1;
+                viewer.needs_full_frame = true;
+                return viewer.skipped <= MAX_SKIPPED_FRAMES;
+            }
+            let diff = match last_frame {
+                Some(last_frame) if !viewer.needs_full_frame => diff
+                    .get_or_insert_with(|| encode_diff(last_frame, frame))
+                    .as_ref(),
+                _ => None,
+            };
+            viewer.pending = match diff {
+                Some(diff) => diff.clone(),
+                None => full_frame
+                    .get_or_insert_with(|| encode_full_frame(frame))
+                    .clone(),
+            };
+            viewer.skipped = 0;
+            viewer.needs_full_frame = false;
+            viewer.flush().is_ok()
+        });
+        self.last_frame = Some(frame.clone());
+    }
+}
+
+fn encode_header(kind: u8, frame: &Canvas) -> Vec<u8> {
+    let mut bytes = vec![kind];
+    bytes.extend(frame.height().to_le_bytes());
+    bytes.extend(frame.width().to_le_bytes());
+    bytes
+}
+
+fn encode_full_frame(frame: &Canvas) -> Vec<u8> {
+    let mut bytes = encode_header(FULL_FRAME, frame);
+    bytes.extend(frame.as_rgb_bytes());
+    bytes
+}
+
+/// Encodes the changes between two frames, `None` if their dimensions differ.
+fn encode_diff(last_frame: &Canvas, frame: &Canvas) -> Option<Vec<u8>> {
+    if (last_frame.height(), last_frame.width()) != (frame.height(), frame.width()) {
+        return None;
+    }
+    let mut changes = Vec::new();
+    let mut count: u32 = 0;
+    for y in 0..frame.height() {
+        for x in 0..frame.width() {
+            let color = frame.get_pixel(y, x);
+            if color != last_frame.get_pixel(y, x) {
+                changes.extend(y.to_le_bytes());
+                changes.extend(x.to_le_bytes());
+                changes.extend(color::to_rgb(color));
+                count += 1;
+            }
+        }
+    }
+    let mut bytes = encode_header(DIFF, frame);
+    bytes.extend(count.to_le_bytes());
+    bytes.extend(changes);
+    Some(bytes)
+}
+
+/// Viewer side of the stream.
+#[derive(Debug)]
+pub struct FrameReceiver {
+    stream: TcpStream,
+    frame: Canvas,
+}
+
+impl FrameReceiver {
+    /// Connects to a window streaming its frames, see [`Window::start_streaming`].
+    pub fn connect(address: impl ToSocketAddrs) -> io::Result<Self> {
+        Ok(FrameReceiver {
+            stream: TcpStream::connect(address)?,
+            frame: Canvas::new(0, 0, Color::Black),
+        })
+    }
+
+    /// Waits for the next frame.
+    pub fn receive(&mut self) -> io::Result<&Canvas> {
+        let mut header = [0; 5];
+        self.stream.read_exact(&mut header)?;
+        let height = u16::from_le_bytes([header[1], header[2]]);
+        let width = u16::from_le_bytes([header[3], header[4]]);
+        match header[0] {
+            FULL_FRAME => {
+                let mut rgb = vec![0; height as usize * width as usize * 3];
+                self.stream.read_exact(&mut rgb)?;
+                self.frame = Canvas::new(height, width, Color::Black);
+                for (i, rgb) in rgb.chunks_exact(3).enumerate() {
+                    let y = (i / width as usize) as u16;
+                    let x = (i % width as usize) as u16;
+                    self.frame
+                        .set_pixel(y, x, color::from_rgb([rgb[0], rgb[1], rgb[2]]));
+                }
+            }
+            DIFF if (height, width) == (self.frame.height(), self.frame.width()) => {
+                let mut count = [0; 4];
+                self.stream.read_exact(&mut count)?;
+                let mut changes = vec![0; u32::from_le_bytes(count) as usize * 7];
+                self.stream.read_exact(&mut changes)?;
+                for change in changes.chunks_exact(7) {
+                    let y = u16::from_le_bytes([change[0], change[1]]);
+                    let x = u16::from_le_bytes([change[2], change[3]]);
+                    if y < height && x < width {
+                        self.frame.set_pixel(
+                            y,
+                            x,
+                            color::from_rgb([change[4], change[5], change[6]]),
+                        );
+                    }
+                }
+            }
+            _ => return Err(io::Error::new(ErrorKind::InvalidData, "invalid message")),
+        }
+        Ok(&self.frame)
+    }
+}
+
+impl Window {
+    /// Starts listening on `address` for viewers, each redrawn frame being sent to them.
+    ///
+    /// Sending does not block the redraws: a viewer still receiving a frame skips the next ones,
+    /// then receives a full frame, and is disconnected after 300 skipped frames in a row.
+    pub fn start_streaming(&mut self, address: impl ToSocketAddrs) -> io::Result<()> {
+        self.streamer = Some(FrameStreamer::bind(address)?);
+        Ok(())
+    }
+
+    /// Stops streaming and disconnects the viewers.
+    pub fn stop_streaming(&mut self) {
+        self.streamer = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpStream;
+    use std::time::{Duration, Instant};
+
+    use crate::Color;
+
+    use super::{FrameReceiver, FrameStreamer, MAX_SKIPPED_FRAMES};
+    use crate::Canvas;
+
+    #[test]
+    fn viewer_receives_full_frames_then_diffs() {
+        let mut streamer = FrameStreamer::bind("127.0.0.1:0").unwrap();
+        let mut receiver = FrameReceiver::connect(streamer.listener.local_addr().unwrap()).unwrap();
+        let mut frame = Canvas::new(2, 3, Color::Rgb { r: 0, g: 0, b: 0 });
+        // The connection is accepted by the first send after it is established.
+        while streamer.viewers.is_empty() {
+            streamer.send(&frame);
+        }
+        assert_eq!(receiver.receive().unwrap(), &frame);
+        frame.set_pixel(1, 2, Color::Rgb { r: 1, g: 2, b: 3 });
+        streamer.send(&frame);
+        assert_eq!(receiver.receive().unwrap(), &frame);
+    }
+
+    #[test]
+    fn stalled_viewer_does_not_block_and_is_dropped() {
+        let mut streamer = FrameStreamer::bind("127.0.0.1:0").unwrap();
+        let _stalled = TcpStream::connect(streamer.listener.local_addr().unwrap()).unwrap();
+        let start = Instant::now();
+        let mut connected = false;
+        for i in 0..MAX_SKIPPED_FRAMES * 2 {
+            // Every pixel changes, for the diffs to fill the socket buffers.
+            streamer.send(&Canvas::new(256, 256, color(i)));
+            connected |= !streamer.viewers.is_empty();
+        }
+        assert!(connected);
+        assert!(streamer.viewers.is_empty());
+        assert!(start.elapsed() < Duration::from_secs(30));
+    }
+
+    fn color(i: u32) -> Color {
+        Color::Rgb {
+            r: i as u8,
+            g: (i >> 8) as u8,
+            b: 0,
+        }
+    }
+}
@@ -0,0 +1,98 @@
+//! Comparison of frames, for visual regression tests of drawing code.
+//!
+//! ```
+//! use winterm::Color;
+//! use winterm::testing::image_diff;
+//! use winterm::{Canvas, Draw, Rect};
+//!
+//! let mut expected = Canvas::new(8, 8, Color::Black);
+//! expected.fill_rect(Rect::new(2, 2, 4, 4), Color::Rgb { r: 255, g: 0, b: 0 });
+//! let mut actual = Canvas::new(8, 8, Color::Black);
+//! actual.fill_rect(Rect::new(2, 2, 4, 4), Color::Rgb { r: 250, g: 0, b: 0 });
+//!
+//! let report = image_diff(&expected, &actual, 8);
+//! assert!(report.is_match(), "{} pixels differ", report.different_pixels());
+//! ```
+
+use std::cmp;
+
+use crate::{color, Canvas, Color};
+
+/// Color of the differing pixels in [`DiffReport::diff`].
+const DIFFERENT: Color = Color::Rgb { r: 255, g: 0, b: 0 };
+/// Color of the pixels in [`DiffReport::diff`] outside of one of the canvases.
+const MISSING: Color = Color::Rgb {
+    r: 255,
+    g: 0,
+    b: 255,
+};
+
+/// Result of [`image_diff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffReport {
+    different_pixels: usize,
+    max_difference: u8,
+    diff: Canvas,
+}
+
+impl DiffReport {
+    /// Checks if no pixel differs by more than the tolerance, and if the canvases have the same
+    /// size.
+    pub fn is_match(&self) -> bool {
+        self.different_pixels == 0
+    }
+
+    /// Gets the number of pixels differing by more than the tolerance, including the ones outside
+    /// of one of the canvases.
+    pub fn different_pixels(&self) -> usize {
+        self.different_pixels
+    }
+
+    /// Gets the largest difference of a RGB channel between two pixels, ignoring the pixels
+    /// outside of one of the canvases.
+    pub fn max_difference(&self) -> u8 {
+        self.max_difference
+    }
+
+    /// Gets the rendered difference, as large as the largest canvas: matching pixels are dimmed
+    /// grey, differing ones red, and the ones outside of one of the canvases magenta.
+    pub fn diff(&self) -> &Canvas {
+        &self.diff
+    }
+}
+
+/// Compares `a` and `b` pixel by pixel in RGB, two pixels matching if none of their channels
+/// differ by more than `tolerance`.
+pub fn image_diff(a: &Canvas, b: &Canvas, tolerance: u8) -> DiffReport {
+    let height = cmp::max(a.height(), b.height());
+    let width = cmp::max(a.width(), b.width());
+    let mut different_pixels = 0;
+    let mut max_difference = 0;
+    let diff = Canvas::from_fn(height, width, |y, x| {
+        let inside = |canvas: &Canvas| y < canvas.height() && x < canvas.width();
+        if !inside(a) || !inside(b) {
+            different_pixels += 1;
+            return MISSING;
+        }
+        let (rgb_a, rgb_b) = (
+            color::to_rgb(a.get_pixel(y, x)),
+            color::to_rgb(b.get_pixel(y, x)),
+        );
+        let difference = (0..3)
+            .map(|channel| rgb_a[channel].abs_diff(rgb_b[channel]))
+            .max()
+            .unwrap_or(0);
+        max_difference = cmp::max(max_difference, difference);
+        if difference > tolerance {
+            different_pixels += 1;
+            return DIFFERENT;
+        }
+        let luma = (u16::from(rgb_a[0]) + u16::from(rgb_a[1]) + u16::from(rgb_a[2])) / 3;
+        color::from_rgb([(luma / 4) as u8; 3])
+    });
+    DiffReport {
+        different_pixels,
+        max_difference,
+        diff,
+    }
+}
@@ -0,0 +1,115 @@
+//! Turtle graphics, drawing lines by moving a pen around.
+//!
+//! ```no_run
+//! use winterm::turtle::Turtle;
+//! use winterm::Window;
+//!
+//! let mut window = Window::new(40, 40)?;
+//! let mut turtle = Turtle::new(30., 10.);
+//! for _ in 0..4 {
+//!     turtle.forward(&mut window, 20.);
+//!     turtle.right(90.);
+//! }
+//! window.redraw()?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use crate::{Color, Draw};
+
+/// Pen moving in straight lines, drawing its path while it is down.
+///
+/// The heading is in degrees, `0` facing up and increasing clockwise.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Turtle {
+    y: f32,
+    x: f32,
+    heading: f32,
+    pen_down: bool,
+    color: Color,
+}
+
+impl Turtle {
+    /// Creates a white turtle at `y` and `x`, facing up with its pen down.
+    pub fn new(y: f32, x: f32) -> Self {
+        Turtle {
+            y,
+            x,
+            heading: 0.,
+            pen_down: true,
+            color: Color::White,
+        }
+    }
+
+    /// Gets the position, as `y` and `x`.
+    pub fn position(&self) -> (f32, f32) {
+        (self.y, self.x)
+    }
+
+    /// Gets the heading, in degrees.
+    pub fn heading(&self) -> f32 {
+        self.heading
+    }
+
+    /// Checks if the pen is down.
+    pub fn is_pen_down(&self) -> bool {
+        self.pen_down
+    }
+
+    /// Moves forward by `distance` pixels, drawing into `target` if the pen is down.
+    pub fn forward<D: Draw + ?Sized>(&mut self, target: &mut D, distance: f32) {
+        let radians = self.heading.to_radians();
+        let y = self.y - distance * radians.cos();
+        let x = self.x + distance * radians.sin();
+        self.go_to(target, y, x);
+    }
+
+    /// Moves backward by `distance` pixels, drawing into `target` if the pen is down.
+    pub fn backward<D: Draw + ?Sized>(&mut self, target: &mut D, distance: f32) {
+        self.forward(target, -distance);
+    }
+
+    /// Moves to `y` and `x`, drawing into `target` if the pen is down.
+    pub fn go_to<D: Draw + ?Sized>(&mut self, target: &mut D, y: f32, x: f32) {
+        if self.pen_down {
+            target.draw_line(
+                self.y.floor() as i32,
+                self.x.floor() as i32,
+                y.floor() as i32,
+                x.floor() as i32,
+                self.color,
+            );
+        }
+        self.y = y;
+        self.x = x;
+    }
+
+    /// Turns left by `degrees`.
+    pub fn left(&mut self, degrees: f32) {
+        self.set_heading(self.heading - degrees);
+    }
+
+    /// Turns right by `degrees`.
+    pub fn right(&mut self, degrees: f32) {
+        self.set_heading(self.heading + degrees);
+    }
+
+    /// Sets the heading, in degrees.
+    pub fn set_heading(&mut self, degrees: f32) {
+        self.heading = degrees.rem_euclid(360.);
+    }
+
+    /// Lifts the pen, the next moves not drawing.
+    pub fn pen_up(&mut self) {
+        self.pen_down = false;
+    }
+
+    /// Puts the pen down, the next moves drawing.
+    pub fn pen_down(&mut self) {
+        self.pen_down = true;
+    }
+
+    /// Sets the pen color.
+    pub fn set_color(&mut self, color: Color) {
+        self.color = color;
+    }
+}
@@ -0,0 +1,293 @@
+//! Colors, keys and modifiers of the API, converted from and to the types of crossterm at the
+//! terminal boundary when the `crossterm` feature is enabled.
+
+use std::ops::{BitOr, BitOrAssign};
+
+#[cfg(feature = "crossterm")]
+use crossterm::event::KeyModifiers;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Color of a pixel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Color {
+    /// Default color of the terminal.
+    Reset,
+    Black,
+    DarkGrey,
+    Red,
+    DarkRed,
+    Green,
+    DarkGreen,
+    Yellow,
+    DarkYellow,
+    Blue,
+    DarkBlue,
+    Magenta,
+    DarkMagenta,
+    Cyan,
+    DarkCyan,
+    White,
+    Grey,
+    /// True color, approximated by terminals without support.
+    Rgb {
+        r: u8,
+        g: u8,
+        b: u8,
+    },
+    /// Color of the 256 colors palette.
+    AnsiValue(u8),
+}
+
+/// Key of the keyboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub enum Key {
+    Backspace,
+    Enter,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Tab,
+    /// Shift and Tab.
+    BackTab,
+    Delete,
+    Insert,
+    /// Function key, e.g. `F(1)` for F1.
+    F(u8),
+    Char(char),
+    Null,
+    Esc,
+    CapsLock,
+    ScrollLock,
+    NumLock,
+    PrintScreen,
+    Pause,
+    Menu,
+    /// Center key of the keypad, 5 without Num Lock.
+    KeypadBegin,
+    /// Media or modifier key, reported by few terminals, converted back to `KeyCode::Null`.
+    Other,
+}
+
+/// Modifier keys held while a key is pressed, combined with `|`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Modifiers(u8);
+
+impl Modifiers {
+    pub const NONE: Self = Self(0);
+    pub const SHIFT: Self = Self(1);
+    pub const CONTROL: Self = Self(1 << 1);
+    pub const ALT: Self = Self(1 << 2);
+    pub const SUPER: Self = Self(1 << 3);
+    pub const HYPER: Self = Self(1 << 4);
+    pub const META: Self = Self(1 << 5);
+
+    /// Returns `true` if all the modifiers of `other` are held.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns `true` if no modifier is held.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl BitOr for Modifiers {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Modifiers {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// Pairs of the modifiers and their crossterm counterparts.
+#[cfg(feature = "crossterm")]
+const MODIFIERS: [(Modifiers, KeyModifiers); 6] = [
+    (Modifiers::SHIFT, KeyModifiers::SHIFT),
+    (Modifiers::CONTROL, KeyModifiers::CONTROL),
+    (Modifiers::ALT, KeyModifiers::ALT),
+    (Modifiers::SUPER, KeyModifiers::SUPER),
+    (Modifiers::HYPER, KeyModifiers::HYPER),
+    (Modifiers::META, KeyModifiers::META),
+];
+
+#[cfg(feature = "crossterm")]
+impl From<KeyModifiers> for Modifiers {
+    fn from(key_modifiers: KeyModifiers) -> Self {
+        MODIFIERS
+            .iter()
+            .filter(|(_, key_modifier)| key_modifiers.contains(*key_modifier))
+            .fold(Modifiers::NONE, |modifiers, (modifier, _)| {
+                modifiers | *modifier
+            })
+    }
+}
+
+#[cfg(feature = "crossterm")]
+impl From<Modifiers> for KeyModifiers {
+    fn from(modifiers: Modifiers) -> Self {
+        MODIFIERS
+            .iter()
+            .filter(|(modifier, _)| modifiers.contains(*modifier))
+            .fold(KeyModifiers::NONE, |key_modifiers, (_, key_modifier)| {
+                key_modifiers | *key_modifier
+            })
+    }
+}
+
+#[cfg(feature = "crossterm")]
+impl From<crossterm::style::Color> for Color {
+    fn from(color: crossterm::style::Color) -> Self {
+        match color {
+            crossterm::style::Color::Reset => Color::Reset,
+            crossterm::style::Color::Black => Color::Black,
+            crossterm::style::Color::DarkGrey => Color::DarkGrey,
+            crossterm::style::Color::Red => Color::Red,
+            crossterm::style::Color::DarkRed => Color::DarkRed,
+            crossterm::style::Color::Green => Color::Green,
+            crossterm::style::Color::DarkGreen => Color::DarkGreen,
+            crossterm::style::Color::Yellow => Color::Yellow,
+            crossterm::style::Color::DarkYellow => Color::DarkYellow,
+            crossterm::style::Color::Blue => Color::Blue,
+            crossterm::style::Color::DarkBlue => Color::DarkBlue,
+            crossterm::style::Color::Magenta => Color::Magenta,
+            crossterm::style::Color::DarkMagenta => Color::DarkMagenta,
+            crossterm::style::Color::Cyan => Color::Cyan,
+            crossterm::style::Color::DarkCyan => Color::DarkCyan,
+            crossterm::style::Color::White => Color::White,
+            crossterm::style::Color::Grey => Color::Grey,
+            crossterm::style::Color::Rgb { r, g, b } => Color::Rgb { r, g, b },
+            crossterm::style::Color::AnsiValue(value) => Color::AnsiValue(value),
+        }
+    }
+}
+
+#[cfg(feature = "crossterm")]
+impl From<Color> for crossterm::style::Color {
+    fn from(color: Color) -> Self {
+        match color {
+            Color::Reset => crossterm::style::Color::Reset,
+            Color::Black => crossterm::style::Color::Black,
+            Color::DarkGrey => crossterm::style::Color::DarkGrey,
+            Color::Red => crossterm::style::Color::Red,
+            Color::DarkRed => crossterm::style::Color::DarkRed,
+            Color::Green => crossterm::style::Color::Green,
+            Color::DarkGreen => crossterm::style::Color::DarkGreen,
+            Color::Yellow => crossterm::style::Color::Yellow,
+            Color::DarkYellow => crossterm::style::Color::DarkYellow,
+            Color::Blue => crossterm::style::Color::Blue,
+            Color::DarkBlue => crossterm::style::Color::DarkBlue,
+            Color::Magenta => crossterm::style::Color::Magenta,
+            Color::DarkMagenta => crossterm::style::Color::DarkMagenta,
+            Color::Cyan => crossterm::style::Color::Cyan,
+            Color::DarkCyan => crossterm::style::Color::DarkCyan,
+            Color::White => crossterm::style::Color::White,
+            Color::Grey => crossterm::style::Color::Grey,
+            Color::Rgb { r, g, b } => crossterm::style::Color::Rgb { r, g, b },
+            Color::AnsiValue(value) => crossterm::style::Color::AnsiValue(value),
+        }
+    }
+}
+
+#[cfg(feature = "crossterm")]
+impl From<crossterm::event::KeyCode> for Key {
+    fn from(code: crossterm::event::KeyCode) -> Self {
+        match code {
+            crossterm::event::KeyCode::Backspace => Key::Backspace,
+            crossterm::event::KeyCode::Enter => Key::Enter,
+            crossterm::event::KeyCode::Left => Key::Left,
+            crossterm::event::KeyCode::Right => Key::Right,
+            crossterm::event::KeyCode::Up => Key::Up,
+            crossterm::event::KeyCode::Down => Key::Down,
+            crossterm::event::KeyCode::Home => Key::Home,
+            crossterm::event::KeyCode::End => Key::End,
+            crossterm::event::KeyCode::PageUp => Key::PageUp,
+            crossterm::event::KeyCode::PageDown => Key::PageDown,
+            crossterm::event::KeyCode::Tab => Key::Tab,
+            crossterm::event::KeyCode::BackTab => Key::BackTab,
+            crossterm::event::KeyCode::Delete => Key::Delete,
+            crossterm::event::KeyCode::Insert => Key::Insert,
+            crossterm::event::KeyCode::F(number) => Key::F(number),
+            crossterm::event::KeyCode::Char(char) => Key::Char(char),
+            crossterm::event::KeyCode::Null => Key::Null,
+            crossterm::event::KeyCode::Esc => Key::Esc,
+            crossterm::event::KeyCode::CapsLock => Key::CapsLock,
+            crossterm::event::KeyCode::ScrollLock => Key::ScrollLock,
+            crossterm::event::KeyCode::NumLock => Key::NumLock,
+            crossterm::event::KeyCode::PrintScreen => Key::PrintScreen,
+            crossterm::event::KeyCode::Pause => Key::Pause,
+            crossterm::event::KeyCode::Menu => Key::Menu,
+            crossterm::event::KeyCode::KeypadBegin => Key::KeypadBegin,
+            crossterm::event::KeyCode::Media(_) | crossterm::event::KeyCode::Modifier(_) => {
+                Key::Other
+            }
+        }
+    }
+}
+
+#[cfg(feature = "crossterm")]
+impl From<Key> for crossterm::event::KeyCode {
+    fn from(key: Key) -> Self {
+        match key {
+            Key::Backspace => crossterm::event::KeyCode::Backspace,
+            Key::Enter => crossterm::event::KeyCode::Enter,
+            Key::Left => crossterm::event::KeyCode::Left,
+            Key::Right => crossterm::event::KeyCode::Right,
+            Key::Up => crossterm::event::KeyCode::Up,
+            Key::Down => crossterm::event::KeyCode::Down,
+            Key::Home => crossterm::event::KeyCode::Home,
+            Key::End => crossterm::event::KeyCode::End,
+            Key::PageUp => crossterm::event::KeyCode::PageUp,
+            Key::PageDown => crossterm::event::KeyCode::PageDown,
+            Key::Tab => crossterm::event::KeyCode::Tab,
+            Key::BackTab => crossterm::event::KeyCode::BackTab,
+            Key::Delete => crossterm::event::KeyCode::Delete,
+            Key::Insert => crossterm::event::KeyCode::Insert,
+            Key::F(number) => crossterm::event::KeyCode::F(number),
+            Key::Char(char) => crossterm::event::KeyCode::Char(char),
+            Key::Null | Key::Other => crossterm::event::KeyCode::Null,
+            Key::Esc => crossterm::event::KeyCode::Esc,
+            Key::CapsLock => crossterm::event::KeyCode::CapsLock,
+            Key::ScrollLock => crossterm::event::KeyCode::ScrollLock,
+            Key::NumLock => crossterm::event::KeyCode::NumLock,
+            Key::PrintScreen => crossterm::event::KeyCode::PrintScreen,
+            Key::Pause => crossterm::event::KeyCode::Pause,
+            Key::Menu => crossterm::event::KeyCode::Menu,
+            Key::KeypadBegin => crossterm::event::KeyCode::KeypadBegin,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "crossterm"))]
+mod tests {
+    #[cfg(feature = "crossterm")]
+    use crossterm::event::KeyModifiers;
+
+    use super::Modifiers;
+
+    #[test]
+    fn modifiers_convert_both_ways() {
+        let modifiers = Modifiers::CONTROL | Modifiers::SHIFT;
+        let key_modifiers = KeyModifiers::from(modifiers);
+        assert_eq!(key_modifiers, KeyModifiers::CONTROL | KeyModifiers::SHIFT);
+        assert_eq!(Modifiers::from(key_modifiers), modifiers);
+        assert!(modifiers.contains(Modifiers::SHIFT));
+        assert!(!modifiers.contains(Modifiers::ALT));
+    }
+}
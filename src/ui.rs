@@ -0,0 +1,309 @@
+//! Immediate mode widgets, such as buttons, checkboxes and sliders, for settings screens.
+//!
+//! The widgets are declared, drawn and handled by the same call every frame, their state being
+//! owned by the application. They are driven by the mouse, once enabled with
+//! [`Window::set_mouse_capture`], and by the keyboard: Tab and Shift+Tab move the focus, Enter
+//! and Space activate the focused widget, and the Left/Right arrows move the focused slider.
+//!
+//! ```no_run
+//! use winterm::ui::Ui;
+//! use winterm::{Rect, Window};
+//!
+//! # let mut window = Window::new(36, 64)?;
+//! window.set_mouse_capture(true)?;
+//! let mut ui = Ui::new();
+//! let (mut sound, mut volume) = (true, 0.5);
+//! loop {
+//!     window.poll_events()?;
+//!     let mut frame = ui.begin(&mut window);
+//!     frame.checkbox(2, 2, "SOUND", &mut sound);
+//!     frame.slider(Rect::new(10, 2, 5, 40), &mut volume, 0.0..=1.0);
+//!     if frame.button(Rect::new(20, 2, 9, 24), "QUIT") {
+//!         break;
+//!     }
+//!     drop(frame);
+//!     window.redraw()?;
+//! }
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::ops::RangeInclusive;
+
+use crate::input::{Event, MouseButton, MouseEventKind};
+use crate::{font, Color, Draw, Key, Rect, Window};
+
+/// Part of the range of a slider moved by each Left/Right arrow press.
+const SLIDER_STEP: f32 = 0.05;
+
+/// Focus and drag state of the widgets, kept between frames.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ui {
+    focused: Option<usize>,
+    dragged: Option<usize>,
+    widgets: usize,
+    text_color: Color,
+    background_color: Color,
+    hovered_color: Color,
+    accent_color: Color,
+}
+
+impl Default for Ui {
+    fn default() -> Self {
+        Ui::new()
+    }
+}
+
+impl Ui {
+    /// Creates a state where nothing is focused, the widgets being white on dark grey with
+    /// yellow accents.
+    pub fn new() -> Self {
+        Ui {
+            focused: None,
+            dragged: None,
+            widgets: 0,
+            text_color: Color::White,
+            background_color: Color::DarkGrey,
+            hovered_color: Color::Grey,
+            accent_color: Color::Yellow,
+        }
+    }
+
+    /// Sets the color of the labels.
+    pub fn text_color(mut self, color: Color) -> Self {
+        self.text_color = color;
+        self
+    }
+
+    /// Sets the color of the buttons, checkboxes and slider tracks.
+    pub fn background_color(mut self, color: Color) -> Self {
+        self.background_color = color;
+        self
+    }
+
+    /// Sets the background color of the widgets under the mouse.
+    pub fn hovered_color(mut self, color: Color) -> Self {
+        self.hovered_color = color;
+        self
+    }
+
+    /// Sets the color of the focus outline, the checkmarks and the slider fills.
+    pub fn accent_color(mut self, color: Color) -> Self {
+        self.accent_color = color;
+        self
+    }
+
+    /// Starts the widgets of a frame, reading the input of the last [`Window::poll_events`].
+    ///
+    /// The widgets must be declared in the same order every frame for the focus to stay on the
+    /// same widget.
+    pub fn begin<'a>(&'a mut self, window: &'a mut Window) -> UiFrame<'a> {
+        let mut input = Input::default();
+        let mut focus_moves = 0;
+        for event in &window.last_events {
+            match event {
+                Event::Mouse(mouse_event) => {
+                    let position = window.cell_to_pixel(mouse_event.column, mouse_event.row);
+                    match mouse_event.kind {
+                        MouseEventKind::Down(MouseButton::Left) => {
+                            input.press = input.press.or(position);
+                            input.pointer = position;
+                        }
+                        MouseEventKind::Drag(MouseButton::Left) => input.pointer = position,
+                        MouseEventKind::Up(MouseButton::Left) => input.released = true,
+                        _ => {}
+                    }
+                }
+                Event::Key(key_event) => match key_event.code {
+                    Key::Tab => focus_moves += 1,
+                    Key::BackTab => focus_moves -= 1,
+                    Key::Enter | Key::Char(' ') => input.activate = true,
+                    Key::Left => input.steps -= 1,
+                    Key::Right => input.steps += 1,
+                    _ => {}
+                },
+                _ => {}
+            }
+        }
+        if focus_moves != 0 && self.widgets > 0 {
+            let widgets = self.widgets as i64;
+            let focused = match self.focused {
+                Some(focused) => focused as i64 + focus_moves,
+                None if focus_moves > 0 => focus_moves - 1,
+                None => focus_moves,
+            };
+            self.focused = Some(focused.rem_euclid(widgets) as usize);
+        }
+        if input.press.is_some() {
+            self.dragged = None;
+        }
+        input.hover = window.mouse_position();
+        UiFrame {
+            ui: self,
+            window,
+            input,
+            next_id: 0,
+        }
+    }
+}
+
+/// Input read by [`Ui::begin`], in pixels.
+#[derive(Debug, Default)]
+struct Input {
+    /// First left button press.
+    press: Option<(u16, u16)>,
+    /// Last left button press or drag.
+    pointer: Option<(u16, u16)>,
+    released: bool,
+    hover: Option<(u16, u16)>,
+    activate: bool,
+    steps: i32,
+}
+
+/// Widgets of a frame, started with [`Ui::begin`].
+#[derive(Debug)]
+pub struct UiFrame<'a> {
+    ui: &'a mut Ui,
+    window: &'a mut Window,
+    input: Input,
+    next_id: usize,
+}
+
+impl UiFrame<'_> {
+    /// Draws a button with a centered label, returning `true` if it was clicked or activated.
+    pub fn button(&mut self, rect: Rect, label: &str) -> bool {
+        let id = self.next_id();
+        let pressed = self.input.press.is_some_and(|(y, x)| rect.contains(y, x));
+        if pressed {
+            self.ui.focused = Some(id);
+        }
+        let focused = self.ui.focused == Some(id);
+        let background = self.background(rect);
+        self.window.fill_rect(rect, background);
+        if focused {
+            self.window.draw_rect(rect, self.ui.accent_color);
+        }
+        let (height, width) = font::text_size(label);
+        self.window.draw_text(
+            rect.y + rect.height.saturating_sub(height) / 2,
+            rect.x + rect.width.saturating_sub(width) / 2,
+            label,
+            self.ui.text_color,
+        );
+        pressed || (focused && self.input.activate)
+    }
+
+    /// Draws a checkbox followed by its label, toggling `checked` if it was clicked or activated.
+    ///
+    /// Returns `true` if `checked` changed.
+    pub fn checkbox(&mut self, y: u16, x: u16, label: &str, checked: &mut bool) -> bool {
+        let id = self.next_id();
+        let size = font::GLYPH_HEIGHT + 2;
+        let (_, label_width) = font::text_size(label);
+        let rect = Rect::new(y, x, size, size + font::GLYPH_WIDTH + label_width);
+        let pressed = self.input.press.is_some_and(|(y, x)| rect.contains(y, x));
+        if pressed {
+            self.ui.focused = Some(id);
+        }
+        let focused = self.ui.focused == Some(id);
+        let toggled = pressed || (focused && self.input.activate);
+        if toggled {
+            *checked = !*checked;
+        }
+        let box_rect = Rect::new(y, x, size, size);
+        let background = self.background(rect);
+        self.window.fill_rect(box_rect, background);
+        if focused {
+            self.window.draw_rect(box_rect, self.ui.accent_color);
+        }
+        if *checked {
+            self.window.fill_rect(
+                Rect::new(y + 2, x + 2, size - 4, size - 4),
+                self.ui.accent_color,
+            );
+        }
+        self.window.draw_text(
+            y + 1,
+            x + size + font::GLYPH_WIDTH,
+            label,
+            self.ui.text_color,
+        );
+        toggled
+    }
+
+    /// Draws a horizontal slider of `value` within `range`, moved by dragging it or with the
+    /// Left/Right arrows once focused.
+    ///
+    /// Returns `true` if `value` changed.
+    pub fn slider(&mut self, rect: Rect, value: &mut f32, range: RangeInclusive<f32>) -> bool {
+        let id = self.next_id();
+        if self.input.press.is_some_and(|(y, x)| rect.contains(y, x)) {
+            self.ui.focused = Some(id);
+            self.ui.dragged = Some(id);
+        }
+        let (start, end) = (*range.start(), *range.end());
+        let previous = *value;
+        if self.ui.dragged == Some(id) {
+            if let Some((_, x)) = self.input.pointer {
+                let ratio = f32::from(x.saturating_sub(rect.x))
+                    / f32::from(rect.width.saturating_sub(1).max(1));
+                *value = start + (end - start) * ratio.clamp(0., 1.);
+            }
+            if self.input.released {
+                self.ui.dragged = None;
+            }
+        }
+        let focused = self.ui.focused == Some(id);
+        if focused && self.input.steps != 0 {
+            *value += (end - start) * SLIDER_STEP * self.input.steps as f32;
+        }
+        *value = value.clamp(start.min(end), start.max(end));
+        let background = self.background(rect);
+        self.window.fill_rect(rect, background);
+        let ratio = if end == start {
+            0.
+        } else {
+            (*value - start) / (end - start)
+        };
+        let filled = (ratio * f32::from(rect.width)).round() as u16;
+        self.window.fill_rect(
+            Rect::new(
+                rect.y + rect.height / 3,
+                rect.x,
+                rect.height.div_ceil(3),
+                filled,
+            ),
+            self.ui.accent_color,
+        );
+        if focused {
+            self.window.draw_rect(rect, self.ui.accent_color);
+        }
+        *value != previous
+    }
+
+    fn next_id(&mut self) -> usize {
+        self.next_id += 1;
+        self.next_id - 1
+    }
+
+    /// Gets the background color of a widget, depending on whether the mouse is over it.
+    fn background(&self, rect: Rect) -> Color {
+        if self.input.hover.is_some_and(|(y, x)| rect.contains(y, x)) {
+            self.ui.hovered_color
+        } else {
+            self.ui.background_color
+        }
+    }
+}
+
+impl Drop for UiFrame<'_> {
+    fn drop(&mut self) {
+        self.ui.widgets = self.next_id;
+        if self
+            .ui
+            .focused
+            .is_some_and(|focused| focused >= self.next_id)
+        {
+            self.ui.focused = None;
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use std::collections::VecDeque;
+
+use crate::{Canvas, Rect, Window};
+
+/// Number of undo states kept by default.
+const DEFAULT_UNDO_LIMIT: usize = 100;
+
+/// Snapshots of the pixels, see [`Window::push_undo_state`].
+#[derive(Debug)]
+pub(crate) struct UndoStack {
+    /// Newest last.
+    undo: VecDeque<Canvas>,
+    redo: Vec<Canvas>,
+    limit: usize,
+}
+
+impl Default for UndoStack {
+    fn default() -> Self {
+        UndoStack {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            limit: DEFAULT_UNDO_LIMIT,
+        }
+    }
+}
+
+impl UndoStack {
+    /// Pushes an undo state, dropping the oldest ones beyond the limit.
+    fn push_undo(&mut self, state: Canvas) {
+        self.undo.push_back(state);
+        while self.undo.len() > self.limit {
+            self.undo.pop_front();
+        }
+    }
+}
+
+impl Window {
+    /// Saves the pixels, to be restored by [`Window::undo`], e.g. before each stroke of a
+    /// drawing tool.
+    ///
+    /// The states undone since the last push can no longer be redone.
+    ///
+    /// ```
+    /// use winterm::Color;
+    /// # use winterm::Window;
+    ///
+    /// # let mut window = Window::new(9, 16)?;
+    /// window.push_undo_state();
+    /// window.set_pixel(0, 0, Color::Red);
+    /// window.undo();
+    /// assert_eq!(window.get_pixel(0, 0), Color::Black);
+    /// window.redo();
+    /// assert_eq!(window.get_pixel(0, 0), Color::Red);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn push_undo_state(&mut self) {
+        self.undo_stack.redo.clear();
+        if self.undo_stack.limit > 0 {
+            self.undo_stack.push_undo(self.pixels.clone());
+        }
+    }
+
+    /// Restores the pixels saved by the last [`Window::push_undo_state`], returning `false` if
+    /// there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        let Some(state) = self.undo_stack.undo.pop_back() else {
+            return false;
+        };
+        let current = std::mem::replace(&mut self.pixels, state);
+        self.undo_stack.redo.push(current);
+        self.mark_dirty(Rect::new(0, 0, self.height(), self.width()));
+        true
+    }
+
+    /// Restores the pixels replaced by the last [`Window::undo`], returning `false` if there is
+    /// nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        let Some(state) = self.undo_stack.redo.pop() else {
+            return false;
+        };
+        let current = std::mem::replace(&mut self.pixels, state);
+        self.undo_stack.push_undo(current);
+        self.mark_dirty(Rect::new(0, 0, self.height(), self.width()));
+        true
+    }
+
+    /// Sets the number of states kept by [`Window::push_undo_state`] and [`Window::undo`], 100
+    /// by default, dropping the oldest undo states and the furthest redo ones if less are kept.
+    pub fn set_undo_limit(&mut self, states: usize) {
+        self.undo_stack.limit = states;
+        let excess = self.undo_stack.undo.len().saturating_sub(states);
+        self.undo_stack.undo.drain(..excess);
+        let excess = self.undo_stack.redo.len().saturating_sub(states);
+        self.undo_stack.redo.drain(..excess);
+    }
+
+    /// Checks if [`Window::undo`] would restore a state.
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.undo.is_empty()
+    }
+
+    /// Checks if [`Window::redo`] would restore a state.
+    pub fn can_redo(&self) -> bool {
+        !self.undo_stack.redo.is_empty()
+    }
+
+    /// Drops the undo and redo states.
+    pub fn clear_undo_history(&mut self) {
+        self.undo_stack.undo.clear();
+        self.undo_stack.redo.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Color;
+
+    use crate::backend::TestBackend;
+    use crate::Window;
+
+    fn window() -> Window {
+        Window::with_backend(2, 2, TestBackend::default()).unwrap()
+    }
+
+    #[test]
+    fn undo_and_redo_restore_the_states_in_order() {
+        let mut window = window();
+        for color in [Color::Red, Color::Green] {
+            window.push_undo_state();
+            window.set_pixel(0, 0, color);
+        }
+        assert!(window.undo());
+        assert_eq!(window.get_pixel(0, 0), Color::Red);
+        assert!(window.undo());
+        assert_eq!(window.get_pixel(0, 0), Color::Black);
+        assert!(!window.undo());
+        assert!(window.redo());
+        assert!(window.redo());
+        assert_eq!(window.get_pixel(0, 0), Color::Green);
+        assert!(!window.redo());
+    }
+
+    #[test]
+    fn push_clears_the_redo_states() {
+        let mut window = window();
+        window.push_undo_state();
+        window.set_pixel(0, 0, Color::Red);
+        window.undo();
+        window.push_undo_state();
+        assert!(!window.can_redo());
+    }
+
+    #[test]
+    fn limit_is_kept_by_undo_redo_cycles() {
+        let mut window = window();
+        for _ in 0..5 {
+            window.push_undo_state();
+        }
+        for _ in 0..5 {
+            window.undo();
+        }
+        window.set_undo_limit(2);
+        assert!(window.undo_stack.redo.len() <= 2);
+        for _ in 0..3 {
+            while window.redo() {}
+            assert!(window.undo_stack.undo.len() <= 2);
+            while window.undo() {}
+        }
+        assert!(window.undo_stack.undo.len() + window.undo_stack.redo.len() <= 2);
+    }
+
+    #[test]
+    fn zero_limit_keeps_no_state() {
+        let mut window = window();
+        window.set_undo_limit(0);
+        window.push_undo_state();
+        assert!(!window.can_undo());
+    }
+}
@@ -0,0 +1,131 @@
+//! Hot reloading of canvases from their files, to iterate on the art while the application runs.
+//!
+//! ```no_run
+//! use winterm::watch::AssetWatcher;
+//! use winterm::{Draw, Window};
+//!
+//! # let mut window = Window::new(36, 64)?;
+//! let mut assets = AssetWatcher::new()?;
+//! let player = assets.watch("player.wtrm")?;
+//! loop {
+//!     window.poll_events()?;
+//!     assets.reload_changed();
+//!     if assets.take_dirty(player) {
+//!         window.blit(assets.canvas(player), 0, 0);
+//!     }
+//!     window.redraw()?;
+//! }
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::canvas::{self, Canvas};
+
+/// Identifier of a canvas watched by an [`AssetWatcher`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WatchedCanvas(usize);
+
+#[derive(Debug)]
+struct Asset {
+    path: PathBuf,
+    canvas: Canvas,
+    dirty: bool,
+}
+
+/// Canvases loaded from files, reloaded when the files change.
+#[derive(Debug)]
+pub struct AssetWatcher {
+    watcher: RecommendedWatcher,
+    events: Receiver<notify::Result<notify::Event>>,
+    directories: Vec<PathBuf>,
+    assets: Vec<Asset>,
+}
+
+impl AssetWatcher {
+    /// Creates a watcher without canvases.
+    pub fn new() -> io::Result<Self> {
+        let (sender, events) = mpsc::channel();
+        Ok(AssetWatcher {
+            watcher: notify::recommended_watcher(sender).map_err(io::Error::other)?,
+            events,
+            directories: Vec::new(),
+            assets: Vec::new(),
+        })
+    }
+
+    /// Loads a canvas and watches its file, the canvas being dirty until
+    /// [`AssetWatcher::take_dirty`] is called.
+    ///
+    /// The files are read with [`Canvas::load`], or as images with the `image` feature when their
+    /// extension is the one of an image format.
+    pub fn watch(&mut self, path: impl AsRef<Path>) -> io::Result<WatchedCanvas> {
+        let path = path.as_ref().canonicalize()?;
+        let canvas = canvas::open(&path)?;
+        // The directory is watched, editors often replacing the files instead of writing them.
+        let directory = path.parent().unwrap_or(&path).to_path_buf();
+        if !self.directories.contains(&directory) {
+            self.watcher
+                .watch(&directory, RecursiveMode::NonRecursive)
+                .map_err(io::Error::other)?;
+            self.directories.push(directory);
+        }
+        self.assets.push(Asset {
+            path,
+            canvas,
+            dirty: true,
+        });
+        Ok(WatchedCanvas(self.assets.len() - 1))
+    }
+
+    /// Gets a watched canvas, as of its last reload.
+    pub fn canvas(&self, id: WatchedCanvas) -> &Canvas {
+        &self.assets[id.0].canvas
+    }
+
+    /// Reloads the canvases whose files changed since the last call, flagging them dirty, and
+    /// returns how many were reloaded.
+    ///
+    /// A canvas whose file cannot be read, e.g. while it is being written, keeps its pixels until
+    /// the next change.
+    pub fn reload_changed(&mut self) -> usize {
+        let mut changed = Vec::new();
+        for event in self.events.try_iter().flatten() {
+            if matches!(event.kind, EventKind::Access(_)) {
+                continue;
+            }
+            for path in event.paths {
+                if let Some(i) = self.assets.iter().position(|asset| asset.path == path) {
+                    if !changed.contains(&i) {
+                        changed.push(i);
+                    }
+                }
+            }
+        }
+        let mut reloaded = 0;
+        for i in changed {
+            let asset = &mut self.assets[i];
+            if let Ok(canvas) = canvas::open(&asset.path) {
+                asset.canvas = canvas;
+                asset.dirty = true;
+                reloaded += 1;
+            }
+        }
+        reloaded
+    }
+
+    /// Checks if a canvas was reloaded since it was last taken, see
+    /// [`AssetWatcher::take_dirty`].
+    pub fn is_dirty(&self, id: WatchedCanvas) -> bool {
+        self.assets[id.0].dirty
+    }
+
+    /// Checks if a canvas was reloaded since the last call, clearing its dirty flag.
+    pub fn take_dirty(&mut self, id: WatchedCanvas) -> bool {
+        std::mem::take(&mut self.assets[id.0].dirty)
+    }
+}
@@ -0,0 +1,451 @@
+//! Small widgets drawn into the pixels, for loading screens, HUDs and menus.
+
+use std::cmp;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::input::{Event, MouseButton, MouseEventKind};
+use crate::{color, font, Color, Draw, Key, Rect, Window};
+
+/// Space left for the selection marker before the menu labels.
+const MENU_INDENT: u16 = 2 * (font::GLYPH_WIDTH + 1);
+/// Side of the saturation and value square of the color picker.
+const PICKER_SQUARE: u16 = 32;
+/// Width of the hue bar of the color picker.
+const PICKER_HUE_WIDTH: u16 = 4;
+/// Side of the preview of the color picker.
+const PICKER_PREVIEW: u16 = 8;
+/// Space between the parts of the color picker.
+const PICKER_GAP: u16 = 2;
+
+/// Direction in which a bar fills.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FillDirection {
+    #[default]
+    LeftToRight,
+    RightToLeft,
+    TopToBottom,
+    BottomToTop,
+}
+
+/// Bar filled proportionally to a value between 0 and 1.
+///
+/// Covers progress bars, health bars (see [`ProgressBar::health`]) and segmented gauges
+/// (see [`ProgressBar::segments`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProgressBar {
+    rect: Rect,
+    fill_color: Color,
+    empty_color: Color,
+    low: Option<(u8, Color)>,
+    border_color: Option<Color>,
+    direction: FillDirection,
+    segments: u16,
+}
+
+impl ProgressBar {
+    /// Creates a green bar on a dark grey background, filling from left to right.
+    pub fn new(rect: Rect) -> Self {
+        ProgressBar {
+            rect,
+            fill_color: Color::Green,
+            empty_color: Color::DarkGrey,
+            low: None,
+            border_color: None,
+            direction: FillDirection::LeftToRight,
+            segments: 0,
+        }
+    }
+
+    /// Creates a health bar, green that turns red when a quarter or less remains.
+    pub fn health(rect: Rect) -> Self {
+        ProgressBar::new(rect).low(25, Color::Red)
+    }
+
+    /// Sets the color of the filled part.
+    pub fn fill_color(mut self, color: Color) -> Self {
+        self.fill_color = color;
+        self
+    }
+
+    /// Sets the color of the empty part.
+    pub fn empty_color(mut self, color: Color) -> Self {
+        self.empty_color = color;
+        self
+    }
+
+    /// Uses `color` for the filled part when the value is at or below `percent`.
+    pub fn low(mut self, percent: u8, color: Color) -> Self {
+        self.low = Some((percent, color));
+        self
+    }
+
+    /// Draws a one pixel border inside the rectangle.
+    pub fn border_color(mut self, color: Color) -> Self {
+        self.border_color = Some(color);
+        self
+    }
+
+    /// Sets the direction in which the bar fills.
+    pub fn direction(mut self, direction: FillDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Splits the bar into `segments` parts separated by one pixel, only filling whole segments.
+    pub fn segments(mut self, segments: u16) -> Self {
+        self.segments = segments;
+        self
+    }
+
+    /// Draws the bar filled according to `value`, clamped between 0 and 1.
+    pub fn draw(&self, target: &mut impl Draw, value: f32) {
+        let value = value.clamp(0., 1.);
+        let mut rect = self.rect;
+        if let Some(border_color) = self.border_color {
+            target.draw_rect(rect, border_color);
+            rect = Rect::new(
+                rect.y.saturating_add(1),
+                rect.x.saturating_add(1),
+                rect.height.saturating_sub(2),
+                rect.width.saturating_sub(2),
+            );
+        }
+        let fill_color = match self.low {
+            Some((percent, color)) if value * 100. <= percent.into() => color,
+            _ => self.fill_color,
+        };
+        let horizontal = matches!(
+            self.direction,
+            FillDirection::LeftToRight | FillDirection::RightToLeft
+        );
+        let length = if horizontal { rect.width } else { rect.height };
+        target.fill_rect(rect, self.empty_color);
+        if self.segments == 0 {
+            let filled = (length as f32 * value).round() as u16;
+            self.fill_span(target, rect, 0, filled, fill_color);
+            return;
+        }
+        let gaps = self.segments - 1;
+        let segment_length = length.saturating_sub(gaps) / self.segments;
+        let filled = (self.segments as f32 * value).floor() as u16;
+        for segment in 0..self.segments {
+            let start = segment * (segment_length + 1);
+            let color = if segment < filled {
+                fill_color
+            } else {
+                self.empty_color
+            };
+            self.fill_span(target, rect, start, segment_length, color);
+        }
+    }
+
+    /// Fills `length` pixels of `rect` starting `start` pixels away from where the bar fills.
+    fn fill_span(&self, target: &mut impl Draw, rect: Rect, start: u16, length: u16, color: Color) {
+        let length = length.min(
+            match self.direction {
+                FillDirection::LeftToRight | FillDirection::RightToLeft => rect.width,
+                FillDirection::TopToBottom | FillDirection::BottomToTop => rect.height,
+            }
+            .saturating_sub(start),
+        );
+        let span = match self.direction {
+            FillDirection::LeftToRight => Rect::new(rect.y, rect.x + start, rect.height, length),
+            FillDirection::RightToLeft => {
+                Rect::new(rect.y, rect.end_x() - start - length, rect.height, length)
+            }
+            FillDirection::TopToBottom => Rect::new(rect.y + start, rect.x, length, rect.width),
+            FillDirection::BottomToTop => {
+                Rect::new(rect.end_y() - start - length, rect.x, length, rect.width)
+            }
+        };
+        target.fill_rect(span, color);
+    }
+}
+
+/// Navigable list of labels, such as a title screen menu.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Menu {
+    labels: Vec<String>,
+    selected: usize,
+    color: Color,
+    selected_color: Color,
+}
+
+impl Menu {
+    /// Creates a white menu with a yellow selected label, the first one being selected.
+    pub fn new<I, S>(labels: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Menu {
+            labels: labels.into_iter().map(Into::into).collect(),
+            selected: 0,
+            color: Color::White,
+            selected_color: Color::Yellow,
+        }
+    }
+
+    /// Sets the color of the labels.
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Sets the color of the selected label.
+    pub fn selected_color(mut self, color: Color) -> Self {
+        self.selected_color = color;
+        self
+    }
+
+    /// Gets the labels.
+    pub fn labels(&self) -> &[String] {
+        &self.labels
+    }
+
+    /// Gets the index of the selected label.
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Selects a label, clamped to the last one.
+    pub fn select(&mut self, index: usize) {
+        self.selected = cmp::min(index, self.labels.len().saturating_sub(1));
+    }
+
+    /// Moves the selection with the Up/Down arrows or W/S keys read during the last
+    /// [`Window::poll_events`], wrapping around.
+    ///
+    /// Returns the index of the selected label if Enter or Space was read.
+    pub fn handle_keys(&mut self, window: &mut Window) -> Option<usize> {
+        if self.labels.is_empty() {
+            return None;
+        }
+        if window.get_key(Key::Up) || window.get_key(Key::Char('w')) {
+            self.selected = self
+                .selected
+                .checked_sub(1)
+                .unwrap_or(self.labels.len() - 1);
+        }
+        if window.get_key(Key::Down) || window.get_key(Key::Char('s')) {
+            self.selected = (self.selected + 1) % self.labels.len();
+        }
+        if window.get_key(Key::Enter) || window.get_key(Key::Char(' ')) {
+            return Some(self.selected);
+        }
+        None
+    }
+
+    /// Gets the height and width in pixels of the menu once drawn.
+    pub fn size(&self) -> (u16, u16) {
+        let (height, width) = font::text_size(&self.labels.join("\n"));
+        (height, width + MENU_INDENT)
+    }
+
+    /// Draws the menu with its top left corner at `y` and `x`, the selected label being marked.
+    pub fn draw(&self, target: &mut impl Draw, y: u16, x: u16) {
+        for (i, label) in self.labels.iter().enumerate() {
+            let label_y = y.saturating_add(i as u16 * (font::GLYPH_HEIGHT + 1));
+            if i == self.selected {
+                target.draw_text(label_y, x, ">", self.selected_color);
+                target.draw_text(
+                    label_y,
+                    x.saturating_add(MENU_INDENT),
+                    label,
+                    self.selected_color,
+                );
+            } else {
+                target.draw_text(label_y, x.saturating_add(MENU_INDENT), label, self.color);
+            }
+        }
+    }
+}
+
+/// Color picker, with a saturation and value square, a hue bar, a preview and the hexadecimal
+/// RGB values.
+///
+/// The arrows move the saturation and value, Page Up/Down the hue, and clicking or dragging the
+/// square or the bar sets them directly, once the mouse is captured.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ColorPicker {
+    y: u16,
+    x: u16,
+    hue: f32,
+    saturation: f32,
+    value: f32,
+}
+
+impl ColorPicker {
+    /// Creates a picker of `color` with its top left corner at `y` and `x`.
+    pub fn new(y: u16, x: u16, color: Color) -> Self {
+        let mut picker = ColorPicker {
+            y,
+            x,
+            hue: 0.,
+            saturation: 0.,
+            value: 0.,
+        };
+        picker.set_color(color);
+        picker
+    }
+
+    /// Gets the picked color.
+    pub fn color(&self) -> Color {
+        color::from_hsv(self.hue, self.saturation, self.value)
+    }
+
+    /// Picks a color.
+    pub fn set_color(&mut self, color: Color) {
+        [self.hue, self.saturation, self.value] = color::to_hsv(color);
+    }
+
+    /// Gets the height and width in pixels of the picker once drawn.
+    pub fn size(&self) -> (u16, u16) {
+        (
+            PICKER_SQUARE + PICKER_GAP + font::GLYPH_HEIGHT,
+            PICKER_SQUARE + PICKER_HUE_WIDTH + PICKER_PREVIEW + 2 * PICKER_GAP,
+        )
+    }
+
+    /// Updates the color with the keys and mouse events read during the last
+    /// [`Window::poll_events`].
+    ///
+    /// Returns the picked color if Enter or Space was read.
+    pub fn handle_input(&mut self, window: &mut Window) -> Option<Color> {
+        let step = 1. / f32::from(PICKER_SQUARE - 1);
+        if window.get_key(Key::Left) {
+            self.saturation = (self.saturation - step).max(0.);
+        }
+        if window.get_key(Key::Right) {
+            self.saturation = (self.saturation + step).min(1.);
+        }
+        if window.get_key(Key::Down) {
+            self.value = (self.value - step).max(0.);
+        }
+        if window.get_key(Key::Up) {
+            self.value = (self.value + step).min(1.);
+        }
+        let hue_step = 360. / f32::from(PICKER_SQUARE);
+        if window.get_key(Key::PageUp) {
+            self.hue = (self.hue + hue_step).rem_euclid(360.);
+        }
+        if window.get_key(Key::PageDown) {
+            self.hue = (self.hue - hue_step).rem_euclid(360.);
+        }
+        for event in &window.last_events {
+            let Event::Mouse(mouse_event) = event else {
+                continue;
+            };
+            if !matches!(
+                mouse_event.kind,
+                MouseEventKind::Down(MouseButton::Left) | MouseEventKind::Drag(MouseButton::Left)
+            ) {
+                continue;
+            }
+            if let Some((y, x)) = window.cell_to_pixel(mouse_event.column, mouse_event.row) {
+                self.click(y, x);
+            }
+        }
+        if window.get_key(Key::Enter) || window.get_key(Key::Char(' ')) {
+            return Some(self.color());
+        }
+        None
+    }
+
+    /// Sets the saturation and value, or the hue, from a pixel of the square or of the bar.
+    fn click(&mut self, y: u16, x: u16) {
+        let Some(offset_y) = y.checked_sub(self.y).filter(|&y| y < PICKER_SQUARE) else {
+            return;
+        };
+        let Some(offset_x) = x.checked_sub(self.x) else {
+            return;
+        };
+        let hue_x = PICKER_SQUARE + PICKER_GAP;
+        if offset_x < PICKER_SQUARE {
+            self.saturation = f32::from(offset_x) / f32::from(PICKER_SQUARE - 1);
+            self.value = 1. - f32::from(offset_y) / f32::from(PICKER_SQUARE - 1);
+        } else if (hue_x..hue_x + PICKER_HUE_WIDTH).contains(&offset_x) {
+            self.hue = f32::from(offset_y) * 360. / f32::from(PICKER_SQUARE);
+        }
+    }
+
+    /// Draws the picker, the saturation and value, and the hue being marked.
+    pub fn draw(&self, target: &mut impl Draw) {
+        let last = f32::from(PICKER_SQUARE - 1);
+        for offset_y in 0..PICKER_SQUARE {
+            for offset_x in 0..PICKER_SQUARE {
+                let color = color::from_hsv(
+                    self.hue,
+                    f32::from(offset_x) / last,
+                    1. - f32::from(offset_y) / last,
+                );
+                target.fill_rect(
+                    Rect::new(
+                        self.y.saturating_add(offset_y),
+                        self.x.saturating_add(offset_x),
+                        1,
+                        1,
+                    ),
+                    color,
+                );
+            }
+        }
+        let picked = self.color();
+        let marker = if color::is_light(picked) {
+            Color::Black
+        } else {
+            Color::White
+        };
+        let cursor_y = ((1. - self.value) * last).round() as u16;
+        let cursor_x = (self.saturation * last).round() as u16;
+        target.draw_rect(
+            Rect::new(
+                self.y.saturating_add(cursor_y).saturating_sub(1),
+                self.x.saturating_add(cursor_x).saturating_sub(1),
+                3,
+                3,
+            ),
+            marker,
+        );
+        let hue_x = self.x.saturating_add(PICKER_SQUARE + PICKER_GAP);
+        for offset_y in 0..PICKER_SQUARE {
+            let hue = f32::from(offset_y) * 360. / f32::from(PICKER_SQUARE);
+            target.fill_rect(
+                Rect::new(self.y.saturating_add(offset_y), hue_x, 1, PICKER_HUE_WIDTH),
+                color::from_hsv(hue, 1., 1.),
+            );
+        }
+        let hue_y = (self.hue * f32::from(PICKER_SQUARE) / 360.) as u16;
+        target.fill_rect(
+            Rect::new(
+                self.y.saturating_add(hue_y.min(PICKER_SQUARE - 1)),
+                hue_x,
+                1,
+                PICKER_HUE_WIDTH,
+            ),
+            Color::White,
+        );
+        target.fill_rect(
+            Rect::new(
+                self.y,
+                hue_x.saturating_add(PICKER_HUE_WIDTH + PICKER_GAP),
+                PICKER_PREVIEW,
+                PICKER_PREVIEW,
+            ),
+            picked,
+        );
+        let [r, g, b] = color::to_rgb(picked);
+        target.draw_text(
+            self.y.saturating_add(PICKER_SQUARE + PICKER_GAP),
+            self.x,
+            &format!("#{r:02X}{g:02X}{b:02X}"),
+            Color::White,
+        );
+    }
+}
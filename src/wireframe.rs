@@ -0,0 +1,140 @@
+//! Wireframe 3D rendering, projecting [nalgebra] points with a perspective [`Camera`].
+//!
+//! ```no_run
+//! use winterm::Color;
+//! use nalgebra::Rotation3;
+//! use winterm::wireframe::{self, Camera};
+//! use winterm::Window;
+//!
+//! let mut window = Window::new(40, 80)?;
+//! let camera = Camera::new([0., 1., 3.].into(), [0., 0., 0.].into());
+//! let rotation = Rotation3::from_euler_angles(0., 0.5, 0.);
+//! let vertices = wireframe::CUBE_VERTICES.map(|vertex| rotation * vertex);
+//! wireframe::draw_wireframe(&mut window, &camera, &vertices, &wireframe::CUBE_EDGES, Color::White);
+//! window.redraw()?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::f32::consts::FRAC_PI_3;
+
+use na::{Isometry3, Point3, Vector3};
+
+use crate::{Color, Draw};
+
+/// Vertices of a cube centered on the origin, with sides of length 1.
+pub const CUBE_VERTICES: [Point3<f32>; 8] = [
+    Point3::new(-0.5, -0.5, -0.5),
+    Point3::new(0.5, -0.5, -0.5),
+    Point3::new(0.5, 0.5, -0.5),
+    Point3::new(-0.5, 0.5, -0.5),
+    Point3::new(-0.5, -0.5, 0.5),
+    Point3::new(0.5, -0.5, 0.5),
+    Point3::new(0.5, 0.5, 0.5),
+    Point3::new(-0.5, 0.5, 0.5),
+];
+
+/// Edges of [`CUBE_VERTICES`], as pairs of indices.
+pub const CUBE_EDGES: [(usize, usize); 12] = [
+    (0, 1),
+    (1, 2),
+    (2, 3),
+    (3, 0),
+    (4, 5),
+    (5, 6),
+    (6, 7),
+    (7, 4),
+    (0, 4),
+    (1, 5),
+    (2, 6),
+    (3, 7),
+];
+
+/// Perspective camera looking at a target, the y axis being up.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Camera {
+    /// Position of the camera.
+    pub position: Point3<f32>,
+    /// Point looked at, in the center of the projection.
+    pub target: Point3<f32>,
+    /// Vertical field of view, in radians.
+    pub fov_y: f32,
+    /// Distance of the near plane, points nearer being clipped.
+    pub near: f32,
+}
+
+impl Camera {
+    /// Creates a camera with a vertical field of view of 60 degrees and a near plane at 0.1.
+    pub fn new(position: Point3<f32>, target: Point3<f32>) -> Self {
+        Camera {
+            position,
+            target,
+            fov_y: FRAC_PI_3,
+            near: 0.1,
+        }
+    }
+
+    pub(crate) fn view(&self) -> Isometry3<f32> {
+        Isometry3::look_at_rh(&self.position, &self.target, &Vector3::y())
+    }
+
+    /// Projects `point` to the `y` and `x` coordinates of a buffer of size `height` and `width`.
+    ///
+    /// Returns `None` if the point is nearer than the near plane, or behind the camera.
+    pub fn project(&self, point: &Point3<f32>, height: u16, width: u16) -> Option<(f32, f32)> {
+        let point = self.view() * point;
+        (-point.z >= self.near).then(|| self.project_view(&point, height, width))
+    }
+
+    /// Projects a point of the view space, where the camera looks towards negative z.
+    pub(crate) fn project_view(&self, point: &Point3<f32>, height: u16, width: u16) -> (f32, f32) {
+        let scale = height as f32 / 2. / (self.fov_y / 2.).tan() / -point.z;
+        (
+            height as f32 / 2. - point.y * scale,
+            width as f32 / 2. + point.x * scale,
+        )
+    }
+}
+
+/// Draws the line between two points, clipped by the near plane of the camera.
+pub fn draw_line_3d<D: Draw + ?Sized>(
+    target: &mut D,
+    camera: &Camera,
+    from: &Point3<f32>,
+    to: &Point3<f32>,
+    color: Color,
+) {
+    let view = camera.view();
+    let (mut from, mut to) = (view * from, view * to);
+    let near_z = -camera.near;
+    if from.z > near_z && to.z > near_z {
+        return;
+    }
+    if from.z > near_z {
+        from = to + (from - to) * ((near_z - to.z) / (from.z - to.z));
+    } else if to.z > near_z {
+        to = from + (to - from) * ((near_z - from.z) / (to.z - from.z));
+    }
+    let (height, width) = (target.height(), target.width());
+    let (y0, x0) = camera.project_view(&from, height, width);
+    let (y1, x1) = camera.project_view(&to, height, width);
+    target.draw_line(
+        y0.round() as i32,
+        x0.round() as i32,
+        y1.round() as i32,
+        x1.round() as i32,
+        color,
+    );
+}
+
+/// Draws the `edges` between `vertices`, each edge being a pair of indices into `vertices`.
+pub fn draw_wireframe<D: Draw + ?Sized>(
+    target: &mut D,
+    camera: &Camera,
+    vertices: &[Point3<f32>],
+    edges: &[(usize, usize)],
+    color: Color,
+) {
+    for &(from, to) in edges {
+        draw_line_3d(target, camera, &vertices[from], &vertices[to], color);
+    }
+}
@@ -0,0 +1,134 @@
+//! Drawing to an [xterm.js](https://xtermjs.org) terminal in a web page, on `wasm32` targets.
+//!
+//! The window writes its frames to the terminal through a [`CallbackBackend`], and the
+//! listeners of the page push the input to its [`EventQueue`](crate::EventQueue), the keys
+//! converted with [`key_event`]:
+//!
+//! ```ignore
+//! let backend = winterm::xterm::backend(terminal);
+//! let events = backend.events();
+//! let window = Window::with_backend(24, 40, backend)?;
+//! // In the `keydown` listener of the terminal.
+//! if let Some(key_event) = winterm::xterm::key_event(&dom_event.key(), modifiers) {
+//!     events.push(Event::Key(key_event));
+//! }
+//! ```
+//!
+//! Waiting for an event is not possible in a web page, the application is thus driven by
+//! `requestAnimationFrame` or a timer polling the events, see [`CallbackBackend`].
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::input::KeyEvent;
+#[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+use crate::CallbackBackend;
+use crate::{Key, Modifiers};
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+extern "C" {
+    /// `Terminal` of xterm.js, already opened in the page.
+    pub type Terminal;
+
+    #[wasm_bindgen(method)]
+    fn write(this: &Terminal, data: &[u8]);
+
+    #[wasm_bindgen(method, getter)]
+    fn cols(this: &Terminal) -> u16;
+
+    #[wasm_bindgen(method, getter)]
+    fn rows(this: &Terminal) -> u16;
+}
+
+/// [`Terminal`] moved into the write callback, which must be [`Send`].
+#[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+struct SendTerminal(Terminal);
+
+// SAFETY: without the `atomics` target feature, a wasm module runs on a single thread, the
+// terminal is thus never accessed from another one.
+#[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+unsafe impl Send for SendTerminal {}
+
+#[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+impl SendTerminal {
+    fn get(&self) -> &Terminal {
+        &self.0
+    }
+}
+
+/// Creates a backend writing to `terminal`, of its current size.
+///
+/// The resizes of the terminal are pushed to the [`EventQueue`](crate::EventQueue) of the
+/// backend as [`Event::Resize`](crate::input::Event::Resize).
+#[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+pub fn backend(terminal: Terminal) -> CallbackBackend {
+    let (columns, rows) = (terminal.cols(), terminal.rows());
+    let terminal = SendTerminal(terminal);
+    CallbackBackend::new(columns, rows, move |bytes| {
+        terminal.get().write(bytes);
+        Ok(())
+    })
+}
+
+/// Converts the `key` of a DOM `KeyboardEvent` pressed with `modifiers`, `None` for the keys
+/// without a [`Key`], e.g. the modifiers themselves.
+///
+/// A shifted `Tab` is converted to [`Key::BackTab`], as done by crossterm.
+pub fn key_event(key: &str, modifiers: Modifiers) -> Option<KeyEvent> {
+    let code = match key {
+        "Backspace" => Key::Backspace,
+        "Enter" => Key::Enter,
+        "ArrowLeft" => Key::Left,
+        "ArrowRight" => Key::Right,
+        "ArrowUp" => Key::Up,
+        "ArrowDown" => Key::Down,
+        "Home" => Key::Home,
+        "End" => Key::End,
+        "PageUp" => Key::PageUp,
+        "PageDown" => Key::PageDown,
+        "Tab" if modifiers.contains(Modifiers::SHIFT) => Key::BackTab,
+        "Tab" => Key::Tab,
+        "Delete" => Key::Delete,
+        "Insert" => Key::Insert,
+        "Escape" => Key::Esc,
+        "CapsLock" => Key::CapsLock,
+        "ScrollLock" => Key::ScrollLock,
+        "NumLock" => Key::NumLock,
+        "PrintScreen" => Key::PrintScreen,
+        "Pause" => Key::Pause,
+        "ContextMenu" => Key::Menu,
+        _ => {
+            let mut chars = key.chars();
+            match (chars.next()?, chars.next()) {
+                (char, None) => Key::Char(char),
+                ('F', Some(_)) => Key::F(key[1..].parse().ok()?),
+                _ => return None,
+            }
+        }
+    };
+    Some(KeyEvent::new(code, modifiers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::key_event;
+    use crate::input::KeyEvent;
+    use crate::{Key, Modifiers};
+
+    #[test]
+    fn dom_keys_convert_to_key_events() {
+        let key = |key| key_event(key, Modifiers::NONE).map(|key_event| key_event.code);
+        assert_eq!(key("a"), Some(Key::Char('a')));
+        assert_eq!(key("é"), Some(Key::Char('é')));
+        assert_eq!(key("Escape"), Some(Key::Esc));
+        assert_eq!(key("ArrowUp"), Some(Key::Up));
+        assert_eq!(key("F12"), Some(Key::F(12)));
+        assert_eq!(key("Shift"), None);
+        assert_eq!(key("Fn"), None);
+        assert_eq!(
+            key_event("Tab", Modifiers::SHIFT),
+            Some(KeyEvent::new(Key::BackTab, Modifiers::SHIFT))
+        );
+    }
+}